@@ -0,0 +1,109 @@
+//! 密钥生成耗时基准
+//!
+//! 运行：`cargo run --release --example keygen_bench [次数]`，默认每项 5 次，输出平均耗时。
+//!
+//! “基线”一列是引入小素数筛之前的做法：每个随机奇数直接做 20 轮 Miller-Rabin。
+//! 这段实现只保留在基准里用于对比，库中的密钥生成不再使用。
+
+use Crypto::ElGamal::elgamal::ElGamalKeys;
+use Crypto::Rsa::RsaPrivateKey;
+use Crypto::Rsa::rsa::RSA;
+use Crypto::numtheory::miller_rabin;
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::One;
+use rand::rngs::OsRng;
+use std::time::{Duration, Instant};
+
+const MILLER_RABIN_ROUNDS: usize = 20;
+
+fn average_ms(runs: u32, mut f: impl FnMut()) -> f64 {
+    let mut total = Duration::ZERO;
+    for _ in 0..runs {
+        let start = Instant::now();
+        f();
+        total += start.elapsed();
+    }
+    total.as_secs_f64() * 1000.0 / runs as f64
+}
+
+fn bench(name: &str, runs: u32, baseline: impl FnMut(), current: impl FnMut()) {
+    let before = average_ms(runs, baseline);
+    let after = average_ms(runs, current);
+    println!("{:<24} {:>12.1} {:>12.1} {:>8.1}x", name, before, after, before / after);
+}
+
+// 基线：随机取 bits 位的奇数，最高两位置 1，逐个做 Miller-Rabin
+fn baseline_prime(bits: usize) -> BigUint {
+    loop {
+        let mut candidate = OsRng.gen_biguint(bits as u64);
+        candidate.set_bit(0, true);
+        candidate.set_bit((bits - 1) as u64, true);
+        candidate.set_bit((bits - 2) as u64, true);
+        if miller_rabin(&candidate, MILLER_RABIN_ROUNDS, &mut OsRng) {
+            return candidate;
+        }
+    }
+}
+
+// 基线：两个素数各占一半位数，e 与 λ(n) 不互素时重来
+fn baseline_rsa(bits: usize) -> RsaPrivateKey {
+    loop {
+        let (p, q) = (baseline_prime(bits / 2), baseline_prime(bits / 2));
+        if let Some(key) = RsaPrivateKey::from_primes(p, q, BigUint::from(65537u32)) {
+            return key;
+        }
+    }
+}
+
+// 基线：随机取 bits 位的奇数 q，q 和 2q + 1 都通过 Miller-Rabin 才接受
+fn baseline_elgamal(bits: usize) -> ElGamalKeys {
+    let one = BigUint::one();
+    let (p, q) = loop {
+        let mut q = OsRng.gen_biguint(bits as u64);
+        q.set_bit(0, true);
+        q.set_bit((bits - 1) as u64, true);
+        let p = &q * 2u32 + 1u32;
+        if miller_rabin(&q, MILLER_RABIN_ROUNDS, &mut OsRng) && miller_rabin(&p, MILLER_RABIN_ROUNDS, &mut OsRng) {
+            break (p, q);
+        }
+    };
+    let g = loop {
+        let g = OsRng.gen_biguint_range(&BigUint::from(2u32), &(&p - 2u32));
+        if g.modpow(&q, &p) != one {
+            break g;
+        }
+    };
+    let x = OsRng.gen_biguint_range(&one, &q);
+    ElGamalKeys::from_private_components(p, g, x).expect("baseline parameters are valid")
+}
+
+fn main() {
+    let runs = std::env::args().nth(1).and_then(|s| s.parse().ok()).unwrap_or(5);
+    println!("每项 {} 次，平均耗时（ms）：", runs);
+    println!("{:<24} {:>12} {:>12} {:>9}", "", "基线", "当前", "加速");
+
+    for bits in [1024, 2048, 3072] {
+        bench(
+            &format!("RSA-{}", bits),
+            runs,
+            || {
+                baseline_rsa(bits);
+            },
+            || {
+                RSA::new(bits).unwrap();
+            },
+        );
+    }
+    for bits in [256, 512] {
+        bench(
+            &format!("ElGamal-{} (安全素数)", bits),
+            runs,
+            || {
+                baseline_elgamal(bits);
+            },
+            || {
+                ElGamalKeys::new(bits);
+            },
+        );
+    }
+}
@@ -1,17 +1,21 @@
-use num_bigint::{BigInt, BigUint, RandBigInt, ToBigInt};
+use num_bigint::{BigUint, RandBigInt};
 use num_integer::Integer;
 use num_traits::{One, Zero};
 use rand::rngs::OsRng;
-use sha2::{Digest, Sha256}; // 新增
+use rand::{CryptoRng, RngCore};
+use crate::hash::HashAlgorithm;
+use crate::numtheory::search::{parallel_search, SearchError, SearchOptions, SearchState};
+use crate::numtheory::sieve::Candidates;
+use crate::numtheory::{is_prime, mod_inverse};
 use std::io;
 use std::str::FromStr;
 
 // 定义 ElGamal 密钥结构
 pub struct ElGamalKeys {
-    p: BigUint,
-    g: BigUint,
-    y: BigUint,
-    x: BigUint,
+    pub(super) p: BigUint,
+    pub(super) g: BigUint,
+    pub(super) y: BigUint,
+    pub(super) x: BigUint,
 }
 
 // ElGamal 加密结果
@@ -28,11 +32,30 @@ pub struct ElGamalSignature {
 
 impl ElGamalKeys {
     pub fn new(bit_length: usize) -> Self {
-        let p = generate_safe_prime(bit_length);
+        Self::generate_with_rng(bit_length, &mut OsRng)
+    }
+
+    /// 使用指定的随机数生成器生成密钥（例如传入 DRBG 以获得可复现的密钥）
+    pub fn generate_with_rng<R: RngCore + CryptoRng + ?Sized>(bit_length: usize, rng: &mut R) -> Self {
+        let p = generate_safe_prime(bit_length, rng);
+        Self::from_safe_prime(p, rng)
+    }
+
+    /// 多线程搜索安全素数并生成密钥；options.cancel 置位后返回 `SearchError::Cancelled`
+    pub fn generate_parallel<R: RngCore + CryptoRng + ?Sized>(
+        bit_length: usize,
+        options: &mut SearchOptions<'_>,
+        rng: &mut R,
+    ) -> Result<Self, SearchError> {
+        let p = parallel_search(options, rng, |worker, state| search_safe_prime(bit_length, worker, state))?;
+        Ok(Self::from_safe_prime(p, rng))
+    }
+
+    // 由安全素数 p 选取生成元和私钥
+    fn from_safe_prime<R: RngCore + ?Sized>(p: BigUint, rng: &mut R) -> Self {
         let q = (&p - BigUint::one()) / BigUint::from(2u32);
-        let g = find_generator(&p, &q);
+        let g = find_generator(&p, &q, rng);
 
-        let mut rng = OsRng;
         let x = rng.gen_biguint_range(&BigUint::one(), &q);
         let y = g.modpow(&x, &p);
 
@@ -71,6 +94,30 @@ impl ElGamalKeys {
         }
     }
 
+    /// 由已解析的 p、g、y 构造公钥，要求 1 < g < p 且 1 < y < p
+    pub fn from_public_components(p: BigUint, g: BigUint, y: BigUint) -> Option<Self> {
+        let one = BigUint::one();
+        if g <= one || g >= p || y <= one || y >= p {
+            return None;
+        }
+        Some(ElGamalKeys { p, g, y, x: BigUint::zero() })
+    }
+
+    /// 由已解析的 p、g、x 构造私钥，要求 1 < g < p 且 0 < x < p - 1，公钥 y 由 g^x mod p 计算
+    pub fn from_private_components(p: BigUint, g: BigUint, x: BigUint) -> Option<Self> {
+        let one = BigUint::one();
+        if g <= one || g >= p || x.is_zero() || x >= &p - &one {
+            return None;
+        }
+        let y = g.modpow(&x, &p);
+        Some(ElGamalKeys { p, g, y, x })
+    }
+
+    /// 是否持有私钥
+    pub fn has_private_key(&self) -> bool {
+        !self.x.is_zero()
+    }
+
     pub fn public_key(&self) -> (String, String, String) {
         (
             self.p.to_str_radix(10),
@@ -138,29 +185,39 @@ impl ElGamalKeys {
     }
 }
 
-fn generate_safe_prime(bit_length: usize) -> BigUint {
-    let mut rng = OsRng;
-    let mut candidate: BigUint;
+// 生成安全素数 p = 2q + 1，q 为 bit_length 位的素数
+//
+// 增量筛同时排除 q 和 2q + 1 含小素因子的候选，再依次对 q、p 做 Miller-Rabin。
+fn generate_safe_prime<R: RngCore + ?Sized>(bit_length: usize, rng: &mut R) -> BigUint {
+    search_safe_prime(bit_length, rng, &SearchState::new()).expect("search is never stopped")
+}
 
-    loop {
-        candidate = rng.gen_biguint(bit_length as u64);
-        if candidate.is_even() {
-            candidate += BigUint::one();
+// 同 generate_safe_prime，state 被停止时返回 None，用于多线程搜索
+fn search_safe_prime<R: RngCore + ?Sized>(bit_length: usize, rng: &mut R, state: &SearchState) -> Option<BigUint> {
+    while !state.is_stopped() {
+        let mut base = rng.gen_biguint(bit_length as u64);
+        if base.is_even() {
+            base += BigUint::one();
         }
-        if candidate.bits() < bit_length as u64 {
-            candidate.set_bit((bit_length - 1) as u64, true);
+        if base.bits() < bit_length as u64 {
+            base.set_bit((bit_length - 1) as u64, true);
         }
 
-        let p = &candidate * BigUint::from(2u32) + BigUint::one();
-
-        if is_prime(&candidate, 20) && is_prime(&p, 20) {
-            return p;
+        for candidate in Candidates::new(base, true) {
+            if candidate.bits() != bit_length as u64 || state.is_stopped() {
+                break;
+            }
+            state.record_candidate();
+            let p = &candidate * BigUint::from(2u32) + BigUint::one();
+            if is_prime(&candidate, 20, rng) && is_prime(&p, 20, rng) {
+                return Some(p);
+            }
         }
     }
+    None
 }
 
-fn find_generator(p: &BigUint, q: &BigUint) -> BigUint {
-    let mut rng = OsRng;
+fn find_generator<R: RngCore + ?Sized>(p: &BigUint, q: &BigUint, rng: &mut R) -> BigUint {
     let mut candidate: BigUint;
 
     loop {
@@ -175,87 +232,14 @@ fn find_generator(p: &BigUint, q: &BigUint) -> BigUint {
     }
 }
 
-fn is_prime(n: &BigUint, k: usize) -> bool {
-    if n <= &BigUint::one() {
-        return false;
-    }
-    if n == &BigUint::from(2u8) || n == &BigUint::from(3u8) {
-        return true;
-    }
-    if n.is_even() {
-        return false;
-    }
-
-    let mut d = n - BigUint::one();
-    let mut s = 0;
-    while d.is_even() {
-        d /= BigUint::from(2u8);
-        s += 1;
-    }
-
-    let mut rng = OsRng;
-    for _ in 0..k {
-        let a = rng.gen_biguint_range(&BigUint::from(2u8), &(n - BigUint::one()));
-        let mut x = a.modpow(&d, n);
-
-        if x == BigUint::one() || x == n - BigUint::one() {
-            continue;
-        }
-
-        let mut found = false;
-        for _ in 0..s - 1 {
-            x = x.modpow(&BigUint::from(2u8), n);
-            if x == n - BigUint::one() {
-                found = true;
-                break;
-            }
-        }
-
-        if !found {
-            return false;
-        }
-    }
-
-    true
-}
-
-fn mod_inverse(a: &BigUint, m: &BigUint) -> Option<BigUint> {
-    let a = a.to_bigint().unwrap();
-    let m = m.to_bigint().unwrap();
-
-    let (mut old_r, mut r) = (a.clone(), m.clone());
-    let (mut old_s, mut s) = (BigInt::from(1), BigInt::from(0));
-
-    while r != BigInt::from(0) {
-        let quotient = &old_r / &r;
-
-        let new_r = &old_r - &quotient * &r;
-        old_r = r;
-        r = new_r;
-
-        let new_s = &old_s - &quotient * &s;
-        old_s = s;
-        s = new_s;
-    }
-
-    if old_r != BigInt::from(1) {
-        return None;
-    }
-
-    let result = if old_s < BigInt::from(0) {
-        old_s + m
-    } else {
-        old_s
-    };
-
-    Some(result.to_biguint().unwrap())
-}
-
 // SHA256 -> BigUint
 pub fn sha256_to_biguint(input: &str) -> BigUint {
-    let mut hasher = Sha256::new();
-    hasher.update(input.as_bytes());
-    let hash = hasher.finalize();
+    hash_to_biguint(HashAlgorithm::Sha256, input)
+}
+
+// 任意摘要算法（包括 SHA-3）-> BigUint，用于签名时选择摘要算法
+pub fn hash_to_biguint(algorithm: HashAlgorithm, input: &str) -> BigUint {
+    let hash = algorithm.digest(input.as_bytes());
     BigUint::from_bytes_be(&hash)
 }
 
@@ -0,0 +1,140 @@
+//! ElGamal 密钥的 JWK 表示（私有扩展，JOSE 未注册 ElGamal 密钥类型）
+//!
+//! 按 RFC 7517 §4.1 的约定使用不会与注册值冲突的 "kty"，成员均为 Base64urlUInt：
+//!
+//! ```text
+//! {"kty":"ElGamal","p":"...","g":"...","y":"..."}           公钥
+//! {"kty":"ElGamal","p":"...","g":"...","y":"...","x":"..."} 私钥
+//! ```
+//!
+//! p 为大素数，g 为生成元，y = g^x mod p。导入私钥时 y 可以省略，给出时必须与 x 一致。
+
+use super::elgamal::ElGamalKeys;
+use crate::encoding::jwk::Jwk;
+
+/// ElGamal 的 JWK 密钥类型
+pub const JWK_KTY: &str = "ElGamal";
+
+impl ElGamalKeys {
+    /// 导出公钥 JWK
+    pub fn to_public_jwk(&self) -> String {
+        self.public_jwk().to_json()
+    }
+
+    /// 导出私钥 JWK，没有私钥（由 `from_public_params` 创建）时返回 None
+    pub fn to_jwk(&self) -> Option<String> {
+        if !self.has_private_key() {
+            return None;
+        }
+        let mut jwk = self.public_jwk();
+        jwk.set_uint("x", &self.x);
+        Some(jwk.to_json())
+    }
+
+    /// 导入 JWK 公钥或私钥，要求 1 < g < p、1 < y < p，私钥另要求 0 < x < p - 1
+    pub fn from_jwk(text: &str) -> Option<Self> {
+        let jwk = Jwk::parse(text)?;
+        if jwk.kty() != JWK_KTY {
+            return None;
+        }
+        let (p, g) = (jwk.uint("p")?, jwk.uint("g")?);
+        let y = if jwk.contains("y") { Some(jwk.uint("y")?) } else { None };
+
+        if jwk.contains("x") {
+            let keys = Self::from_private_components(p, g, jwk.uint("x")?)?;
+            if y.is_some_and(|y| y != keys.y) {
+                return None;
+            }
+            return Some(keys);
+        }
+
+        Self::from_public_components(p, g, y?)
+    }
+
+    fn public_jwk(&self) -> Jwk {
+        let mut jwk = Jwk::new(JWK_KTY);
+        jwk.set_uint("p", &self.p);
+        jwk.set_uint("g", &self.g);
+        jwk.set_uint("y", &self.y);
+        jwk
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drbg::HmacDrbg;
+    use num_bigint::BigUint;
+
+    fn test_keys() -> ElGamalKeys {
+        let mut rng = HmacDrbg::new(&[0x45; 32], b"nonce", b"elgamal jwk test").unwrap();
+        ElGamalKeys::generate_with_rng(256, &mut rng)
+    }
+
+    #[test]
+    fn private_key_round_trip() {
+        let keys = test_keys();
+        let text = keys.to_jwk().unwrap();
+        let jwk = Jwk::parse(&text).unwrap();
+        assert_eq!(jwk.kty(), JWK_KTY);
+        assert_eq!(jwk.uint("x"), Some(keys.x.clone()));
+
+        let imported = ElGamalKeys::from_jwk(&text).unwrap();
+        assert!(imported.has_private_key());
+        assert_eq!(imported.private_key(), keys.private_key());
+        assert_eq!(imported.public_key(), keys.public_key());
+        assert_eq!(imported.to_jwk().unwrap(), text);
+
+        // y 可以省略，给出时必须等于 g^x mod p
+        let mut without_y = Jwk::new(JWK_KTY);
+        for name in ["p", "g", "x"] {
+            without_y.set(name, jwk.get(name).unwrap().clone());
+        }
+        assert_eq!(ElGamalKeys::from_jwk(&without_y.to_json()).unwrap().to_jwk().unwrap(), text);
+        let mut wrong_y = jwk.clone();
+        wrong_y.set_uint("y", &(&keys.y + 1u32));
+        assert!(ElGamalKeys::from_jwk(&wrong_y.to_json()).is_none());
+    }
+
+    #[test]
+    fn public_key_round_trip() {
+        let keys = test_keys();
+        let text = keys.to_public_jwk();
+        assert!(!Jwk::parse(&text).unwrap().contains("x"));
+
+        let imported = ElGamalKeys::from_jwk(&text).unwrap();
+        assert!(!imported.has_private_key());
+        assert!(imported.to_jwk().is_none());
+        assert_eq!(imported.public_key(), keys.public_key());
+        assert_eq!(imported.to_public_jwk(), text);
+
+        let message = BigUint::from(42u32);
+        assert_eq!(keys.decrypt(&imported.encrypt(&message)), message);
+    }
+
+    #[test]
+    fn rejects_invalid_keys() {
+        let jwk = Jwk::parse(&test_keys().to_jwk().unwrap()).unwrap();
+        let p = jwk.uint("p").unwrap();
+        let cases: [(&str, BigUint); 4] = [
+            ("g", BigUint::from(1u32)),
+            ("g", p.clone()),
+            ("x", BigUint::from(0u32)),
+            ("x", &p - 1u32),
+        ];
+        for (name, value) in cases {
+            let mut modified = jwk.clone();
+            modified.set_uint(name, &value);
+            assert!(ElGamalKeys::from_jwk(&modified.to_json()).is_none(), "{}", name);
+        }
+
+        let mut rsa = jwk.clone();
+        rsa.set("kty", crate::encoding::json::JsonValue::String("RSA".to_string()));
+        assert!(ElGamalKeys::from_jwk(&rsa.to_json()).is_none());
+        let mut public = Jwk::new(JWK_KTY);
+        for name in ["p", "g"] {
+            public.set(name, jwk.get(name).unwrap().clone());
+        }
+        assert!(ElGamalKeys::from_jwk(&public.to_json()).is_none());
+    }
+}
@@ -1,3 +1,6 @@
 pub mod elgamal;
+pub mod jwk;
+pub mod params;
+pub mod pkcs8;
 
 // 如果需要，添加其他导出 
\ No newline at end of file
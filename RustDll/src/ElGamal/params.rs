@@ -0,0 +1,309 @@
+//! 可证明的 ElGamal/DSA 域参数 (FIPS 186-4 A.1.2.1.2 Shawe-Taylor 构造，A.2.3 可验证的生成元)
+//!
+//! p = 2t·q·p0 + 1，q 为 N 位素数，g 的阶为 q。p - 1 的一半是 q 的倍数，
+//! 因此用这组参数生成的密钥同样可以做本模块的 ElGamal 签名。
+//! 发布初始种子、计数器和生成元编号后，第三方可以用 `verify_provable` 重新构造并核对 p、q、g。
+
+use super::elgamal::ElGamalKeys;
+use crate::hash::HashAlgorithm;
+use crate::numtheory::provable::{hash_integer, st_random_prime, ProvablePrime};
+use num_bigint::{BigUint, RandBigInt};
+use num_integer::Integer;
+use num_traits::One;
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+
+// 生成元推导时附加在种子后的标签 (FIPS 186-4 A.2.3)
+const GGEN: &[u8] = b"ggen";
+
+/// 域参数 (p, q, g)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DomainParameters {
+    p: BigUint,
+    q: BigUint,
+    g: BigUint,
+}
+
+/// 域参数的生成记录，与域参数一起发布即可供第三方核对
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DomainParameterSeed {
+    /// 初始种子，长度不少于 N 位
+    pub first_seed: Vec<u8>,
+    /// 构造 p 时的生成计数器
+    pub p_counter: usize,
+    /// 构造 q 时的生成计数器
+    pub q_counter: usize,
+    /// 生成元编号，同一组 p、q 可以用不同编号得到不同的生成元
+    pub index: u8,
+}
+
+// 构造过程的中间结果：生成元需要用到 p、q 构造结束时的种子
+struct Construction {
+    p: ProvablePrime,
+    q: ProvablePrime,
+}
+
+impl DomainParameters {
+    /// 由种子构造 L 位的 p、N 位的 q，并按编号 index 推导生成元 g
+    ///
+    /// 要求 2 <= N <= 摘要位数、L >= 2N + 8 且种子不少于 N 位；参数不符合要求，
+    /// 或者计数器超出上限（换一个种子即可）时返回 None。
+    pub fn generate_provable(
+        l: usize,
+        n: usize,
+        hash: HashAlgorithm,
+        first_seed: &[u8],
+        index: u8,
+    ) -> Option<(Self, DomainParameterSeed)> {
+        let Construction { p, q } = construct(l, n, hash, first_seed)?;
+        let g = canonical_generator(hash, &p.prime, &q.prime, first_seed, &p.seed, &q.seed, index)?;
+        let record = DomainParameterSeed {
+            first_seed: first_seed.to_vec(),
+            p_counter: p.counter,
+            q_counter: q.counter,
+            index,
+        };
+        Some((DomainParameters { p: p.prime, q: q.prime, g }, record))
+    }
+
+    /// 使用指定的随机数生成器生成 N 位的种子；构造失败时自动换种子重试
+    pub fn generate_provable_with_rng<R: RngCore + CryptoRng + ?Sized>(
+        l: usize,
+        n: usize,
+        hash: HashAlgorithm,
+        rng: &mut R,
+    ) -> Option<(Self, DomainParameterSeed)> {
+        if !valid_lengths(l, n, hash) {
+            return None;
+        }
+        let mut seed = vec![0u8; n.div_ceil(8)];
+        loop {
+            rng.fill_bytes(&mut seed);
+            if let Some(result) = Self::generate_provable(l, n, hash, &seed, 1) {
+                return Some(result);
+            }
+        }
+    }
+
+    /// 由已解析的 p、q、g 构造域参数，要求 1 < q < p 且 1 < g < p（不检查素性，可用 `verify_provable` 核对）
+    pub fn from_components(p: BigUint, q: BigUint, g: BigUint) -> Option<Self> {
+        let one = BigUint::one();
+        if q <= one || q >= p || g <= one || g >= p {
+            return None;
+        }
+        Some(DomainParameters { p, q, g })
+    }
+
+    /// 用生成记录重新构造 p、q、g，与本参数及记录中的计数器一致时返回 true
+    pub fn verify_provable(&self, hash: HashAlgorithm, record: &DomainParameterSeed) -> bool {
+        let (l, n) = (self.p.bits() as usize, self.q.bits() as usize);
+        let Some(Construction { p, q }) = construct(l, n, hash, &record.first_seed) else {
+            return false;
+        };
+        if p.prime != self.p || q.prime != self.q || p.counter != record.p_counter || q.counter != record.q_counter {
+            return false;
+        }
+        canonical_generator(hash, &p.prime, &q.prime, &record.first_seed, &p.seed, &q.seed, record.index)
+            .is_some_and(|g| g == self.g)
+    }
+
+    pub fn p(&self) -> &BigUint {
+        &self.p
+    }
+
+    pub fn q(&self) -> &BigUint {
+        &self.q
+    }
+
+    pub fn g(&self) -> &BigUint {
+        &self.g
+    }
+}
+
+impl ElGamalKeys {
+    /// 在给定域参数下生成密钥，私钥 x 取自 [1, q)
+    pub fn from_domain_parameters(params: &DomainParameters) -> Self {
+        Self::from_domain_parameters_with_rng(params, &mut OsRng)
+    }
+
+    /// 使用指定的随机数生成器在给定域参数下生成密钥
+    pub fn from_domain_parameters_with_rng<R: RngCore + CryptoRng + ?Sized>(params: &DomainParameters, rng: &mut R) -> Self {
+        let x = rng.gen_biguint_range(&BigUint::one(), &params.q);
+        let y = params.g.modpow(&x, &params.p);
+        ElGamalKeys { p: params.p.clone(), g: params.g.clone(), y, x }
+    }
+}
+
+fn valid_lengths(l: usize, n: usize, hash: HashAlgorithm) -> bool {
+    !hash.is_xof() && n >= 2 && n <= hash.output_len() * 8 && l >= 2 * n + 8
+}
+
+// A.1.2.1.2：先构造 N 位的 q 和约 L/2 位的 p0，再在 2t·q·p0 + 1 中寻找可由 Pocklington 定理证明的素数 p
+fn construct(l: usize, n: usize, hash: HashAlgorithm, first_seed: &[u8]) -> Option<Construction> {
+    if !valid_lengths(l, n, hash) || first_seed.len() * 8 < n {
+        return None;
+    }
+
+    let q = st_random_prime(hash, n, first_seed)?;
+    let ProvablePrime { prime: p0, seed: mut pseed, mut counter } =
+        st_random_prime(hash, l.div_ceil(2) + 1, &q.seed)?;
+
+    let outlen = hash.output_len() * 8;
+    let iterations = l.div_ceil(outlen) - 1;
+    let old_counter = counter;
+
+    let top = BigUint::one() << (l - 1);
+    let x = &top + hash_integer(hash, &mut pseed, iterations) % &top;
+
+    let two_q_p0: BigUint = (&q.prime * &p0) << 1;
+    let mut t = x.div_ceil(&two_q_p0);
+    loop {
+        if &two_q_p0 * &t + 1u32 > &top << 1 {
+            t = top.div_ceil(&two_q_p0);
+        }
+        let p = &two_q_p0 * &t + 1u32;
+        counter += 1;
+
+        let a = hash_integer(hash, &mut pseed, iterations);
+        let a = (a % (&p - 3u32)) + 2u32;
+        let z = a.modpow(&((&t * &q.prime) << 1), &p);
+        if (&z - 1u32).gcd(&p).is_one() && z.modpow(&p0, &p).is_one() {
+            return Some(Construction { p: ProvablePrime { prime: p, seed: pseed, counter }, q });
+        }
+        if counter > 4 * l + old_counter {
+            return None;
+        }
+        t += 1u32;
+    }
+}
+
+// A.2.3：g = Hash(domain_parameter_seed || "ggen" || index || count)^((p-1)/q) mod p，
+// 其中 domain_parameter_seed = firstseed || pseed || qseed
+fn canonical_generator(
+    hash: HashAlgorithm,
+    p: &BigUint,
+    q: &BigUint,
+    first_seed: &[u8],
+    p_seed: &[u8],
+    q_seed: &[u8],
+    index: u8,
+) -> Option<BigUint> {
+    let e = (p - 1u32) / q;
+    let mut u = [first_seed, p_seed, q_seed, GGEN, &[index]].concat();
+    let prefix_len = u.len();
+    for count in 1..=u16::MAX {
+        u.truncate(prefix_len);
+        u.extend_from_slice(&count.to_be_bytes());
+        let w = BigUint::from_bytes_be(&hash.digest(&u));
+        let g = w.modpow(&e, p);
+        if g >= BigUint::from(2u8) {
+            return Some(g);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::numtheory::is_probable_prime;
+
+    fn seed() -> Vec<u8> {
+        (1..=20).collect()
+    }
+
+    fn number(hex_str: &str) -> BigUint {
+        BigUint::parse_bytes(hex_str.as_bytes(), 16).unwrap()
+    }
+
+    #[test]
+    fn matches_reference_construction() {
+        // L = 512、N = 160、SHA-256、种子 01 02 .. 14、index = 1；
+        // 期望值由按 FIPS 186-4 A.1.2.1.2 与 A.2.3 独立实现的脚本得到
+        let (params, record) = DomainParameters::generate_provable(512, 160, HashAlgorithm::Sha256, &seed(), 1).unwrap();
+        assert_eq!(
+            params.p,
+            number(concat!(
+                "e950ac3df6df787497896b542825a62a1ddc3a31c79c29c1d963ee42bfbaefd0",
+                "d018bd914b5a42a4fa03cea926a5f454fc2a2b895dd7a304983ce73c88501adb",
+            ))
+        );
+        assert_eq!(params.q, number("b6d8dda21958e6008e405e8df3e69b0c1ed6907b"));
+        assert_eq!(
+            params.g,
+            number(concat!(
+                "a94e55da096a20da01e962340abddde9099876933af193056874b58658697ab3",
+                "f1122043253bfac500d06ae9fe20a8fd22fc1d413c019f86edb31d284dc870fa",
+            ))
+        );
+        assert_eq!(record.p_counter, 536);
+        assert_eq!(record.q_counter, 109);
+        assert_eq!(record.index, 1);
+    }
+
+    #[test]
+    fn same_seed_gives_same_parameters() {
+        let (params, record) = DomainParameters::generate_provable(768, 256, HashAlgorithm::Sha256, &[0x5a; 32], 7).unwrap();
+        assert_eq!(
+            DomainParameters::generate_provable(768, 256, HashAlgorithm::Sha256, &[0x5a; 32], 7),
+            Some((params.clone(), record))
+        );
+
+        assert_eq!(params.p.bits(), 768);
+        assert_eq!(params.q.bits(), 256);
+        assert!(is_probable_prime(&params.p) && is_probable_prime(&params.q));
+        assert!((&params.p - 1u32).is_multiple_of(&params.q));
+        // g 的阶为 q
+        assert!(params.g.modpow(&params.q, &params.p).is_one());
+        assert!(!params.g.is_one());
+
+        let (other, _) = DomainParameters::generate_provable(768, 256, HashAlgorithm::Sha256, &[0xa5; 32], 7).unwrap();
+        assert_ne!(other.p, params.p);
+    }
+
+    #[test]
+    fn verify_provable_checks_every_field() {
+        let hash = HashAlgorithm::Sha256;
+        let (params, record) = DomainParameters::generate_provable(512, 160, hash, &seed(), 1).unwrap();
+        assert!(params.verify_provable(hash, &record));
+        assert!(!params.verify_provable(HashAlgorithm::Sha384, &record));
+
+        let mut tampered = record.clone();
+        tampered.p_counter += 1;
+        assert!(!params.verify_provable(hash, &tampered));
+
+        let mut tampered = record.clone();
+        tampered.q_counter += 1;
+        assert!(!params.verify_provable(hash, &tampered));
+
+        let mut tampered = record.clone();
+        tampered.first_seed[0] ^= 0x01;
+        assert!(!params.verify_provable(hash, &tampered));
+
+        // 换一个编号得到同一组 p、q 下的另一个生成元，只能用自己的记录核对
+        let mut tampered = record.clone();
+        tampered.index = 2;
+        assert!(!params.verify_provable(hash, &tampered));
+        let (second, second_record) = DomainParameters::generate_provable(512, 160, hash, &seed(), 2).unwrap();
+        assert_eq!((&second.p, &second.q), (&params.p, &params.q));
+        assert_ne!(second.g, params.g);
+        assert!(second.verify_provable(hash, &second_record));
+    }
+
+    #[test]
+    fn keys_from_domain_parameters() {
+        let (params, _) = DomainParameters::generate_provable(512, 160, HashAlgorithm::Sha256, &seed(), 1).unwrap();
+        let keys = ElGamalKeys::from_domain_parameters(&params);
+        assert!(keys.x >= BigUint::one() && keys.x < params.q);
+        assert_eq!(keys.y, params.g.modpow(&keys.x, &params.p));
+    }
+
+    #[test]
+    fn rejects_invalid_lengths() {
+        let hash = HashAlgorithm::Sha256;
+        assert!(DomainParameters::generate_provable(512, 257, hash, &[0u8; 40], 1).is_none());
+        assert!(DomainParameters::generate_provable(327, 160, hash, &seed(), 1).is_none());
+        assert!(DomainParameters::generate_provable(512, 160, hash, &seed()[..19], 1).is_none());
+        assert!(DomainParameters::generate_provable(512, 160, HashAlgorithm::Shake256, &seed(), 1).is_none());
+    }
+}
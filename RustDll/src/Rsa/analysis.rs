@@ -0,0 +1,303 @@
+//! RSA 密码分析工具（教学演示与密钥审计）
+//!
+//! - `wiener`：私钥指数过小（d < n^(1/4) / 3）时由 e/n 的连分数恢复私钥；
+//! - `fermat_factor`：p、q 过于接近时从 √n 附近分解模数；
+//! - `common_modulus`：同一消息用同一模数、互素的两个公钥指数加密时直接恢复明文；
+//! - `hastad_broadcast`：同一消息用小公钥指数 e 发给 e 个接收方时由中国剩余定理恢复明文；
+//! - `batch_gcd`：在大量模数中找出共享素因子的模数。
+//!
+//! 明文相关的攻击只适用于无填充的教科书 RSA；OAEP / PKCS#1 v1.5 的随机填充使它们失效。
+//! 审计 `RSA::new` 生成的密钥时，可以把 `RSA::n`、`RSA::e` 直接传给这里的函数，任何一项成功都说明密钥不安全。
+
+use super::private_key::RsaPrivateKey;
+use crate::numtheory::{crt, egcd, mod_inverse};
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer;
+use num_traits::{CheckedSub, One, Zero};
+
+/// Wiener 攻击：d < n^(1/4) / 3 时 k/d 必然是 e/n 的某个渐近分数，逐个尝试即可恢复私钥
+///
+/// 返回的私钥满足 e·d ≡ 1 (mod φ(n))，d 与原私钥相同；攻击失败时返回 None。
+pub fn wiener(n: &BigUint, e: &BigUint) -> Option<RsaPrivateKey> {
+    let (mut numerator, mut denominator) = (e.clone(), n.clone());
+    // 渐近分数 k/d 的分子、分母递推：h_i = a_i·h_(i-1) + h_(i-2)
+    let (mut k_prev, mut k) = (BigUint::zero(), BigUint::one());
+    let (mut d_prev, mut d) = (BigUint::one(), BigUint::zero());
+
+    while !denominator.is_zero() {
+        let (quotient, remainder) = numerator.div_rem(&denominator);
+        (numerator, denominator) = (denominator, remainder);
+
+        let next_k = &quotient * &k + &k_prev;
+        let next_d = &quotient * &d + &d_prev;
+        (k_prev, k) = (k, next_k);
+        (d_prev, d) = (d, next_d);
+
+        if k.is_zero() {
+            continue;
+        }
+        if let Some((p, q)) = factor_from_phi_candidate(n, e, &k, &d) {
+            return RsaPrivateKey::from_components(n.clone(), e.clone(), d, p, q);
+        }
+    }
+    None
+}
+
+// 若 (e·d - 1) / k 是 φ(n)，则 p、q 是 x² - (n - φ + 1)x + n = 0 的两个根
+fn factor_from_phi_candidate(n: &BigUint, e: &BigUint, k: &BigUint, d: &BigUint) -> Option<(BigUint, BigUint)> {
+    let ed_minus_one = (e * d).checked_sub(&BigUint::one())?;
+    let (phi, remainder) = ed_minus_one.div_rem(k);
+    if !remainder.is_zero() {
+        return None;
+    }
+    let sum = (n + 1u32).checked_sub(&phi)?;
+    let discriminant = (&sum * &sum).checked_sub(&(n << 2))?;
+    let root = exact_sqrt(&discriminant)?;
+    let p = (&sum + &root) >> 1;
+    let q = sum.checked_sub(&root)? >> 1;
+    (&p * &q == *n && q > BigUint::one()).then_some((p, q))
+}
+
+/// Fermat 分解：从 a = ⌈√n⌉ 开始寻找使 a² - n = b² 的 a，得到 n = (a - b)(a + b)
+///
+/// |p - q| 越小所需的步数越少；n 为偶数、不大于 1，或 max_iterations 步内没有找到时返回 None。
+/// 成功时返回 (p, q)，p <= q。
+pub fn fermat_factor(n: &BigUint, max_iterations: usize) -> Option<(BigUint, BigUint)> {
+    if n <= &BigUint::one() || n.is_even() {
+        return None;
+    }
+    let mut a = n.sqrt();
+    if &a * &a < *n {
+        a += 1u32;
+    }
+    let mut b_squared = &a * &a - n;
+    for _ in 0..max_iterations {
+        if let Some(b) = exact_sqrt(&b_squared) {
+            let p = &a - &b;
+            if p > BigUint::one() {
+                return Some((p, a + b));
+            }
+            return None;
+        }
+        // (a + 1)² - n = a² - n + 2a + 1
+        b_squared += (&a << 1) + 1u32;
+        a += 1u32;
+    }
+    None
+}
+
+/// 共模攻击：c1 = m^e1 mod n、c2 = m^e2 mod n 且 gcd(e1, e2) = 1 时，由 a·e1 + b·e2 = 1 得到 m = c1^a · c2^b mod n
+///
+/// 指数不互素，或需要取逆的密文与 n 不互素时返回 None。
+pub fn common_modulus(n: &BigUint, e1: &BigUint, c1: &BigUint, e2: &BigUint, c2: &BigUint) -> Option<BigUint> {
+    let (g, a, b) = egcd(&BigInt::from(e1.clone()), &BigInt::from(e2.clone()));
+    if !g.is_one() {
+        return None;
+    }
+    let left = signed_pow(c1, &a, n)?;
+    let right = signed_pow(c2, &b, n)?;
+    Some(left * right % n)
+}
+
+// c^exponent mod n，负指数先对 c 取逆
+fn signed_pow(c: &BigUint, exponent: &BigInt, n: &BigUint) -> Option<BigUint> {
+    let magnitude = exponent.magnitude();
+    if exponent.sign() == Sign::Minus {
+        Some(mod_inverse(c, n)?.modpow(magnitude, n))
+    } else {
+        Some(c.modpow(magnitude, n))
+    }
+}
+
+/// Håstad 广播攻击：同一明文 m 以公钥指数 e 分别用不同模数加密，收集到至少 e 个 (密文, 模数) 后，
+/// 由中国剩余定理得到 m^e mod ∏n_i；由于 m^e < ∏n_i，开 e 次方即得 m
+///
+/// 少于 e 组、模数之间方程组无解（同一明文时不会出现），或结果不是整 e 次幂时返回 None。
+pub fn hastad_broadcast(e: u32, ciphertexts: &[(BigUint, BigUint)]) -> Option<BigUint> {
+    if e < 2 || ciphertexts.len() < e as usize {
+        return None;
+    }
+    let (combined, _) = crt(ciphertexts)?;
+    let m = combined.nth_root(e);
+    (m.pow(e) == combined).then_some(m)
+}
+
+/// 批量 GCD (Bernstein)：用乘积树和余数树在准线性时间内求出每个模数与其余模数之积的最大公因子
+///
+/// 返回共享素因子的模数的下标和求得的公因子。公因子等于模数本身时，说明该模数与其他模数重复
+/// 或两个素因子都被共享，需要逐对求 gcd 进一步区分；不大于 1 的模数会被忽略。
+pub fn batch_gcd(moduli: &[BigUint]) -> Vec<(usize, BigUint)> {
+    let one = BigUint::one();
+    let leaves: Vec<BigUint> = moduli.iter().map(|n| if n > &one { n.clone() } else { one.clone() }).collect();
+    if leaves.len() < 2 {
+        return Vec::new();
+    }
+
+    // 乘积树：tree[0] 为叶子，每一层是上一层相邻两项的乘积，最后一层只有总乘积
+    let mut tree = vec![leaves];
+    while let Some(level) = tree.last().filter(|level| level.len() > 1) {
+        let parents = level.chunks(2).map(|pair| pair.iter().product()).collect();
+        tree.push(parents);
+    }
+
+    // 余数树：自顶向下求 P mod n²
+    let mut remainders = tree.pop().unwrap_or_default();
+    while let Some(level) = tree.pop() {
+        remainders = level
+            .iter()
+            .enumerate()
+            .map(|(i, node)| &remainders[i / 2] % (node * node))
+            .collect();
+        if tree.is_empty() {
+            return remainders
+                .iter()
+                .zip(&level)
+                .enumerate()
+                .filter_map(|(i, (remainder, n))| {
+                    let g = (remainder / n).gcd(n);
+                    (!g.is_one()).then_some((i, g))
+                })
+                .collect();
+        }
+    }
+    Vec::new()
+}
+
+// 完全平方数的平方根，不是完全平方数时返回 None
+fn exact_sqrt(n: &BigUint) -> Option<BigUint> {
+    let root = n.sqrt();
+    (&root * &root == *n).then_some(root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rsa::padding::pkcs1v15_encode;
+    use crate::Rsa::rsa::RSA;
+    use crate::drbg::HmacDrbg;
+    use crate::numtheory::is_probable_prime;
+    use rand::rngs::OsRng;
+    use std::collections::BTreeSet;
+
+    fn drbg(label: &[u8]) -> HmacDrbg {
+        HmacDrbg::new(&[0x17; 32], &[], label).unwrap()
+    }
+
+    // 两个 512 位素数，来自确定性生成的 1024 位密钥
+    fn primes(label: &[u8]) -> (BigUint, BigUint) {
+        let key = RsaPrivateKey::generate_with_rng(1024, &mut drbg(label)).unwrap();
+        (key.p().clone(), key.q().clone())
+    }
+
+    fn next_prime(mut n: BigUint) -> BigUint {
+        n.set_bit(0, true);
+        while !is_probable_prime(&n) {
+            n += 2u32;
+        }
+        n
+    }
+
+    #[test]
+    fn wiener_recovers_small_private_exponent() {
+        let (p, q) = primes(b"wiener");
+        let n = &p * &q;
+        let phi = (&p - 1u32) * (&q - 1u32);
+        // d 约 200 位，小于 n^(1/4) / 3 ≈ 2^254
+        let mut d = (BigUint::one() << 200u32) + 1u32;
+        while !d.gcd(&phi).is_one() {
+            d += 2u32;
+        }
+        let e = mod_inverse(&d, &phi).unwrap();
+
+        let key = wiener(&n, &e).unwrap();
+        assert_eq!(key.d(), &d);
+        assert_eq!(key.n(), &n);
+        assert_eq!(BTreeSet::from([key.p(), key.q()]), BTreeSet::from([&p, &q]));
+    }
+
+    #[test]
+    fn fermat_factors_close_primes() {
+        let (p, _) = primes(b"fermat");
+        let q = next_prime(&p + (BigUint::one() << 64u32));
+        let n = &p * &q;
+        assert_eq!(fermat_factor(&n, 16), Some((p, q)));
+        assert_eq!(fermat_factor(&BigUint::from(100u32), 16), None);
+    }
+
+    #[test]
+    fn common_modulus_recovers_message() {
+        let (p, q) = primes(b"common modulus");
+        let n = &p * &q;
+        let m = BigUint::from_bytes_be(b"attack at dawn");
+        let (e1, e2) = (BigUint::from(65537u32), BigUint::from(3u32));
+        let (c1, c2) = (m.modpow(&e1, &n), m.modpow(&e2, &n));
+        assert_eq!(common_modulus(&n, &e1, &c1, &e2, &c2), Some(m.clone()));
+        assert_eq!(common_modulus(&n, &e2, &c2, &e1, &c1), Some(m));
+        assert_eq!(common_modulus(&n, &e1, &c1, &e1, &c1), None);
+    }
+
+    #[test]
+    fn hastad_recovers_broadcast_message() {
+        let e = BigUint::from(3u32);
+        let moduli: Vec<BigUint> = [&b"hastad 1"[..], b"hastad 2", b"hastad 3"]
+            .iter()
+            .map(|label| {
+                let (p, q) = primes(label);
+                p * q
+            })
+            .collect();
+        // m³ 远大于单个模数，只能通过中国剩余定理恢复
+        let m = BigUint::from_bytes_be(&[0x5a; 50]);
+        let ciphertexts: Vec<_> = moduli.iter().map(|n| (m.modpow(&e, n), n.clone())).collect();
+        assert_eq!(hastad_broadcast(3, &ciphertexts), Some(m));
+        assert_eq!(hastad_broadcast(3, &ciphertexts[..2]), None);
+    }
+
+    #[test]
+    fn batch_gcd_finds_shared_and_duplicate_moduli() {
+        let (p, q) = primes(b"batch 1");
+        let (r, s) = primes(b"batch 2");
+        let (t, u) = primes(b"batch 3");
+        let moduli = [&p * &q, &p * &r, &t * &u, &t * &u, &s * &next_prime(&s + 1000u32)];
+        let found = batch_gcd(&moduli);
+        assert_eq!(found, vec![(0, p.clone()), (1, p), (2, &t * &u), (3, &t * &u)]);
+    }
+
+    #[test]
+    fn generated_key_survives_all_attacks() {
+        let rsa = RSA::new(1024).unwrap();
+        let (n, e) = (rsa.n(), rsa.e());
+
+        assert!(wiener(n, e).is_none());
+        assert_eq!(fermat_factor(n, 10_000), None);
+
+        // 填充后的两次加密使用不同的随机数，共模攻击恢复出的值既不是明文也不是任何一个填充块
+        let k = rsa.modulus_len();
+        let message = b"attack at dawn";
+        let em1 = BigUint::from_bytes_be(&pkcs1v15_encode(message, k, &mut OsRng).unwrap());
+        let em2 = BigUint::from_bytes_be(&pkcs1v15_encode(message, k, &mut OsRng).unwrap());
+        let e3 = BigUint::from(3u32);
+        let recovered = common_modulus(n, e, &em1.modpow(e, n), &e3, &em2.modpow(&e3, n)).unwrap();
+        assert!(recovered != em1 && recovered != em2 && recovered != BigUint::from_bytes_be(message));
+
+        // e = 65537 时需要 65537 组密文；即使 e = 3，PKCS#1 v1.5 填充也让三份密文对应不同的明文
+        let keys: Vec<RSA> = (0..3).map(|_| RSA::new(1024).unwrap()).collect();
+        let raw: Vec<_> = keys
+            .iter()
+            .map(|key| (BigUint::from_bytes_be(&key.encrypt_pkcs1v15(message).unwrap()), key.n().clone()))
+            .collect();
+        assert_eq!(hastad_broadcast(65537, &raw), None);
+        let padded: Vec<_> = keys
+            .iter()
+            .map(|key| {
+                let em = BigUint::from_bytes_be(&pkcs1v15_encode(message, k, &mut OsRng).unwrap());
+                (em.modpow(&e3, key.n()), key.n().clone())
+            })
+            .collect();
+        assert_eq!(hastad_broadcast(3, &padded), None);
+
+        let mut moduli: Vec<BigUint> = keys.iter().map(|key| key.n().clone()).collect();
+        moduli.push(n.clone());
+        assert!(batch_gcd(&moduli).is_empty());
+    }
+}
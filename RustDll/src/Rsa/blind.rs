@@ -0,0 +1,531 @@
+//! RSA 盲签名 (RFC 9474 RSABSSA)
+//!
+//! 协议流程：
+//!
+//! 1. 客户端 `prepare` 消息（随机化变体在消息前加 32 字节随机前缀）；
+//! 2. 客户端 `blind` 得到盲化消息和盲化因子的逆 inv，把盲化消息发给签名方；
+//! 3. 签名方 `blind_sign` 对盲化消息做私钥运算，看不到原消息；
+//! 4. 客户端 `finalize` 去除盲化并验证，得到普通的 RSASSA-PSS 签名；
+//! 5. 任何人都可以用 `blind_verify`（或 `verify` 加 `BlindVariant::scheme`）验证签名。
+//!
+//! 所有变体都使用 SHA-384 和 MGF1-SHA-384；inv 是客户端的秘密状态，不能泄露给签名方。
+
+use super::padding::i2osp;
+use super::rsa::RSA;
+use super::signature::{pss_encode, SignatureScheme};
+use super::RsaError;
+use crate::hash::HashAlgorithm;
+use crate::numtheory::mod_inverse;
+use num_bigint::{BigUint, RandBigInt};
+use num_integer::Integer;
+use num_traits::One;
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+
+/// 随机化变体的消息前缀长度（字节）
+pub const MSG_PREFIX_LEN: usize = 32;
+
+/// RFC 9474 定义的四种变体
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlindVariant {
+    /// RSABSSA-SHA384-PSS-Randomized（推荐）
+    Sha384PssRandomized,
+    /// RSABSSA-SHA384-PSSZERO-Randomized
+    Sha384PssZeroRandomized,
+    /// RSABSSA-SHA384-PSS-Deterministic
+    Sha384PssDeterministic,
+    /// RSABSSA-SHA384-PSSZERO-Deterministic，签名可由消息唯一确定
+    Sha384PssZeroDeterministic,
+}
+
+impl BlindVariant {
+    /// 由 FFI 的变体编号（CRYPTO_RSA_BSSA_*）得到变体
+    pub fn from_id(variant: i32) -> Option<Self> {
+        match variant {
+            1 => Some(BlindVariant::Sha384PssRandomized),
+            2 => Some(BlindVariant::Sha384PssZeroRandomized),
+            3 => Some(BlindVariant::Sha384PssDeterministic),
+            4 => Some(BlindVariant::Sha384PssZeroDeterministic),
+            _ => None,
+        }
+    }
+
+    /// PSS 盐长度：PSS 变体为 48 字节（摘要长度），PSSZERO 变体为 0
+    pub fn salt_len(&self) -> usize {
+        match self {
+            BlindVariant::Sha384PssRandomized | BlindVariant::Sha384PssDeterministic => {
+                HashAlgorithm::Sha384.output_len()
+            }
+            BlindVariant::Sha384PssZeroRandomized | BlindVariant::Sha384PssZeroDeterministic => 0,
+        }
+    }
+
+    /// 是否在消息前加随机前缀
+    pub fn is_randomized(&self) -> bool {
+        matches!(self, BlindVariant::Sha384PssRandomized | BlindVariant::Sha384PssZeroRandomized)
+    }
+
+    /// 最终签名对应的 RSASSA-PSS 方案
+    pub fn scheme(&self) -> SignatureScheme {
+        SignatureScheme::Pss { hash: HashAlgorithm::Sha384, salt_len: self.salt_len() }
+    }
+}
+
+/// 准备待签名的消息：随机化变体返回 32 字节随机前缀 || msg，确定性变体原样返回
+///
+/// 后续的 blind、finalize、verify 都使用准备后的消息。
+pub fn prepare(variant: BlindVariant, msg: &[u8]) -> Vec<u8> {
+    prepare_with_rng(variant, msg, &mut OsRng)
+}
+
+/// 使用指定的随机数生成器准备消息
+pub fn prepare_with_rng<R: RngCore + CryptoRng + ?Sized>(variant: BlindVariant, msg: &[u8], rng: &mut R) -> Vec<u8> {
+    let mut prepared = Vec::with_capacity(MSG_PREFIX_LEN + msg.len());
+    if variant.is_randomized() {
+        prepared.resize(MSG_PREFIX_LEN, 0);
+        rng.fill_bytes(&mut prepared);
+    }
+    prepared.extend_from_slice(msg);
+    prepared
+}
+
+impl RSA {
+    /// 盲化已准备的消息，返回 (盲化消息, inv)，两者长度都等于模长
+    ///
+    /// 编码后的消息与 n 不互素时返回 `RsaError::InvalidInput`。
+    pub fn blind(&self, variant: BlindVariant, prepared_msg: &[u8]) -> Result<(Vec<u8>, Vec<u8>), RsaError> {
+        self.blind_with_rng(variant, prepared_msg, &mut OsRng)
+    }
+
+    /// 使用指定的随机数生成器产生 PSS 盐和盲化因子
+    pub fn blind_with_rng<R: RngCore + CryptoRng + ?Sized>(
+        &self,
+        variant: BlindVariant,
+        prepared_msg: &[u8],
+        rng: &mut R,
+    ) -> Result<(Vec<u8>, Vec<u8>), RsaError> {
+        let k = self.modulus_len();
+        let em_bits = (self.n.bits() as usize).checked_sub(1).ok_or(RsaError::KeyTooSmall)?;
+        let encoded = pss_encode(HashAlgorithm::Sha384, prepared_msg, variant.salt_len(), em_bits, rng)?;
+
+        let m = BigUint::from_bytes_be(&encoded);
+        if !m.gcd(&self.n).is_one() {
+            return Err(RsaError::InvalidInput);
+        }
+
+        let two = BigUint::from(2u8);
+        let (r, inv) = loop {
+            let r = rng.gen_biguint_range(&two, &self.n);
+            if let Some(inv) = mod_inverse(&r, &self.n) {
+                break (r, inv);
+            }
+        };
+        let z = (m * r.modpow(&self.e, &self.n)) % &self.n;
+        Ok((i2osp(&z, k), i2osp(&inv, k)))
+    }
+
+    /// 签名方对盲化消息做私钥运算，并用公钥检查结果以防止故障泄露私钥
+    ///
+    /// 盲化消息长度必须等于模长且小于 n，否则返回 `RsaError::InvalidInput`。
+    pub fn blind_sign(&self, blinded_msg: &[u8]) -> Result<Vec<u8>, RsaError> {
+        let k = self.modulus_len();
+        if blinded_msg.len() != k {
+            return Err(RsaError::InvalidInput);
+        }
+        let m = BigUint::from_bytes_be(blinded_msg);
+        if m >= self.n {
+            return Err(RsaError::InvalidInput);
+        }
+
+        let s = self.private_op(&m)?;
+        if s.modpow(&self.e, &self.n) != m {
+            return Err(RsaError::PrivateKeyUnavailable);
+        }
+        Ok(i2osp(&s, k))
+    }
+
+    /// 去除盲化得到最终签名，签名验证不通过时返回 `RsaError::Verification`
+    pub fn finalize(
+        &self,
+        variant: BlindVariant,
+        prepared_msg: &[u8],
+        blind_sig: &[u8],
+        inv: &[u8],
+    ) -> Result<Vec<u8>, RsaError> {
+        let k = self.modulus_len();
+        if blind_sig.len() != k || inv.len() != k {
+            return Err(RsaError::InvalidInput);
+        }
+        let z = BigUint::from_bytes_be(blind_sig);
+        let s = (z * BigUint::from_bytes_be(inv)) % &self.n;
+
+        let signature = i2osp(&s, k);
+        if !self.blind_verify(variant, prepared_msg, &signature) {
+            return Err(RsaError::Verification);
+        }
+        Ok(signature)
+    }
+
+    /// 验证盲签名协议产生的签名（即 RSASSA-PSS-SHA384 验证）
+    pub fn blind_verify(&self, variant: BlindVariant, prepared_msg: &[u8], signature: &[u8]) -> bool {
+        self.verify(variant.scheme(), prepared_msg, signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rsa::RsaPrivateKey;
+
+    // RFC 9474 附录 A.1 RSABSSA-SHA384-PSS-Deterministic 测试向量（4096 位密钥，e = 65537）
+    const N: &[&str] = &[
+        "aec4d69addc70b990ea66a5e70603b6fee27aafebd08f2d94cbe1250c556e047a928d635c3f45ee9b66d1bc628a03bac",
+        "9b7c3f416fe20dabea8f3d7b4bbf7f963be335d2328d67e6c13ee4a8f955e05a3283720d3e1f139c38e43e0338ad058a",
+        "9495c53377fc35be64d208f89b4aa721bf7f7d3fef837be2a80e0f8adf0bcd1eec5bb040443a2b2792fdca522a7472ae",
+        "d74f31a1ebe1eebc1f408660a0543dfe2a850f106a617ec6685573702eaaa21a5640a5dcaf9b74e397fa3af18a2f1b7c",
+        "03ba91a6336158de420d63188ee143866ee415735d155b7c2d854d795b7bc236cffd71542df34234221a0413e142d8c6",
+        "1355cc44d45bda94204974557ac2704cd8b593f035a5724b1adf442e78c542cd4414fce6f1298182fb6d8e53cef1adfd",
+        "2e90e1e4deec52999bdc6c29144e8d52a125232c8c6d75c706ea3cc06841c7bda33568c63a6c03817f722b50fcf89823",
+        "7d788a4400869e44d90a3020923dc646388abcc914315215fcd1bae11b1c751fd52443aac8f601087d8d42737c18a3fa",
+        "11ecd4131ecae017ae0a14acfc4ef85b83c19fed33cfd1cd629da2c4c09e222b398e18d822f77bb378dea3cb360b605e",
+        "5aa58b20edc29d000a66bd177c682a17e7eb12a63ef7c2e4183e0d898f3d6bf567ba8ae84f84f1d23bf8b8e261c3729e",
+        "2fa6d07b832e07cddd1d14f55325c6f924267957121902dc19b3b32948bdead5",
+    ];
+    const P: &[&str] = &[
+        "e1f4d7a34802e27c7392a3cea32a262a34dc3691bd87f3f310dc75673488930559c120fd0410194fb8a0da55bd0b8122",
+        "7e843fdca6692ae80e5a5d414116d4803fca7d8c30eaaae57e44a1816ebb5c5b0606c536246c7f11985d731684150b63",
+        "c9a3ad9e41b04c0b5b27cb188a692c84696b742a80d3cd00ab891f2457443dadfeba6d6daf108602be26d7071803c671",
+        "05a5426838e6889d77e8474b29244cefaf418e381b312048b457d73419213063c60ee7b0d81820165864fef93523c963",
+        "5c22210956e53a8d96322493ffc58d845368e2416e078e5bcb5d2fd68ae6acfa54f9627c42e84a9d3f2774017e32ebca",
+        "06308a12ecc290c7cd1156dcccfb2311",
+    ];
+    const Q: &[&str] = &[
+        "c601a9caea66dc3835827b539db9df6f6f5ae77244692780cd334a006ab353c806426b60718c05245650821d39445d3a",
+        "b591ed10a7339f15d83fe13f6a3dfb20b9452c6a9b42eaa62a68c970df3cadb2139f804ad8223d56108dfde30ba7d367",
+        "e9b0a7a80c4fdba2fd9dde6661fc73fc2947569d2029f2870fc02d8325acf28c9afa19ecf962daa7916e21afad09eb62",
+        "fe9f1cf91b77dc879b7974b490d3ebd2e95426057f35d0a3c9f45f79ac727ab81a519a8b9285932d9b2e5ccd347e59f3",
+        "f32ad9ca359115e7da008ab7406707bd0e8e185a5ed8758b5ba266e8828f8d863ae133846304a2936ad7bc7c9803879d",
+        "2fc4a28e69291d73dbd799f8bc238385",
+    ];
+    const MSG: &[&str] = &[
+        "8f3dc6fb8c4a02f4d6352edf0907822c1210a9b32f9bdda4c45a698c80023aa6b59f8cfec5fdbb36331372ebefedae7d",
+    ];
+    const SALT: &[&str] = &[
+        "051722b35f458781397c3a671a7d3bd3096503940e4c4f1aaa269d60300ce449555cd7340100df9d46944c5356825abf",
+    ];
+    const INV: &[&str] = &[
+        "80682c48982407b489d53d1261b19ec8627d02b8cda5336750b8cee332ae260de57b02d72609c1e0e9f28e2040fc65b6",
+        "f02d56dbd6aa9af8fde656f70495dfb723ba01173d4707a12fddac628ca29f3e32340bd8f7ddb557cf819f6b01e445ad",
+        "96f874ba235584ee71f6581f62d4f43bf03f910f6510deb85e8ef06c7f09d9794a008be7ff2529f0ebb69decef646387",
+        "dc767b74939265fec0223aa6d84d2a8a1cc912d5ca25b4e144ab8f6ba054b54910176d5737a2cff011da431bd5f2a0d2",
+        "d66b9e70b39f4b050e45c0d9c16f02deda9ddf2d00f3e4b01037d7029cd49c2d46a8e1fc2c0c17520af1f4b5e25ba396",
+        "afc4cd60c494a4c426448b35b49635b337cfb08e7c22a39b256dd032c00adddafb51a627f99a0e1704170ac1f1912e49",
+        "d9db10ec04c19c58f420212973e0cb329524223a6aa56c7937c5dffdb5d966b6cd4cbc26f3201dd25c80960a1a111b32",
+        "947bb78973d269fac7f5186530930ed19f68507540eed9e1bab8b00f00d8ca09b3f099aae46180e04e3584bd7ca054df",
+        "18a1504b89d1d1675d0966c4ae1407be325cdf623cf13ff13e4a28b594d59e3eadbadf6136eee7a59d6a444c9eb4e219",
+        "8e8a974f27a39eb63af2c9af3870488b8adaad444674f512133ad80b9220e09158521614f1faadfe8505ef57b7df6813",
+        "048603f0dd04f4280177a11380fbfc861dbcbd7418d62155248dad5fdec0991f",
+    ];
+    const BLINDED_MSG: &[&str] = &[
+        "10c166c6a711e81c46f45b18e5873cc4f494f003180dd7f115585d871a28930259654fe28a54dab319cc5011204c8373",
+        "b50a57b0fdc7a678bd74c523259dfe4fd5ea9f52f170e19dfa332930ad1609fc8a00902d725cfe50685c95e5b2968c9a",
+        "2828a21207fcf393d15f849769e2af34ac4259d91dfd98c3a707c509e1af55647efaa31290ddf48e0133b798562af5ea",
+        "bd327270ac2fb6c594734ce339a14ea4fe1b9a2f81c0bc230ca523bda17ff42a377266bc2778a274c0ae5ec5a8cbbe36",
+        "4fcf0d2403f7ee178d77ff28b67a20c7ceec009182dbcaa9bc99b51ebbf13b7d542be337172c6474f2cd3561219fe0df",
+        "a3fb207cff89632091ab841cf38d8aa88af6891539f263adb8eac6402c41b6ebd72984e43666e537f5f5fe27b2b5aa11",
+        "4957e9a580730308a5f5a9c63a1eb599f093ab401d0c6003a451931b6d124180305705845060ebba6b0036154fcef3e5",
+        "e9f9e4b87e8f084542fd1dd67e7782a5585150181c01eb6d90cb95883837384a5b91dbb606f266059ecc51b5acbaa280",
+        "e45cfd2eec8cc1cdb1b7211c8e14805ba683f9b78824b2eb005bc8a7d7179a36c152cb87c8219e5569bba911bb32a1b9",
+        "23ca83de0e03fb10fba75d85c55907dda5a2606bf918b056c3808ba496a4d95532212040a5f44f37e1097f26dc27b98a",
+        "51837daa78f23e532156296b64352669c94a8a855acf30533d8e0594ace7c442",
+    ];
+    const BLIND_SIG: &[&str] = &[
+        "364f6a40dbfbc3bbb257943337eeff791a0f290898a6791283bba581d9eac90a6376a837241f5f73a78a5c6746e1306b",
+        "a3adab6067c32ff69115734ce014d354e2f259d4cbfb890244fd451a497fe6ecf9aa90d19a2d441162f7eaa7ce3fc4e8",
+        "9fd4e76b7ae585be2a2c0fd6fb246b8ac8d58bcb585634e30c9168a434786fe5e0b74bfe8187b47ac091aa571ffea0a8",
+        "64cb906d0e28c77a00e8cd8f6aba4317a8cc7bf32ce566bd1ef80c64de041728abe087bee6cadd0b7062bde5ceef308a",
+        "23bd1ccc154fd0c3a26110df6193464fc0d24ee189aea8979d722170ba945fdcce9b1b4b63349980f3a92dc2e5418c54",
+        "d38a862916926b3f9ca270a8cf40dfb9772bfbdd9a3e0e0892369c18249211ba857f35963d0e05d8da98f1aa0c6bba58",
+        "f47487b8f663e395091275f82941830b050b260e4767ce2fa903e75ff8970c98bfb3a08d6db91ab1746c86420ee2e909",
+        "bf681cac173697135983c3594b2def673736220452fde4ddec867d40ff42dd3da36c84e3e52508b891a00f50b4f62d11",
+        "2edb3b6b6cc3dbd546ba10f36b03f06c0d82aeec3b25e127af545fac28e1613a0517a6095ad18a98ab79f68801e05c17",
+        "5e15bae21f821e80c80ab4fdec6fb34ca315e194502b8f3dcf7892b511aee45060e3994cd15e003861bc7220a2babd7b",
+        "40eda03382548a34a7110f9b1779bf3ef6011361611e6bc5c0dc851e1509de1a",
+    ];
+    const SIG: &[&str] = &[
+        "6fef8bf9bc182cd8cf7ce45c7dcf0e6f3e518ae48f06f3c670c649ac737a8b8119a34d51641785be151a697ed7825fdf",
+        "ece82865123445eab03eb4bb91cecf4d6951738495f8481151b62de869658573df4e50a95c17c31b52e154ae26a04067",
+        "d5ecdc1592c287550bb982a5bb9c30fd53a768cee6baabb3d483e9f1e2da954c7f4cf492fe3944d2fe456c1ecaf08403",
+        "69e33fb4010e6b44bb1d721840513524d8e9a3519f40d1b81ae34fb7a31ee6b7ed641cb16c2ac999004c2191de020145",
+        "7523f5a4700dd649267d9286f5c1d193f1454c9f868a57816bf5ff76c838a2eeb616a3fc9976f65d4371deecfbab2936",
+        "2caebdff69c635fe5a2113da4d4d8c24f0b16a0584fa05e80e607c5d9a2f765f1f069f8d4da21f27c2a3b5c984b4ab24",
+        "899bef46c6d9323df4862fe51ce300fca40fb539c3bb7fe2dcc9409e425f2d3b95e70e9c49c5feb6ecc9d43442c33d50",
+        "003ee936845892fb8be475647da9a080f5bc7f8a716590b3745c2209fe05b17992830ce15f32c7b22cde755c8a2fe50b",
+        "d814a0434130b807dc1b7218d4e85342d70695a5d7f29306f25623ad1e8aa08ef71b54b8ee447b5f64e73d09bdd6c3b7",
+        "ca224058d7c67cc7551e9241688ada12d859cb7646fbd3ed8b34312f3b49d69802f0eaa11bc4211c2f7a29cd5c01ed01",
+        "a39001c5856fab36228f5ee2f2e1110811872fe7c865c42ed59029c706195d52",
+    ];
+
+    // RFC 9474 附录 A 只有 PSS-Deterministic 的向量在手边，其余三个变体使用同一密钥、同一消息和同一 inv，
+    // 随机前缀取 00 01 .. 1f，PSS 盐取 30 31 .. 5f；期望值由独立的 Python 实现计算（该实现能逐字节
+    // 复现上面的 RFC 向量），最终签名另用 cryptography 库的 RSASSA-PSS 验证通过
+    const PSS_RANDOMIZED_BLINDED_MSG: &[&str] = &[
+        "3eb5f6c03c092739640f9357f5999f20d589cd8683917070190481a71d9c898deda9cb278f971827baabe2b370fad489",
+        "758587eefbc5d392d264f1a5c75fd1c0a2902f1a66a68b50dfed53fe7158296948c3ffe44501520868a666f3c4ae08fd",
+        "b44d9facb632a1758452773e11e3f7b1c78532a211987f7c970ba83f408c1371eeccaf8109d4cf72a3c6331c8ee41cae",
+        "4720857e3acfd8128218b90eb32e1d7c277280452516e6e962ae5a22e959494388b5fead1611be5ddcbd93408ed57eee",
+        "fd3c14ce98785f94850398500c9d74dc7ecef7aa3b9738d5e3123a119dfb083d54309292ceeb073bd84dc0c210807b18",
+        "a0ea4774bd032c8e58d171888800a6ac13bba39da1bb0955a5773b384866e776b61c67c35f036e626325fd7e1d39e5af",
+        "df3e77f95635b2402064f6fd68ec651adeec65b1b3d9c38587cdb77b1deb9a3198e8e2e17f66802a29ae0db40fafca23",
+        "fd364f4ea5c823a7b9678643c14ede9c4fcdea1dec23e5e59947b0e30e26538af9b94baf7f4789a663b85c074208c51f",
+        "35961bad4d131fdb0a9bc884deeeba5872d4aa9b01c0a5a901798008e81f8994869c9382172b702c8fbe6636e8a06b0a",
+        "8bd3deb1b40395ddd590a6554047fee65d1fb4ddbde891e82ad0248711cbf2b28e43012fc0f3302f84d42e08fde28380",
+        "aa68d545061580ec93f926abe62136c53815c57b91fe142e06698b095baa41a6",
+    ];
+    const PSS_RANDOMIZED_BLIND_SIG: &[&str] = &[
+        "43256c0aa6611b294c2f971bf381030ca1b3feffc579d46c65cc733a7cbdd35c030aa2eb2afbdfc47e3b4ea71987df36",
+        "e60336e3d6fe1839b4a022c956dd7997fe4213680366a9e0259fb6338bda19279945f99bbdf4cc1f8dcf4521f6d22c36",
+        "2b582956e85ccf7d17251a3fcf630eda6aba5476387183a8912a190c15ce6a5f4c128cbc7b270c338a3c9eccabe8af61",
+        "14f98b0be1a6a2ae19404001286224de8b4c836f6a12d6491480fac7b9b855a00e170f576bf4bd7a8be2312bc9b5bbf4",
+        "e18805e7841f356dafdffb5ec6d828321153358e7a8aab3c04aefe4873e2068692236f8fe5e8f581768514abec08c38d",
+        "1d7064fe218f9343d3e1aa1659f0f8f0bd6a5e3923c92298943f3c6dbcea1643e9a0c3542d8bc001a8626c32e6d5765a",
+        "6d91748c81c0a586077fbfaa38fc5fbedfd1fb82513f4cbf8661491c5dee8163e30e8ad7e9d5a0fbd0e09e37b80f065e",
+        "a0a5bd721424006e2d86d502e1154a1a2f75bfac538d7b087208b9b7980d3f04b2fcb127a6f2a6e04d38b775b792c40b",
+        "7823b9ba6d49a73933e4b2af5f348999fa983d53db2c028b6b9b73e1918025a5acacc2a2fe84f52a5111728559154f48",
+        "bea461379329e7488edb42043333238def4e7341ff86b2778051c0293f75bf118b6db156a7027651065449edd48ebbe3",
+        "9b3197697d4f8492dd9ded4b4606ea5c8759e6038ebbce051015451757909d77",
+    ];
+    const PSS_RANDOMIZED_SIG: &[&str] = &[
+        "95f87483c4d66bd9131ceef4ea4b002cda819235bb6fe0a850b69f935ad92bfceb9a2f6c212c3f770ae0640db774aeeb",
+        "af86189806c8900e60f5f22ff64f5ff67108148c8a8a55f6d602e73ec411fce915d21185a0f11e4d0737d505c582d534",
+        "68370858179f63f9107b485049fa1591de56d28679abae21a37be16b9593f2df7a84e18edb1c6093d4d93d20271229fb",
+        "b44ba5372e42a05d4f33f53b08f365d6f4a5de8a4c0046b22ba6a98e3f8c292d30bf0c1b93fc970a84cd462c4ad59484",
+        "187e1685b9d539e9d450f41bdb35114fa3194fb0f29bf09f36d2572922cc23b92936ff94d98ca7d7b17ead010724540a",
+        "9aa30cdf3582deeae14ffa604131726767840cb44126b9f54fb9c65b3c3bd47b35bd463f70b593d55e9c66deb063914c",
+        "26e16c90d30b9a72278cbecf2b17e7a5e69eb7b064c7e423f3cbcf468fca6376582623a3af05817434d11eda0944c0fd",
+        "caf800a6df1ec82874dfed43165427524f64781c7afb4dcd5258786a240652e39eabfbd3e7f04892ea5705f9188505dd",
+        "90861b7e2c8eee974672499a266bb6840ac920157e2c23401d1662f83bded7e5f12b427095da214e4f32699c16c1552a",
+        "66742feaf5e6fa9bf61cec158daa5d4b5ee1c60e41486e76c99c60411c621b6fd96bfacf825b25e9b9632d5f000c85cd",
+        "63c9c323663b39f27f774e6f58c736d274dd5e7f61241b8438df2feb2c9b3ceb",
+    ];
+    const PSSZERO_RANDOMIZED_BLINDED_MSG: &[&str] = &[
+        "72bf77173316598e0ecfd6e9e05477d949bbc5b4d28fb1b52ce93cbd49f979d41b62dfc56faf188bcb3059c2ef4d1cbc",
+        "805bc1c1cf303e2d910b2f644947dddb24483499415b52538c98b0ee5f60fa541b3e0bb89222b436ce92499041459155",
+        "6aa31021295dd2a09ddb18521d2ca88b45490f53c4c3481af7653a7f1388f360d4eb265ba089ad17de70d1f1cd95aa5a",
+        "b7ef9bd19f49b9cd201fa41035f3fd3139e5359e63810d9ae9a57e2e103dce27cfaf373171cfef884e9948a71f6e4999",
+        "6fa1a3693425b258f2188b30433d8aade4be8f72dc4d56d60016be52542cfeef88dd96da615e705a0e48972ebfa44a72",
+        "772a36d78f83d5ea01608aca490ee84c4f00b289931aaf1aa24776dd23beaf0518ef335442357f5e21ee538418bc74ed",
+        "e388ebf36c5fc228da991fa18f00ecbb630c3283651c88553b5f09338563c8b23d838399439dd7cd6f936437c7a5381c",
+        "771d9c3c25dde0fdb62876af0856e7ec788da67c2917a78668676fbb9872822a387f3f1c75cad823bf536b0264cd0b83",
+        "0a5e0ae68b499d6965eab2b8fbf6695fe6f2d116d37ea56db64c3860f77e0c3528fc312926bbd92f94a73c915694dfbf",
+        "75c8c849d380f843ee64f1b13a35c2808d6cc9b2200d96d13b96da0aa04328c4414fe3a760fa04aae9b4f29ea1b456b4",
+        "c67f3c8fddb83e5de8c51a6dfc47b44d82a850e697b2c9ad99ff6c778aea2f37",
+    ];
+    const PSSZERO_RANDOMIZED_BLIND_SIG: &[&str] = &[
+        "0db7872eb8e3d6050f254707cf4ef8f5d2b50040fd32d39e73dfcc1b589a2e2ddcf8f7e14e880daa6522955a29053666",
+        "e7f6a23d2d671848f7b58980cdbcc843368a3285859f9d3a1ac81b45b24f1385dc6ff188d4bc82808e2495568a5545d8",
+        "5e62b8bad2bfe7871d46ec3c33d24130f7201869b43ef58e55604151f1c9bd9cc902fd970bf4ee3de6e15984b2546b28",
+        "ac025b423c1679b981f05171d1d1e3107c73d6a80e401a14c9dd84463719ca9d46e22082befeb589f845f8bffc7f4372",
+        "4ef2a7a339f706a6f0172fdb90d617bc246c4cf91b1f53479d54d53c12b8bc79d1dd15ed906e24fe342df2a619d1ef67",
+        "aea6966a82ecb853159ae5aff754cf94af7e118f46191c80bd8cb1124e81b562219c68fa83e2c14eb3643ce516ad581f",
+        "eb8fbdfcd9d8e11ddb947962af290a27fb16767fb0bb3baf635e1de81b9356cba48319ab861e13c990fdcbe41649b5b6",
+        "c30b72e84c6d16be920fc5fe7b776bcc852682592624b4cb66bc788f9da8f32aee319facd3e285a293848b64fdc08b34",
+        "32737e9acc9d11cbe2d8a2a4067c92330ba9d4722fe7bf266f3bac8efc62a3a9ce487bde7a72f0f40f8fdd192c72fd87",
+        "0b1cd43db0482ac41e13ec3c35b6162bdf696a530cf41276a6c5ebef9096d6f4a01c1b3f18ba4b59b8664fc20b4a6052",
+        "746833bd1615e779630f6f27dd39afc25a912ebbb9f9cae0fc5c96d5d5fbe1e5",
+    ];
+    const PSSZERO_RANDOMIZED_SIG: &[&str] = &[
+        "2ddb0eed268c5725d0b52eb026deb4edfe93371446d0e34a75aa1184cf92393e2a6edceb6be8b87cc5e439c1eef23b5c",
+        "9bfc43a0a41ca3e32ae8f537b0da8cb36e6451461e29ebd9f8c92b07ea186cd10497899967c10dafcf9e28a50c966018",
+        "ccf2a57ca4684a70865d57cc3fc7bea1aae52e208bdc61d6dd9e6b3c0c44ca9956982e0781185103d906d6cf34b8b843",
+        "e3ec24d56acc95ee8cf64a91cc9d41b6f6abd5f4017ed414f091fe8d95200e6e437d81a781e49dbe2b371346e738dfd7",
+        "6463c41e80cba12fdc6fe528895edcad3b904fea4cb8cdf197453823744cfa3394e089f0b74ce3183dad4d94b86d91b0",
+        "8ee765260f0bfbca2c16a7a75504e710dee2c4384f71ac2e501312ec3ccde6f51fa67cd1f9dff2129786295f7265e8b6",
+        "0c7922a1f438756785f6c001c3ed969f79cc39350136eceec6cb52c9ea4ea09015b923fe75dd30fd489f35b904cf10d6",
+        "6f5b27496b01e6b119f9467b47ac36ded4752f234d359881cf730ef79b3c5800bac84f11f703ccc66db716fc3268baaf",
+        "e6824e965f38322ce43e09b18ab40ca67002d42b0d0722aa60044ea89799a545b07ef312853564f23e369e0d0488a2ef",
+        "62ee8543206688f103f3a17307f5fe0d10c79e4705ffbf2dbf8cdd3629854e7adcab7ec0f0d647e46bdee846d4f9ed73",
+        "86e8c4f77ac464abfcf08f087e08318260053f7975dabfe55dd5533193b6820d",
+    ];
+    const PSSZERO_DETERMINISTIC_BLINDED_MSG: &[&str] = &[
+        "982790826556aabe6004467671a864397eea3b95740e9a11c8b80b99ee0cf4dbc50af860bda81b601a2eceaa6943ef10",
+        "4f13325ad0be2e37f42030b3120e87cfee8cfe59cde1acfb25485a43275ebe777292e2518181ae531e596f988ff16f45",
+        "8daa5a42408939cbe60e7271391a21657276427d195bee6a20054101d4ceb892ecdea402ea1a866acf0e451a3336f07e",
+        "7589330d96c3883fd5bc1a829a715b618b74a86b2a898764246ad081d4c9f1edb8ab5077e315fde2417ec2dd33cad93e",
+        "120340b49be89c18a63e62c6bb289037283d3bf18608be11ee4c823c710b0c6b89235fed3f03a7b96ddd25a8f54f20da",
+        "c37ce8905093ad8e066810f354fb1773236e3d3788ba755de2c9bce8d340078bb1831ddc7314a5018673427ced65cb35",
+        "6281aae08b5e6636f3eb2417e09d6ae476a9abcc410bc8c90813d0740e39ae75efae4c02eed49dbb7aa51258bb711974",
+        "45d17a6029bf566ba6b36282173af2c42e9b9631366f22eb6a19ef1d92bd3ce0631d3a7fb3288195b0ba380a3828d541",
+        "1cefd5eba83e52198c001ac9946a333a33d89d4d235fc833239d59837f04eaf065e9563659b00c7624a6263b727d8f2c",
+        "07959ba2bb592e7ff251b8f09c85995fd2e4474e743586576b518230986b6076b762ae77088a37e4bffd2ef41ae68d6d",
+        "4e79205290b4f76c42ef039638c41cdc6fe8af9b429c0dee45b2942e3861da2a",
+    ];
+    const PSSZERO_DETERMINISTIC_BLIND_SIG: &[&str] = &[
+        "362ef369f9b8c1487e285514702a7cd6fe03e4a2fb854881f3d3f986b7742a0c9bfab6562a6cd5ed71c574af67d7e77e",
+        "71b33420c08ebb0ff37886b858297f9562fc366066c6d8e77bad1918b04756ba03f5c385d44f06759daf1b7a38b2a642",
+        "48dee95d0e3886c8afa1f74afd8ac3c56520d0f3fd206df8e0d257312756803b09a79d0cc38112592c3aec32de5a9bc3",
+        "284c5a0a2d0808b102deafa5cc60f04e3d71c0284cba04f17f88aa8e07d5544fe0265807d515877f79d30ed26d522b9d",
+        "9c56597647b0dbca5a69d6418f8d1b51481723f272c2a3d48f6f4fd6beeac3576c3edb00e8779964548aeab8e004c7c4",
+        "f8ef9cb6e680e2d2d49792004bb3e6974fa48f241a361ca449c02bd4c0ad4e66252c55e656f16049908efe59acbafa11",
+        "71895dfac64d909808e5420469d622c7253ec1de7522b41634d383bf8786bf881cbf1561627f1e62b2d93300ec30ec0f",
+        "5f0ab32036fce068bc76b0b0c6452079537f8d7f8dcee4b42bbf2d9ad7499d3835cd93cfc7e8ebea3554ab5241e181e5",
+        "d73241b7bebf0a281b63594a35f4993e2b416d60db966b58b648cfcba2c4bee4c2830aae4a70ff55012480298f549c13",
+        "b1b2684277bca12f592471b8a99285174f1c0ebb38fc80e74a10b3f02ec3e6682ba873f7ff0e1e79718b470927c74ed7",
+        "54d4f7c3d9a55e22246e829cdb5a1c6fb2a0a6c896df303063c918bcf5eb0017",
+    ];
+    const PSSZERO_DETERMINISTIC_SIG: &[&str] = &[
+        "4454b6983ff01cb28545329f394936efa42ed231e15efbc025fdaca00277acf0c8e00e3d8b0ecebd35b057b8ebfc14e1",
+        "a7097368a4abd20b555894ccef3d1b9528c6bcbda6b95376bef230d0f1feff0c1064c62c60a7ae7431d1fdfa43a81eed",
+        "9235e363e1ffa0b2797aba6aad6082fcd285e14fc8b71de6b9c87cb4059c7dc1e96ae1e63795a1e9af86b9073d1d848a",
+        "ef3eca8a03421bcd116572456b53bcfd4dabb0a9691f1fabda3ed0ce357aee2cfee5b1a0eb226f69716d4e011d96eede",
+        "5e38a9acb531a64336a0d5b0bae3ab085b658692579a376740ff6ce69e89b06f360520b864e33d82d029c808248a19e1",
+        "8e31f0ecd16fac5cd4870f8d3ebc1c32c718124152dc905672ab0b7af48bf7d1ac1ff7b9c742549c91275ab105458ae3",
+        "7621757add83482bbcf779e777bbd61126e93686635d4766aedf5103cf7978f3856ccac9e28d21a850dbb03c81112861",
+        "6d315d717be1c2b6254f8509acae862042c034530329ce15ca2e2f6b1f5fd59272746e3918c748c0eb810bf76884fa10",
+        "fcf749326bbfaa5ba285a0186a22e4f628dbf178d3bb5dc7e165ca73f6a55ecc14c4f5a26c4693ce5da032264cbec319",
+        "b12ddb9787d0efa4fcf1e5ccee35ad85ecd453182df9ed735893f830b570faae8be0f6fe2e571a4e0d927cba4debd368",
+        "d3b4fca33ec6251897a137cf75474a32ac8256df5e5ffa518b88b43fb6f63a24",
+    ];
+
+    fn decode(hex_lines: &[&str]) -> Vec<u8> {
+        hex::decode(hex_lines.concat()).unwrap()
+    }
+
+    fn number(hex_lines: &[&str]) -> BigUint {
+        BigUint::from_bytes_be(&decode(hex_lines))
+    }
+
+    // 依次吐出预置字节的随机数生成器，用于重放向量中的盐和盲化因子
+    struct ReplayRng(std::vec::IntoIter<u8>);
+
+    impl RngCore for ReplayRng {
+        fn next_u32(&mut self) -> u32 {
+            let mut bytes = [0u8; 4];
+            self.fill_bytes(&mut bytes);
+            u32::from_le_bytes(bytes)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            let mut bytes = [0u8; 8];
+            self.fill_bytes(&mut bytes);
+            u64::from_le_bytes(bytes)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            for byte in dest {
+                *byte = self.0.next().expect("replay bytes exhausted");
+            }
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    impl CryptoRng for ReplayRng {}
+
+    fn vector_key() -> RSA {
+        let (p, q) = (number(P), number(Q));
+        let e = BigUint::from(65537u32);
+        let phi = (&p - 1u32) * (&q - 1u32);
+        let d = mod_inverse(&e, &phi).unwrap();
+        RSA::from_crt_key(RsaPrivateKey::from_components(number(N), e, d, p, q).unwrap())
+    }
+
+    // prepare_with_rng 先取 32 字节前缀（仅随机化变体），blind_with_rng 再取 PSS 盐，然后以
+    // gen_biguint_range(2, n) 取 r：后者按小端序读取 r - 2 的 4096 位，首次采样即落在范围内
+    fn replay_prefix_salt_and_r(prefix: &[u8], salt: &[u8], n: &BigUint) -> ReplayRng {
+        let r = mod_inverse(&number(INV), n).unwrap();
+        let mut r_bytes = (r - 2u32).to_bytes_le();
+        r_bytes.resize(n.bits().div_ceil(8) as usize, 0);
+        ReplayRng([prefix, salt, &r_bytes].concat().into_iter())
+    }
+
+    // 按协议流程重放一个变体的向量：(盲化消息, 盲签名, 最终签名)
+    fn check_vector(variant: BlindVariant, prefix: &[u8], salt: &[u8], expected: [&[&str]; 3]) {
+        let rsa = vector_key();
+        let msg = decode(MSG);
+        let [expected_blinded_msg, expected_blind_sig, expected_sig] = expected.map(decode);
+
+        let mut rng = replay_prefix_salt_and_r(prefix, salt, &rsa.n);
+        let prepared = prepare_with_rng(variant, &msg, &mut rng);
+        assert_eq!(prepared, [prefix, &msg].concat());
+
+        let (blinded_msg, inv) = rsa.blind_with_rng(variant, &prepared, &mut rng).unwrap();
+        assert_eq!(blinded_msg, expected_blinded_msg);
+        assert_eq!(inv, decode(INV));
+        assert_eq!(rng.0.len(), 0);
+
+        let blind_sig = rsa.blind_sign(&blinded_msg).unwrap();
+        assert_eq!(blind_sig, expected_blind_sig);
+
+        let sig = rsa.finalize(variant, &prepared, &blind_sig, &inv).unwrap();
+        assert_eq!(sig, expected_sig);
+        assert!(rsa.blind_verify(variant, &prepared, &sig));
+        assert!(rsa.verify(variant.scheme(), &prepared, &sig));
+    }
+
+    fn prefix() -> Vec<u8> {
+        (0x00..0x20).collect()
+    }
+
+    fn salt() -> Vec<u8> {
+        (0x30..0x60).collect()
+    }
+
+    #[test]
+    fn rfc9474_pss_deterministic_vector() {
+        check_vector(BlindVariant::Sha384PssDeterministic, &[], &decode(SALT), [BLINDED_MSG, BLIND_SIG, SIG]);
+    }
+
+    #[test]
+    fn pss_randomized_vector() {
+        check_vector(
+            BlindVariant::Sha384PssRandomized,
+            &prefix(),
+            &salt(),
+            [PSS_RANDOMIZED_BLINDED_MSG, PSS_RANDOMIZED_BLIND_SIG, PSS_RANDOMIZED_SIG],
+        );
+    }
+
+    #[test]
+    fn psszero_randomized_vector() {
+        check_vector(
+            BlindVariant::Sha384PssZeroRandomized,
+            &prefix(),
+            &[],
+            [PSSZERO_RANDOMIZED_BLINDED_MSG, PSSZERO_RANDOMIZED_BLIND_SIG, PSSZERO_RANDOMIZED_SIG],
+        );
+    }
+
+    #[test]
+    fn psszero_deterministic_vector() {
+        check_vector(
+            BlindVariant::Sha384PssZeroDeterministic,
+            &[],
+            &[],
+            [PSSZERO_DETERMINISTIC_BLINDED_MSG, PSSZERO_DETERMINISTIC_BLIND_SIG, PSSZERO_DETERMINISTIC_SIG],
+        );
+    }
+
+    #[test]
+    fn randomized_variant_prepends_prefix() {
+        let msg = decode(MSG);
+        let prefix = vec![0x5a; MSG_PREFIX_LEN];
+        let mut rng = ReplayRng(prefix.clone().into_iter());
+        let prepared = prepare_with_rng(BlindVariant::Sha384PssRandomized, &msg, &mut rng);
+        assert_eq!(prepared, [prefix, msg].concat());
+    }
+
+    #[test]
+    fn tampered_blind_signature_is_rejected() {
+        let variant = BlindVariant::Sha384PssDeterministic;
+        let rsa = vector_key();
+        let msg = decode(MSG);
+        let mut blind_sig = decode(BLIND_SIG);
+        blind_sig[100] ^= 1;
+        assert_eq!(rsa.finalize(variant, &msg, &blind_sig, &decode(INV)), Err(RsaError::Verification));
+        assert!(!rsa.blind_verify(BlindVariant::Sha384PssZeroDeterministic, &msg, &decode(SIG)));
+    }
+}
@@ -0,0 +1,40 @@
+//! RSA 基数盲化：私钥运算前把输入乘以 r^e，运算后乘以 r^-1，使运算时间与攻击者选择的输入无关
+
+use crate::numtheory::mod_inverse;
+use num_bigint::{BigUint, RandBigInt};
+use num_integer::Integer;
+use num_traits::One;
+use rand::rngs::OsRng;
+
+/// 盲化因子：r^e mod n 与 r^-1 mod n，r 取自 OsRng
+pub(crate) struct Blinding {
+    blind: BigUint,
+    unblind: BigUint,
+}
+
+impl Blinding {
+    /// 生成新的盲化因子，n 太小无法取到与 n 互素的 r 时返回 None
+    pub(crate) fn new(e: &BigUint, n: &BigUint) -> Option<Self> {
+        let two = BigUint::from(2u8);
+        if n <= &two {
+            return None;
+        }
+        loop {
+            let r = OsRng.gen_biguint_range(&two, n);
+            if !r.gcd(n).is_one() {
+                continue;
+            }
+            let unblind = mod_inverse(&r, n)?;
+            let blind = r.modpow(e, n);
+            return Some(Blinding { blind, unblind });
+        }
+    }
+
+    pub(crate) fn blind(&self, x: &BigUint, n: &BigUint) -> BigUint {
+        (x * &self.blind) % n
+    }
+
+    pub(crate) fn unblind(&self, y: &BigUint, n: &BigUint) -> BigUint {
+        (y * &self.unblind) % n
+    }
+}
@@ -0,0 +1,140 @@
+//! RSA 混合加密信封：RSA-KEM 封装随机密钥，AES-128-GCM 加密任意长度的载荷
+//!
+//! 信封格式（k 为模长字节数）：
+//!
+//! | 偏移    | 长度 | 内容                                         |
+//! |---------|------|----------------------------------------------|
+//! | 0       | 1    | 版本号 `ENVELOPE_VERSION`                    |
+//! | 1       | k    | RSA-KEM 密文 C0 = z^e mod n，大端定长        |
+//! | 1 + k   | L    | AES-128-GCM 加密的载荷，L 等于明文长度       |
+//! | 1 + k + L | 16 | GCM 认证标签                                 |
+//!
+//! z 在 [0, n) 内均匀随机，AES 密钥和 nonce 由
+//! HKDF-SHA256(salt = 空, IKM = I2OSP(z, k), info = `ENVELOPE_INFO`) 派生出的 28 字节
+//! 依次切分得到；每个信封的密钥只用一次，因此 nonce 无需随信封传输。
+//! 版本号与 C0 作为 GCM 的附加认证数据，篡改任何字节都会导致解封失败。
+
+use super::padding::i2osp;
+use super::rsa::RSA;
+use super::validation::{check_public_key, KeyError};
+use super::RsaError;
+use crate::aes::gcm::{AesGcm, NONCE_LEN, TAG_LEN};
+use crate::hash::HashAlgorithm;
+use crate::kdf::hkdf;
+use num_bigint::{BigUint, RandBigInt};
+use rand::rngs::OsRng;
+
+/// 当前信封格式版本
+pub const ENVELOPE_VERSION: u8 = 1;
+
+/// 派生载荷密钥时使用的 HKDF info
+pub const ENVELOPE_INFO: &[u8] = b"Crypto RSA-KEM AES-128-GCM v1";
+
+/// 封装信封允许的最小模数位数，更小的模数无法提供有意义的 KEM 安全性
+pub const MIN_ENVELOPE_MODULUS_BITS: usize = 1024;
+
+impl RSA {
+    /// 用公钥封装任意长度的消息，返回信封
+    ///
+    /// 公钥先经过 `check_public_key` 校验；模数不足 `MIN_ENVELOPE_MODULUS_BITS` 位时返回
+    /// `RsaError::KeyTooSmall`，公钥无效时返回 `RsaError::InvalidInput`。
+    pub fn seal(&self, plaintext: &[u8]) -> Result<Vec<u8>, RsaError> {
+        match check_public_key(&self.n, &self.e) {
+            Ok(()) if self.n.bits() as usize >= MIN_ENVELOPE_MODULUS_BITS => {}
+            Ok(()) | Err(KeyError::ModulusTooSmall) => return Err(RsaError::KeyTooSmall),
+            Err(_) => return Err(RsaError::InvalidInput),
+        }
+
+        let k = self.modulus_len();
+        let z = OsRng.gen_biguint_below(&self.n);
+        let c0 = i2osp(&z.modpow(&self.e, &self.n), k);
+
+        let mut envelope = Vec::with_capacity(1 + k + plaintext.len() + TAG_LEN);
+        envelope.push(ENVELOPE_VERSION);
+        envelope.extend_from_slice(&c0);
+
+        let (cipher, nonce) = payload_cipher(&z, k);
+        let sealed = cipher.encrypt(&nonce, &envelope, plaintext);
+        envelope.extend_from_slice(&sealed);
+        Ok(envelope)
+    }
+
+    /// 用私钥打开信封；格式错误、密钥不匹配或数据被篡改都返回 `RsaError::Decryption`
+    pub fn open(&self, envelope: &[u8]) -> Result<Vec<u8>, RsaError> {
+        let k = self.modulus_len();
+        if (self.n.bits() as usize) < MIN_ENVELOPE_MODULUS_BITS || envelope.len() < 1 + k + TAG_LEN || envelope[0] != ENVELOPE_VERSION {
+            return Err(RsaError::Decryption);
+        }
+        let (header, sealed) = envelope.split_at(1 + k);
+        let c0 = BigUint::from_bytes_be(&header[1..]);
+        if c0 >= self.n {
+            return Err(RsaError::Decryption);
+        }
+        let z = self.private_op(&c0).map_err(|_| RsaError::Decryption)?;
+
+        let (cipher, nonce) = payload_cipher(&z, k);
+        cipher.decrypt(&nonce, header, sealed).ok_or(RsaError::Decryption)
+    }
+}
+
+// 由 KEM 秘密 z 派生载荷的 AES-128-GCM 密钥和 nonce
+fn payload_cipher(z: &BigUint, k: usize) -> (AesGcm, [u8; NONCE_LEN]) {
+    let okm = hkdf(HashAlgorithm::Sha256, &[], &i2osp(z, k), ENVELOPE_INFO, 16 + NONCE_LEN)
+        .expect("HKDF-SHA256 output length is within limits");
+    let mut nonce = [0u8; NONCE_LEN];
+    nonce.copy_from_slice(&okm[16..]);
+    (AesGcm::new(&okm[..16]).expect("16-byte AES key"), nonce)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drbg::HmacDrbg;
+
+    fn test_key() -> RSA {
+        let mut rng = HmacDrbg::new(&[0x39; 32], b"nonce", b"rsa envelope test").unwrap();
+        RSA::generate_with_rng(1024, &mut rng).unwrap()
+    }
+
+    #[test]
+    fn seal_open_round_trip() {
+        let rsa = test_key();
+        let public = RSA::from_public_parts(rsa.n().clone(), rsa.e().clone());
+        for message in [&b""[..], b"hello", &[0xa5; 1000]] {
+            let envelope = public.seal(message).unwrap();
+            assert_eq!(envelope.len(), 1 + rsa.modulus_len() + message.len() + TAG_LEN);
+            assert_eq!(rsa.open(&envelope).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn tampered_envelope_is_rejected() {
+        let rsa = test_key();
+        let envelope = rsa.seal(b"attack at dawn").unwrap();
+        for i in [0, 1, rsa.modulus_len(), envelope.len() - 20, envelope.len() - 1] {
+            let mut tampered = envelope.clone();
+            tampered[i] ^= 0x01;
+            assert_eq!(rsa.open(&tampered), Err(RsaError::Decryption), "byte {i}");
+        }
+        assert_eq!(rsa.open(&envelope[..envelope.len() - 1]), Err(RsaError::Decryption));
+        assert_eq!(rsa.open(&[]), Err(RsaError::Decryption));
+
+        let other = RSA::generate_with_rng(1024, &mut HmacDrbg::new(&[0x40; 32], b"", b"").unwrap()).unwrap();
+        assert_eq!(other.open(&envelope), Err(RsaError::Decryption));
+    }
+
+    #[test]
+    fn invalid_public_key_is_rejected() {
+        let e = BigUint::from(65537u32);
+        let zero = RSA::from_public_parts(BigUint::from(0u32), e.clone());
+        assert_eq!(zero.seal(b"x"), Err(RsaError::KeyTooSmall));
+
+        // 61 · 53，能通过模数的基本检查但远小于最小长度
+        let tiny = RSA::from_public_parts(BigUint::from(3233u32), BigUint::from(17u32));
+        assert_eq!(tiny.seal(b"x"), Err(RsaError::KeyTooSmall));
+
+        let rsa = test_key();
+        let no_exponent = RSA::from_public_parts(rsa.n().clone(), BigUint::from(0u32));
+        assert_eq!(no_exponent.seal(b"x"), Err(RsaError::InvalidInput));
+    }
+}
@@ -0,0 +1,283 @@
+//! RSA 密钥的 JWK 表示 (RFC 7518 §6.3)
+//!
+//! 公钥只含 "n"、"e"；由素因子构造的私钥再输出 "d"、"p"、"q"、"dp"、"dq"、"qi"，
+//! 多素数密钥的第三个及以后的素因子放在 "oth" 数组中（每项含 "r"、"d"、"t"）。
+
+use super::private_key::RsaPrivateKey;
+use super::rsa::RSA;
+use super::RsaError;
+use crate::encoding::json::JsonValue;
+use crate::encoding::jwk::{object_uint, uint_object, Jwk};
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+
+/// RSA 的 JWK 密钥类型
+pub const JWK_KTY: &str = "RSA";
+
+// 私钥中除 n、e、d 外的 CRT 成员
+const CRT_MEMBERS: [&str; 5] = ["p", "q", "dp", "dq", "qi"];
+
+impl RSA {
+    /// 导出公钥 JWK
+    pub fn to_public_jwk(&self) -> String {
+        self.public_jwk().to_json()
+    }
+
+    /// 导出私钥 JWK；持有 CRT 私钥时包含全部 CRT 参数，否则只含 n、e、d
+    ///
+    /// 没有私钥，或者实例由 `from_private_key` 创建而缺少 e 时返回 `RsaError::PrivateKeyUnavailable`。
+    pub fn to_jwk(&self) -> Result<String, RsaError> {
+        let d = self.d.as_ref().ok_or(RsaError::PrivateKeyUnavailable)?;
+        if self.e.is_zero() {
+            return Err(RsaError::PrivateKeyUnavailable);
+        }
+
+        let mut jwk = self.public_jwk();
+        jwk.set_uint("d", d);
+        if let Some(key) = self.crt_key() {
+            jwk.set_uint("p", key.p());
+            jwk.set_uint("q", key.q());
+            jwk.set_uint("dp", key.dp());
+            jwk.set_uint("dq", key.dq());
+            jwk.set_uint("qi", key.qinv());
+            if !key.other_primes().is_empty() {
+                let oth = key
+                    .other_primes()
+                    .iter()
+                    .map(|info| uint_object(&[("r", info.prime()), ("d", info.exponent()), ("t", info.coefficient())]))
+                    .collect();
+                jwk.set("oth", JsonValue::Array(oth));
+            }
+        }
+        Ok(jwk.to_json())
+    }
+
+    /// 导入 JWK 公钥或私钥
+    ///
+    /// 私钥若带 "p"，则 CRT 成员必须齐全且与 n、e、d 一致；只有 "d" 时做一次加解密一致性检查。
+    /// "kty" 不是 "RSA"、成员缺失或参数不一致时返回 None。
+    pub fn from_jwk(text: &str) -> Option<Self> {
+        let jwk = Jwk::parse(text)?;
+        if jwk.kty() != JWK_KTY {
+            return None;
+        }
+        let (n, e) = (jwk.uint("n")?, jwk.uint("e")?);
+        if n <= BigUint::one() || e.is_zero() {
+            return None;
+        }
+        if !jwk.contains("d") {
+            return Some(Self::from_public_parts(n, e));
+        }
+
+        let d = jwk.uint("d")?;
+        if !CRT_MEMBERS.iter().any(|name| jwk.contains(name)) && !jwk.contains("oth") {
+            let two = BigUint::from(2u32);
+            if two.modpow(&e, &n).modpow(&d, &n) != two {
+                return None;
+            }
+            return Some(Self::from_parts(n, e, d));
+        }
+
+        crt_key_from_jwk(&jwk, n, e, d).map(Self::from_crt_key)
+    }
+
+    fn public_jwk(&self) -> Jwk {
+        let mut jwk = Jwk::new(JWK_KTY);
+        jwk.set_uint("n", &self.n);
+        jwk.set_uint("e", &self.e);
+        jwk
+    }
+}
+
+// 由素因子重新计算 CRT 参数，并要求与 JWK 中给出的值一致
+fn crt_key_from_jwk(jwk: &Jwk, n: BigUint, e: BigUint, d: BigUint) -> Option<RsaPrivateKey> {
+    let [p, q, dp, dq, qi] = CRT_MEMBERS.map(|name| jwk.uint(name));
+    let (p, q, dp, dq, qi) = (p?, q?, dp?, dq?, qi?);
+
+    let mut primes = vec![p, q];
+    let mut others = Vec::new();
+    if jwk.contains("oth") {
+        for info in jwk.array("oth")? {
+            let (r, exponent, coefficient) = (object_uint(info, "r")?, object_uint(info, "d")?, object_uint(info, "t")?);
+            primes.push(r);
+            others.push((exponent, coefficient));
+        }
+        if others.is_empty() {
+            return None;
+        }
+    }
+
+    let key = RsaPrivateKey::from_multi_prime_components(n, e, d, primes)?;
+    if key.dp() != &dp || key.dq() != &dq || key.qinv() != &qi {
+        return None;
+    }
+    let others_match = key
+        .other_primes()
+        .iter()
+        .zip(&others)
+        .all(|(info, (exponent, coefficient))| info.exponent() == exponent && info.coefficient() == coefficient);
+    if !others_match {
+        return None;
+    }
+    Some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drbg::HmacDrbg;
+    use crate::encoding::jwk::{decode_uint, encode_uint};
+
+    // RFC 7517 附录 A.1 的公钥集合：一个 EC 密钥和一个 RSA 密钥
+    const RFC7517_A1: &str = r#"{"keys":
+       [
+         {"kty":"EC",
+          "crv":"P-256",
+          "x":"MKBCTNIcKUSDii11ySs3526iDZ8AiTo7Tu6KPAqv7D4",
+          "y":"4Etl6SRW2YiLUrN5vfvVHuhp7x8PxltmWWlbbM4IFyM",
+          "use":"enc",
+          "kid":"1"},
+
+         {"kty":"RSA",
+          "n": "0vx7agoebGcQSuuPiLJXZptN9nndrQmbXEps2aiAFbWhM78LhWx4cbbfAAtVT86zwu1RK7aPFFxuhDR1L6tSoc_BJECPebWKRXjBZCiFV4n3oknjhMstn64tZ_2W-5JsGY4Hc5n9yBXArwl93lqt7_RN5w6Cf0h4QyQ5v-65YGjQR0_FDW2QvzqY368QQMicAtaSqzs8KJZgnYb9c7d0zgdAZHzu6qMQvRL5hajrn1n91CbOpbISD08qNLyrdkt-bFTWhAI4vMQFh6WeZu0fM4lFd2NcRwr3XPksINHaQ-G_xBniIqbw0Ls1jF44-csFCur-kEgU8awapJzKnqDKgw",
+          "e":"AQAB",
+          "alg":"RS256",
+          "kid":"2011-04-29"}
+       ]
+     }"#;
+
+    // RFC 7517 附录 A.2 的 RSA 私钥
+    const RFC7517_A2_RSA: &str = r#"{"kty":"RSA",
+      "n":"0vx7agoebGcQSuuPiLJXZptN9nndrQmbXEps2aiAFbWhM78LhWx4cbbfAAtVT86zwu1RK7aPFFxuhDR1L6tSoc_BJECPebWKRXjBZCiFV4n3oknjhMstn64tZ_2W-5JsGY4Hc5n9yBXArwl93lqt7_RN5w6Cf0h4QyQ5v-65YGjQR0_FDW2QvzqY368QQMicAtaSqzs8KJZgnYb9c7d0zgdAZHzu6qMQvRL5hajrn1n91CbOpbISD08qNLyrdkt-bFTWhAI4vMQFh6WeZu0fM4lFd2NcRwr3XPksINHaQ-G_xBniIqbw0Ls1jF44-csFCur-kEgU8awapJzKnqDKgw",
+      "e":"AQAB",
+      "d":"X4cTteJY_gn4FYPsXB8rdXix5vwsg1FLN5E3EaG6RJoVH-HLLKD9M7dx5oo7GURknchnrRweUkC7hT5fJLM0WbFAKNLWY2vv7B6NqXSzUvxT0_YSfqijwp3RTzlBaCxWp4doFk5N2o8Gy_nHNKroADIkJ46pRUohsXywbReAdYaMwFs9tv8d_cPVY3i07a3t8MN6TNwm0dSawm9v47UiCl3Sk5ZiG7xojPLu4sbg1U2jx4IBTNBznbJSzFHK66jT8bgkuqsk0GjskDJk19Z4qwjwbsnn4j2WBii3RL-Us2lGVkY8fkFzme1z0HbIkfz0Y6mqnOYtqc0X4jfcKoAC8Q",
+      "p":"83i-7IvMGXoMXCskv73TKr8637FiO7Z27zv8oj6pbWUQyLPQBQxtPVnwD20R-60eTDmD2ujnMt5PoqMrm8RfmNhVWDtjjMmCMjOpSXicFHj7XOuVIYQyqVWlWEh6dN36GVZYk93N8Bc9vY41xy8B9RzzOGVQzXvNEvn7O0nVbfs",
+      "q":"3dfOR9cuYq-0S-mkFLzgItgMEfFzB2q3hWehMuG0oCuqnb3vobLyumqjVZQO1dIrdwgTnCdpYzBcOfW5r370AFXjiWft_NGEiovonizhKpo9VVS78TzFgxkIdrecRezsZ-1kYd_s1qDbxtkDEgfAITAG9LUnADun4vIcb6yelxk",
+      "dp":"G4sPXkc6Ya9y8oJW9_ILj4xuppu0lzi_H7VTkS8xj5SdX3coE0oimYwxIi2emTAue0UOa5dpgFGyBJ4c8tQ2VF402XRugKDTP8akYhFo5tAA77Qe_NmtuYZc3C3m3I24G2GvR5sSDxUyAN2zq8Lfn9EUms6rY3Ob8YeiKkTiBj0",
+      "dq":"s9lAH9fggBsoFR8Oac2R_E2gw282rT2kGOAhvIllETE1efrA6huUUvMfBcMpn8lqeW6vzznYY5SSQF7pMdC_agI3nG8Ibp1BUb0JUiraRNqUfLhcQb_d9GF4Dh7e74WbRsobRonujTYN1xCaP6TO61jvWrX-L18txXw494Q_cgk",
+      "qi":"GyM_p6JrXySiz1toFgKbWV-JdI3jQ4ypu9rbMWx3rQJBfmt0FoYzgUIZEVFEcOqwemRN81zoDAaa-Bk0KWNGDjJHZDdDmFhW3AN7lI-puxk_mHZGJ11rxyR8O55XLSe3SPmRfKwZI6yU24ZxvQKFYItdldUKGzO6Ia6zTKhAVRU",
+      "alg":"RS256",
+      "kid":"2011-04-29"}"#;
+
+    // RFC 7517 附录 B 的 RSA 公钥（省略 "x5c" 证书链）
+    const RFC7517_B: &str = r#"{"kty":"RSA",
+      "use":"sig",
+      "kid":"1b94c",
+      "n":"vrjOfz9Ccdgx5nQudyhdoR17V-IubWMeOZCwX_jj0hgAsz2J_pqYW08PLbK_PdiVGKPrqzmDIsLI7sA25VEnHU1uCLNwBuUiCO11_-7dYbsr4iJmG0Qu2j8DsVyT1azpJC_NG84Ty5KKthuCaPod7iI7w0LK9orSMhBEwwZDCxTWq4aYWAchc8t-emd9qOvWtVMDC2BXksRngh6X5bUYLy6AyHKvj-nUy1wgzjYQDwHMTplCoLtU-o-8SNnZ1tmRoGE9uJkBLdh5gFENabWnU5m1ZqZPdwS-qo-meMvVfJb6jJVWRpl2SUtCnYG2C32qvbWbjZ_jBPD5eunqsIo1vQ",
+      "e":"AQAB"}"#;
+
+    fn member(text: &str, name: &str) -> BigUint {
+        Jwk::parse(text).unwrap().uint(name).unwrap()
+    }
+
+    #[test]
+    fn rfc7517_public_keys() {
+        let set = JsonValue::parse(RFC7517_A1).unwrap();
+        let keys = set.get("keys").and_then(JsonValue::as_array).unwrap();
+        let [ec, rsa] = keys else { panic!("A.1 应含两个密钥") };
+
+        assert!(RSA::from_jwk(&ec.to_string()).is_none());
+        let rsa = RSA::from_jwk(&rsa.to_string()).unwrap();
+        assert!(!rsa.has_private_key());
+        assert_eq!(rsa.n().bits(), 2048);
+        assert_eq!(rsa.e(), &BigUint::from(65537u32));
+        // 导出只保留 n、e，"alg"、"kid" 等未解释成员被丢弃
+        let exported = Jwk::parse(&rsa.to_public_jwk()).unwrap();
+        assert_eq!(exported.get("n"), keys[1].get("n"));
+        assert!(!exported.contains("kid"));
+
+        let rsa = RSA::from_jwk(RFC7517_B).unwrap();
+        assert_eq!(rsa.n(), &member(RFC7517_B, "n"));
+        assert_eq!(rsa.n().bits(), 2048);
+    }
+
+    #[test]
+    fn rfc7517_private_key() {
+        let rsa = RSA::from_jwk(RFC7517_A2_RSA).unwrap();
+        let key = rsa.crt_key().unwrap();
+        assert_eq!(key.validate(), Ok(()));
+        let exported = Jwk::parse(&rsa.to_jwk().unwrap()).unwrap();
+        for name in CRT_MEMBERS.iter().chain(&["n", "e", "d"]) {
+            assert_eq!(exported.uint(name), Some(member(RFC7517_A2_RSA, name)), "{}", name);
+        }
+
+        let message = BigUint::from(0x1234_5678u32);
+        let c = message.modpow(rsa.e(), rsa.n());
+        assert_eq!(key.decrypt_raw(&c), Some(message));
+
+        // 任何一个 CRT 成员与 n、e、d 不一致都要拒绝
+        for name in CRT_MEMBERS {
+            let mut jwk = Jwk::parse(RFC7517_A2_RSA).unwrap();
+            jwk.set_uint(name, &(member(RFC7517_A2_RSA, name) + 2u32));
+            assert!(RSA::from_jwk(&jwk.to_json()).is_none(), "{}", name);
+        }
+        let mut missing_q = JsonValue::parse(RFC7517_A2_RSA).unwrap();
+        if let JsonValue::Object(members) = &mut missing_q {
+            members.retain(|(name, _)| name != "q");
+        }
+        assert!(RSA::from_jwk(&missing_q.to_string()).is_none());
+    }
+
+    #[test]
+    fn multi_prime_private_key_round_trip() {
+        let mut rng = HmacDrbg::new(&[0x43; 32], b"nonce", b"rsa jwk test").unwrap();
+        let key = RsaPrivateKey::generate_multi_prime(1024, 3, &BigUint::from(65537u32), &mut rng).unwrap();
+        let rsa = RSA::from_crt_key(key.clone());
+        let text = rsa.to_jwk().unwrap();
+
+        let jwk = Jwk::parse(&text).unwrap();
+        let oth = jwk.array("oth").unwrap();
+        assert_eq!(oth.len(), 1);
+        assert_eq!(object_uint(&oth[0], "r").as_ref(), Some(key.other_primes()[0].prime()));
+
+        let imported = RSA::from_jwk(&text).unwrap();
+        assert_eq!(imported.crt_key(), Some(&key));
+        assert_eq!(imported.to_jwk().unwrap(), text);
+
+        // "oth" 中的指数或系数被篡改、或者为空数组时拒绝
+        for field in ["d", "t"] {
+            let original = object_uint(&oth[0], field).unwrap();
+            let mut entry = oth[0].clone();
+            if let JsonValue::Object(members) = &mut entry {
+                for (name, value) in members.iter_mut() {
+                    if name == field {
+                        *value = JsonValue::String(encode_uint(&(original.clone() + 1u32)));
+                    }
+                }
+            }
+            let mut modified = jwk.clone();
+            modified.set("oth", JsonValue::Array(vec![entry]));
+            assert!(RSA::from_jwk(&modified.to_json()).is_none(), "{}", field);
+        }
+        let mut empty = jwk.clone();
+        empty.set("oth", JsonValue::Array(Vec::new()));
+        assert!(RSA::from_jwk(&empty.to_json()).is_none());
+    }
+
+    #[test]
+    fn private_key_without_crt_members() {
+        let jwk = Jwk::parse(RFC7517_A2_RSA).unwrap();
+        let mut minimal = Jwk::new(JWK_KTY);
+        for name in ["n", "e", "d"] {
+            minimal.set(name, jwk.get(name).unwrap().clone());
+        }
+        let rsa = RSA::from_jwk(&minimal.to_json()).unwrap();
+        assert!(rsa.has_private_key());
+        assert!(rsa.crt_key().is_none());
+        assert_eq!(rsa.to_jwk().unwrap(), minimal.to_json());
+
+        minimal.set_uint("d", &(member(RFC7517_A2_RSA, "d") + 1u32));
+        assert!(RSA::from_jwk(&minimal.to_json()).is_none());
+    }
+
+    #[test]
+    fn base64url_uint_encoding() {
+        assert_eq!(encode_uint(&BigUint::from(65537u32)), "AQAB");
+        assert_eq!(encode_uint(&BigUint::zero()), "AA");
+        assert_eq!(decode_uint("AAEAAQ"), Some(BigUint::from(65537u32)));
+        assert_eq!(decode_uint(""), None);
+        assert_eq!(decode_uint("AQAC"), Some(BigUint::from(65538u32)));
+        assert_eq!(decode_uint("AQB"), None);
+        assert_eq!(decode_uint("AQ+B"), None);
+    }
+}
@@ -1,3 +1,61 @@
+pub mod analysis;
+pub mod blind;
+mod blinding;
+pub mod envelope;
+pub mod jwk;
+pub mod padding;
+pub mod pem;
+pub mod pkcs1;
+pub mod pkcs8;
+pub mod private_key;
+pub mod provable;
 pub mod rsa;
+pub mod signature;
+pub mod validation;
+
+pub use blind::BlindVariant;
+pub use padding::{mgf1, RsaPadding};
+pub use pem::KeyFormat;
+pub use private_key::{OtherPrimeInfo, RsaPrivateKey};
+pub use provable::ProvableSeed;
+pub use signature::SignatureScheme;
+pub use validation::KeyError;
+
+use std::fmt;
+
+/// RSA 错误类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RsaError {
+    /// 消息超出当前模长和填充方式允许的长度
+    MessageTooLong,
+    /// 解密失败；为避免成为填充预言机，不区分具体原因
+    Decryption,
+    /// 摘要算法不能用于该填充方式
+    UnsupportedHash,
+    /// 缺少私钥或私钥运算失败
+    PrivateKeyUnavailable,
+    /// 模长太小，无法容纳填充
+    KeyTooSmall,
+    /// 输入不在允许的范围内（例如盲签名中长度不符或大于模数的消息）
+    InvalidInput,
+    /// 签名验证失败
+    Verification,
+}
+
+impl fmt::Display for RsaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RsaError::MessageTooLong => write!(f, "消息过长"),
+            RsaError::Decryption => write!(f, "解密失败"),
+            RsaError::UnsupportedHash => write!(f, "不支持的摘要算法"),
+            RsaError::PrivateKeyUnavailable => write!(f, "私钥不可用"),
+            RsaError::KeyTooSmall => write!(f, "密钥长度不足"),
+            RsaError::InvalidInput => write!(f, "输入无效"),
+            RsaError::Verification => write!(f, "签名验证失败"),
+        }
+    }
+}
+
+impl std::error::Error for RsaError {}
 
 // 如果需要，添加其他导出 
\ No newline at end of file
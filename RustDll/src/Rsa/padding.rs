@@ -0,0 +1,472 @@
+//! RSA 填充方案 (RFC 8017)：MGF1 掩码生成函数、RSAES-OAEP 与 RSAES-PKCS1-v1_5
+
+use super::RsaError;
+use crate::hash::{constant_time_eq, HashAlgorithm, Hmac};
+use num_bigint::BigUint;
+use rand::RngCore;
+
+/// 加密填充方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RsaPadding {
+    /// RSAES-PKCS1-v1_5，仅用于与旧系统互通，解密使用隐式拒绝
+    Pkcs1v15,
+    /// RSAES-OAEP，OAEP 与 MGF1 使用同一摘要算法，标签为空
+    Oaep(HashAlgorithm),
+}
+
+impl RsaPadding {
+    /// 由 FFI 的填充编号（CRYPTO_RSA_PADDING_*）和摘要算法编号得到填充方式
+    pub fn from_id(padding: i32, hash_algorithm: i32) -> Option<Self> {
+        match padding {
+            1 => Some(RsaPadding::Pkcs1v15),
+            2 => HashAlgorithm::from_id(hash_algorithm).map(RsaPadding::Oaep),
+            _ => None,
+        }
+    }
+}
+
+/// MGF1 掩码生成函数 (RFC 8017 B.2.1)
+pub fn mgf1(algorithm: HashAlgorithm, seed: &[u8], len: usize) -> Vec<u8> {
+    let mut mask = Vec::with_capacity(len + algorithm.output_len());
+    let mut counter = 0u32;
+    while mask.len() < len {
+        let mut hasher = algorithm.hasher();
+        hasher.update(seed);
+        hasher.update(&counter.to_be_bytes());
+        mask.extend_from_slice(&hasher.finalize());
+        counter += 1;
+    }
+    mask.truncate(len);
+    mask
+}
+
+/// EME-OAEP 编码，k 为模长（字节）
+pub fn oaep_encode<R: RngCore + ?Sized>(
+    algorithm: HashAlgorithm,
+    message: &[u8],
+    label: &[u8],
+    k: usize,
+    rng: &mut R,
+) -> Result<Vec<u8>, RsaError> {
+    if algorithm.is_xof() {
+        return Err(RsaError::UnsupportedHash);
+    }
+    let h_len = algorithm.output_len();
+    if k < 2 * h_len + 2 {
+        return Err(RsaError::KeyTooSmall);
+    }
+    if message.len() > k - 2 * h_len - 2 {
+        return Err(RsaError::MessageTooLong);
+    }
+
+    // DB = lHash || PS || 0x01 || M
+    let mut db = algorithm.digest(label);
+    db.resize(k - h_len - 1 - message.len() - 1, 0);
+    db.push(0x01);
+    db.extend_from_slice(message);
+
+    let mut seed = vec![0u8; h_len];
+    rng.fill_bytes(&mut seed);
+
+    xor_in_place(&mut db, &mgf1(algorithm, &seed, k - h_len - 1));
+    xor_in_place(&mut seed, &mgf1(algorithm, &db, h_len));
+
+    let mut em = Vec::with_capacity(k);
+    em.push(0x00);
+    em.extend_from_slice(&seed);
+    em.extend_from_slice(&db);
+    Ok(em)
+}
+
+/// EME-OAEP 解码，em 必须为 k 字节
+///
+/// 所有检查都会完整执行，任何失败都只返回 `RsaError::Decryption`，不泄露失败的位置。
+pub fn oaep_decode(algorithm: HashAlgorithm, em: &[u8], label: &[u8], k: usize) -> Result<Vec<u8>, RsaError> {
+    if algorithm.is_xof() {
+        return Err(RsaError::UnsupportedHash);
+    }
+    let h_len = algorithm.output_len();
+    if k < 2 * h_len + 2 || em.len() != k {
+        return Err(RsaError::Decryption);
+    }
+
+    let mut seed = em[1..1 + h_len].to_vec();
+    let mut db = em[1 + h_len..].to_vec();
+    xor_in_place(&mut seed, &mgf1(algorithm, &db, h_len));
+    xor_in_place(&mut db, &mgf1(algorithm, &seed, k - h_len - 1));
+
+    let l_hash = algorithm.digest(label);
+    let mut good = ct_mask(constant_time_eq(&db[..h_len], &l_hash)) & ct_is_zero(em[0]);
+
+    // 在 PS 之后寻找第一个 0x01，遍历整个 DB 而不提前退出
+    let mut looking = 0xffu8;
+    let mut invalid = 0u8;
+    let mut index = 0usize;
+    for (i, &b) in db.iter().enumerate().skip(h_len) {
+        let is_zero = ct_is_zero(b);
+        let is_one = ct_is_zero(b ^ 0x01);
+        let found = looking & is_one;
+        index = ct_select_usize(found, i, index);
+        invalid |= looking & !is_zero & !is_one;
+        looking &= !is_one;
+    }
+    good &= !looking & !invalid;
+
+    if std::hint::black_box(good) != 0xff {
+        return Err(RsaError::Decryption);
+    }
+    Ok(db[index + 1..].to_vec())
+}
+
+/// EME-PKCS1-v1_5 编码：EM = 0x00 || 0x02 || PS || 0x00 || M，PS 为至少 8 字节的非零随机数
+pub fn pkcs1v15_encode<R: RngCore + ?Sized>(message: &[u8], k: usize, rng: &mut R) -> Result<Vec<u8>, RsaError> {
+    if k < 11 {
+        return Err(RsaError::KeyTooSmall);
+    }
+    if message.len() > k - 11 {
+        return Err(RsaError::MessageTooLong);
+    }
+
+    let mut em = Vec::with_capacity(k);
+    em.push(0x00);
+    em.push(0x02);
+    let mut byte = [0u8; 1];
+    while em.len() < k - message.len() - 1 {
+        rng.fill_bytes(&mut byte);
+        if byte[0] != 0 {
+            em.push(byte[0]);
+        }
+    }
+    em.push(0x00);
+    em.extend_from_slice(message);
+    Ok(em)
+}
+
+// 隐式拒绝时尝试的候选长度个数
+const MAX_LEN_GEN_TRIES: usize = 128;
+
+/// 隐式拒绝的密钥派生密钥：KDK = HMAC-SHA256(SHA256(I2OSP(d, k)), C)
+pub(crate) fn implicit_rejection_kdk(d: &BigUint, ciphertext: &[u8], k: usize) -> Vec<u8> {
+    let d_hash = HashAlgorithm::Sha256.digest(&i2osp(d, k));
+    let mut hmac = Hmac::new(HashAlgorithm::Sha256, &d_hash).expect("SHA-256 supports HMAC");
+    hmac.update(&vec![0u8; k.saturating_sub(ciphertext.len())]);
+    hmac.update(ciphertext);
+    hmac.finalize()
+}
+
+// 隐式拒绝使用的 PRF：HMAC-SHA256(KDK, I2OSP(i, 2) || label || I2OSP(bits, 2)) 逐块拼接
+fn implicit_rejection_prf(kdk: &[u8], label: &[u8], len: usize) -> Vec<u8> {
+    let bits = ((len * 8) as u16).to_be_bytes();
+    let mut out = Vec::with_capacity(len + 32);
+    let mut iter = 0u16;
+    while out.len() < len {
+        let mut hmac = Hmac::new(HashAlgorithm::Sha256, kdk).expect("SHA-256 supports HMAC");
+        hmac.update(&iter.to_be_bytes());
+        hmac.update(label);
+        hmac.update(&bits);
+        out.extend_from_slice(&hmac.finalize());
+        iter += 1;
+    }
+    out.truncate(len);
+    out
+}
+
+/// EME-PKCS1-v1_5 解码（隐式拒绝）
+///
+/// 填充错误时不返回错误，而是返回由 KDK 确定性派生的伪随机消息，调用方无法据此构造
+/// Bleichenbacher 预言机。整个过程不依赖填充是否正确而分支。
+pub fn pkcs1v15_decode_implicit(em: &[u8], kdk: &[u8]) -> Vec<u8> {
+    let k = em.len();
+    if k < 11 {
+        return Vec::new();
+    }
+
+    // 先准备好替代消息：在候选长度中选出最后一个小于 max_sep_offset 的值
+    let synthetic = implicit_rejection_prf(kdk, b"message", k);
+    let candidates = implicit_rejection_prf(kdk, b"length", MAX_LEN_GEN_TRIES * 2);
+    let max_sep_offset = k - 2 - 8;
+    let mut len_mask = max_sep_offset;
+    len_mask |= len_mask >> 1;
+    len_mask |= len_mask >> 2;
+    len_mask |= len_mask >> 4;
+    len_mask |= len_mask >> 8;
+
+    let mut synthetic_length = 0usize;
+    for pair in candidates.chunks(2) {
+        let candidate = (((pair[0] as usize) << 8) | pair[1] as usize) & len_mask;
+        let below = ct_lt_usize(candidate, max_sep_offset);
+        synthetic_length = ct_select_usize(below, candidate, synthetic_length);
+    }
+    let synthetic_index = k - synthetic_length;
+
+    // 检查真实填充
+    let mut good = ct_is_zero(em[0]) & ct_is_zero(em[1] ^ 0x02);
+    let mut found_zero = 0u8;
+    let mut zero_index = 0usize;
+    for (i, &b) in em.iter().enumerate().skip(2) {
+        let is_zero = ct_is_zero(b);
+        zero_index = ct_select_usize(!found_zero & is_zero, i, zero_index);
+        found_zero |= is_zero;
+    }
+    // PS 至少 8 字节；找不到分隔符时 zero_index 为 0，同样不通过
+    good &= !ct_lt_usize(zero_index, 2 + 8);
+
+    let message_index = ct_select_usize(good, zero_index + 1, synthetic_index);
+    (message_index..k)
+        .map(|i| ct_select_u8(good, em[i], synthetic[i]))
+        .collect()
+}
+
+/// I2OSP：把整数编码为定长大端字节串，x 必须小于 256^len
+pub(crate) fn i2osp(x: &BigUint, len: usize) -> Vec<u8> {
+    let bytes = x.to_bytes_be();
+    let mut out = vec![0u8; len - bytes.len()];
+    out.extend_from_slice(&bytes);
+    out
+}
+
+fn xor_in_place(data: &mut [u8], mask: &[u8]) {
+    for (a, b) in data.iter_mut().zip(mask.iter()) {
+        *a ^= b;
+    }
+}
+
+// 常数时间辅助函数，掩码取值为 0x00 或 0xff
+fn ct_is_zero(b: u8) -> u8 {
+    ((b as u16).wrapping_sub(1) >> 8) as u8
+}
+
+fn ct_mask(flag: bool) -> u8 {
+    0u8.wrapping_sub(flag as u8)
+}
+
+fn ct_lt_usize(a: usize, b: usize) -> u8 {
+    let borrow = (a as u128).wrapping_sub(b as u128) >> 127;
+    0u8.wrapping_sub(borrow as u8)
+}
+
+fn ct_select_u8(mask: u8, a: u8, b: u8) -> u8 {
+    (a & mask) | (b & !mask)
+}
+
+fn ct_select_usize(mask: u8, a: usize, b: usize) -> usize {
+    let m = 0usize.wrapping_sub((mask & 1) as usize);
+    (a & m) | (b & !m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rsa::RsaPrivateKey;
+    use crate::Rsa::rsa::RSA;
+    use crate::numtheory::mod_inverse;
+
+    // oaep-vect.txt（PKCS #1 v2.1 测试向量）示例 1：1024 位密钥，e = 65537，OAEP 与 MGF1 均使用 SHA-1
+    const N: &str = concat!(
+        "a8b3b284af8eb50b387034a860f146c4919f318763cd6c5598c8ae4811a1e0abc4c7e0b082d693a5e7fced675cf46685",
+        "12772c0cbc64a742c6c630f533c8cc72f62ae833c40bf25842e984bb78bdbf97c0107d55bdb662f5c4e0fab9845cb514",
+        "8ef7392dd3aaff93ae1e6b667bb3d4247616d4f5ba10d4cfd226de88d39f16fb",
+    );
+    const P: &str = concat!(
+        "d32737e7267ffe1341b2d5c0d150a81b586fb3132bed2f8d5262864a9cb9f30af38be448598d413a172efb802c21acf1",
+        "c11c520c2f26a471dcad212eac7ca39d",
+    );
+    const Q: &str = concat!(
+        "cc8853d1d54da630fac004f471f281c7b8982d8224a490edbeb33d3e3d5cc93c4765703d1dd791642f1f116a0dd852be",
+        "2419b2af72bfe9a030e860b0288b5d77",
+    );
+
+    // (消息, seed, 密文)：示例 1.1 与 1.3
+    const EXAMPLES: [(&str, &str, &str); 2] = [
+        (
+            "6628194e12073db03ba94cda9ef9532397d50dba79b987004afefe34",
+            "18b776ea21069d69776a33e96bad48e1dda0a5ef",
+            concat!(
+                "354fe67b4a126d5d35fe36c777791a3f7ba13def484e2d3908aff722fad468fb21696de95d0be911c2d3174f8afcc201",
+                "035f7b6d8e69402de5451618c21a535fa9d7bfc5b8dd9fc243f8cf927db31322d6e881eaa91a996170e657a05a266426",
+                "d98c88003f8477c1227094a0d9fa1e8c4024309ce1ecccb5210035d47ac72e8a",
+            ),
+        ),
+        (
+            concat!(
+                "d94ae0832e6445ce42331cb06d531a82b1db4baad30f746dc916df24d4e3c2451fff59a6423eb0e1d02d4fe646cf699d",
+                "fd818c6e97b051",
+            ),
+            "2514df4695755a67b288eaf4905c36eec66fd2fd",
+            concat!(
+                "423736ed035f6026af276c35c0b3741b365e5f76ca091b4e8c29e2f0befee603595aa8322d602d2e625e95eb81b2f1c9",
+                "724e822eca76db8618cf09c5343503a4360835b5903bc637e3879fb05e0ef32685d5aec5067cd7cc96fe4b2670b6eac3",
+                "066b1fcf5686b68589aafb7d629b02d8f8625ca3833624d4800fb081b1cf94eb",
+            ),
+        ),
+    ];
+
+    // 依次吐出向量中的 seed，作为 OAEP 的随机数输出
+    struct FixedSeed(std::vec::IntoIter<u8>);
+
+    impl FixedSeed {
+        fn new(seed: Vec<u8>) -> Self {
+            FixedSeed(seed.into_iter())
+        }
+    }
+
+    impl RngCore for FixedSeed {
+        fn next_u32(&mut self) -> u32 {
+            let mut bytes = [0u8; 4];
+            self.fill_bytes(&mut bytes);
+            u32::from_le_bytes(bytes)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            let mut bytes = [0u8; 8];
+            self.fill_bytes(&mut bytes);
+            u64::from_le_bytes(bytes)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            for byte in dest {
+                *byte = self.0.next().expect("seed bytes exhausted");
+            }
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    fn number(hex_str: &str) -> BigUint {
+        BigUint::from_bytes_be(&hex::decode(hex_str).unwrap())
+    }
+
+    fn vector_d() -> BigUint {
+        let (p, q) = (number(P), number(Q));
+        mod_inverse(&BigUint::from(65537u32), &((&p - 1u32) * (&q - 1u32))).unwrap()
+    }
+
+    fn vector_key() -> RSA {
+        let e = BigUint::from(65537u32);
+        RSA::from_crt_key(RsaPrivateKey::from_components(number(N), e, vector_d(), number(P), number(Q)).unwrap())
+    }
+
+    #[test]
+    fn oaep_vect_sha1_examples() {
+        let rsa = vector_key();
+        let k = rsa.modulus_len();
+        for (message, seed, ciphertext) in EXAMPLES {
+            let (message, ciphertext) = (hex::decode(message).unwrap(), hex::decode(ciphertext).unwrap());
+            let mut rng = FixedSeed::new(hex::decode(seed).unwrap());
+
+            let em = oaep_encode(HashAlgorithm::Sha1, &message, &[], k, &mut rng).unwrap();
+            // OAEP 恰好消耗 hLen 字节的 seed
+            assert_eq!(rng.0.len(), 0);
+            let c = BigUint::from_bytes_be(&em).modpow(rsa.e(), rsa.n());
+            assert_eq!(i2osp(&c, k), ciphertext);
+
+            assert_eq!(rsa.decrypt_oaep(HashAlgorithm::Sha1, &ciphertext, &[]).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn oaep_rejects_modified_ciphertext_and_label() {
+        let rsa = vector_key();
+        let ciphertext = hex::decode(EXAMPLES[0].2).unwrap();
+        assert_eq!(rsa.decrypt_oaep(HashAlgorithm::Sha1, &ciphertext, b"label"), Err(RsaError::Decryption));
+        assert_eq!(rsa.decrypt_oaep(HashAlgorithm::Sha256, &ciphertext, &[]), Err(RsaError::Decryption));
+
+        let mut modified = ciphertext;
+        modified[64] ^= 0x01;
+        assert_eq!(rsa.decrypt_oaep(HashAlgorithm::Sha1, &modified, &[]), Err(RsaError::Decryption));
+
+        let message = [0u8; 128 - 2 * 20 - 1];
+        let mut rng = FixedSeed::new(vec![0u8; 20]);
+        assert_eq!(oaep_encode(HashAlgorithm::Sha1, &message, &[], 128, &mut rng), Err(RsaError::MessageTooLong));
+    }
+
+    #[test]
+    fn pkcs1v15_round_trip() {
+        let rsa = vector_key();
+        let k = rsa.modulus_len();
+        for len in [0, 1, 16, 64, k - 11] {
+            let message: Vec<u8> = (0..len).map(|i| i as u8).collect();
+            let ciphertext = rsa.encrypt_pkcs1v15(&message).unwrap();
+            assert_eq!(ciphertext.len(), k);
+            assert_eq!(rsa.decrypt_pkcs1v15(&ciphertext).unwrap(), message);
+        }
+        assert_eq!(rsa.encrypt_pkcs1v15(&vec![0u8; k - 10]), Err(RsaError::MessageTooLong));
+    }
+
+    // OpenSSL 3.5 `pkeyutl -decrypt -pkeyopt rsa_padding_mode:pkcs1` 的输出，OpenSSL 按
+    // draft-irtf-cfrg-rsa-guidance 实现隐式拒绝；密钥为上面的 oaep-vect 示例 1。
+    // (密文, 替代消息)，对应的 EM 分别为：没有 0x00 分隔符、块类型为 0x01、PS 只有 7 字节
+    const IMPLICIT_REJECTION: [(&str, &str); 3] = [
+        (
+            concat!(
+                "1660514eaa637b1bdd6c07aff60a52350690a27b258cdaf310bef33751a904718ae954cb3eda0f8ac2270c7c4ec9b726",
+                "9dee25466323cec149837464b13e453079cdf7dc87e3e6a71609a1a10cc840a01c992bcc546f71fa4e4fcc858f2493cd",
+                "835f54b6674cb679c8ed1a813c084d6effbb987678a4c5c802d551232c3cc196",
+            ),
+            concat!(
+                "f102a8ab1670fdcbbe5d525c6952dc5788d41e92f7404bb03d2ce6047c206f5df7cddd5d1d570e94f203ad194bdd5fc6",
+                "3f6fb8079433dbbfcfc8bda328d42ede83a8e8505065abb16083ab2e",
+            ),
+        ),
+        (
+            concat!(
+                "6a117aa9a0aa746b5364eca4f04eebfed503ba4ebd831bba875aab95f6fa2469b17b86a4e609d2b2515abef94e253c03",
+                "4d42913d54f41d519a8a8aacb025150a684faa3614160d84f2390ae4c7422d686ccc6cd2ed56cc73a7b3465cf5687657",
+                "0634e5b71e357505ca5a407ce97f739f9b29aa97bd85edaf66ea3d568899a036",
+            ),
+            concat!(
+                "11dec645c01c60320a52c19593547d14cc903b695c5261460e7b16ec3e20d369f825768fd5f547837e5d4ce076b03d02",
+                "1059edeea18a3b0589e9166a88c3a8bfea278ef925fe2a0e22891886",
+            ),
+        ),
+        (
+            concat!(
+                "9a5dd0f7dc93da0289c357447debe2ecb9406d1ada17b993937fdbab1ceb738dedc05b6006468eb6122cc507014b7813",
+                "00708c7ab3d75b6b53f9e6dceb8b0379bfde271045ce3a847f8b395129ea9159c8c6dc03b7b593326795a6a603e603d3",
+                "44c83ea3f4c788ac295e1004bc227c149e7d06bd931460adb59f8648a8f2dc75",
+            ),
+            "e03a8398eaac568b947352365bc7d0b9f69eed472453caffaf840c561769def019386c1aeabbddb2026c07",
+        ),
+    ];
+
+    #[test]
+    fn implicit_rejection_matches_openssl() {
+        let rsa = vector_key();
+        for (ciphertext, synthetic) in IMPLICIT_REJECTION {
+            let ciphertext = hex::decode(ciphertext).unwrap();
+            assert_eq!(hex::encode(rsa.decrypt_pkcs1v15(&ciphertext).unwrap()), synthetic);
+        }
+    }
+
+    #[test]
+    fn implicit_rejection_is_deterministic() {
+        let rsa = vector_key();
+        let k = rsa.modulus_len();
+        let ciphertext = hex::decode(IMPLICIT_REJECTION[0].0).unwrap();
+        let first = rsa.decrypt_pkcs1v15(&ciphertext).unwrap();
+        assert_eq!(rsa.decrypt_pkcs1v15(&ciphertext).unwrap(), first);
+        // 替代消息的长度落在正常明文的取值范围内
+        assert!(first.len() <= k - 11);
+
+        // 替代消息只由 KDK 决定：同一 KDK 下不同的错误填充得到同一结果
+        let kdk = implicit_rejection_kdk(&vector_d(), &ciphertext, k);
+        let no_separator = [&[0x00, 0x02][..], &[0x11; 126]].concat();
+        let wrong_type = [&[0x00, 0x01][..], &[0xff; 100], &[0x00], &[0x41; 25]].concat();
+        let short_ps = [&[0x00, 0x02][..], &[0x01; 7], &[0x00], &[0x41; 118]].concat();
+        assert_eq!(pkcs1v15_decode_implicit(&no_separator, &kdk), first);
+        assert_eq!(pkcs1v15_decode_implicit(&wrong_type, &kdk), first);
+        assert_eq!(pkcs1v15_decode_implicit(&short_ps, &kdk), first);
+
+        // 填充正确时返回真实消息，与 KDK 无关
+        let valid = [&[0x00, 0x02][..], &[0x01; 8], &[0x00], &[0x41; 117]].concat();
+        assert_eq!(pkcs1v15_decode_implicit(&valid, &kdk), vec![0x41; 117]);
+
+        // KDK 绑定密文：换一个密文得到不同的替代消息
+        let other = hex::decode(IMPLICIT_REJECTION[1].0).unwrap();
+        let other_kdk = implicit_rejection_kdk(&vector_d(), &other, k);
+        assert_ne!(pkcs1v15_decode_implicit(&no_separator, &other_kdk), first);
+    }
+}
@@ -0,0 +1,121 @@
+//! RSA 密钥的 DER / PEM 导入导出，支持 PKCS#1、PKCS#8（含口令加密）与 SubjectPublicKeyInfo
+
+use super::pkcs1::{
+    public_key_from_pkcs1_der, public_key_to_pkcs1_der, PKCS1_PRIVATE_KEY_LABEL, PKCS1_PUBLIC_KEY_LABEL,
+};
+use super::pkcs8::{public_key_from_spki_der, public_key_to_spki_der, PKCS8_LABEL, SPKI_LABEL};
+use super::private_key::RsaPrivateKey;
+use super::rsa::RSA;
+use super::RsaError;
+use crate::encoding::pem;
+use crate::encoding::pkcs8::{
+    decrypt_private_key_info, encrypt_private_key_info, Pbes2Params, ENCRYPTED_PRIVATE_KEY_LABEL,
+};
+
+/// 密钥编码格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyFormat {
+    /// 公钥为 PKCS#1 RSAPublicKey，私钥为 PKCS#1 RSAPrivateKey
+    Pkcs1,
+    /// 公钥为 SubjectPublicKeyInfo，私钥为 PKCS#8 PrivateKeyInfo（OpenSSL 默认）
+    Pkcs8,
+}
+
+impl KeyFormat {
+    /// 由 FFI 的格式编号（CRYPTO_KEY_FORMAT_*）得到编码格式
+    pub fn from_id(format: i32) -> Option<Self> {
+        match format {
+            1 => Some(KeyFormat::Pkcs1),
+            2 => Some(KeyFormat::Pkcs8),
+            _ => None,
+        }
+    }
+}
+
+impl RSA {
+    /// 导出私钥（DER），需要带 CRT 参数的私钥
+    pub fn to_der(&self, format: KeyFormat) -> Result<Vec<u8>, RsaError> {
+        let key = self.crt_key().ok_or(RsaError::PrivateKeyUnavailable)?;
+        Ok(match format {
+            KeyFormat::Pkcs1 => key.to_pkcs1_der(),
+            KeyFormat::Pkcs8 => key.to_pkcs8_der(),
+        })
+    }
+
+    /// 导出私钥（PEM），需要带 CRT 参数的私钥
+    pub fn to_pem(&self, format: KeyFormat) -> Result<String, RsaError> {
+        let der = self.to_der(format)?;
+        Ok(pem::encode(private_key_label(format), &der))
+    }
+
+    /// 导出公钥（DER）
+    pub fn to_public_der(&self, format: KeyFormat) -> Vec<u8> {
+        match format {
+            KeyFormat::Pkcs1 => public_key_to_pkcs1_der(&self.n, &self.e),
+            KeyFormat::Pkcs8 => public_key_to_spki_der(&self.n, &self.e),
+        }
+    }
+
+    /// 导出公钥（PEM）
+    pub fn to_public_pem(&self, format: KeyFormat) -> String {
+        let label = match format {
+            KeyFormat::Pkcs1 => PKCS1_PUBLIC_KEY_LABEL,
+            KeyFormat::Pkcs8 => SPKI_LABEL,
+        };
+        pem::encode(label, &self.to_public_der(format))
+    }
+
+    /// 导入 DER 编码的私钥或公钥，自动识别四种格式；私钥的 CRT 参数不一致时返回 None
+    pub fn from_der(der: &[u8]) -> Option<Self> {
+        if let Some(key) = RsaPrivateKey::from_pkcs8_der(der).or_else(|| RsaPrivateKey::from_pkcs1_der(der)) {
+            return Some(Self::from_crt_key(key));
+        }
+        let (n, e) = public_key_from_spki_der(der).or_else(|| public_key_from_pkcs1_der(der))?;
+        Some(Self::from_public_parts(n, e))
+    }
+
+    /// 导入 PEM 编码的私钥或公钥，按标签识别格式
+    pub fn from_pem(text: &str) -> Option<Self> {
+        let (label, der) = pem::decode(text)?;
+        match label.as_str() {
+            PKCS8_LABEL => RsaPrivateKey::from_pkcs8_der(&der).map(Self::from_crt_key),
+            PKCS1_PRIVATE_KEY_LABEL => RsaPrivateKey::from_pkcs1_der(&der).map(Self::from_crt_key),
+            SPKI_LABEL => public_key_from_spki_der(&der).map(|(n, e)| Self::from_public_parts(n, e)),
+            PKCS1_PUBLIC_KEY_LABEL => public_key_from_pkcs1_der(&der).map(|(n, e)| Self::from_public_parts(n, e)),
+            _ => None,
+        }
+    }
+
+    /// 导出口令加密的 PKCS#8 私钥（EncryptedPrivateKeyInfo，DER）
+    ///
+    /// 没有 CRT 私钥或参数无效（PRF 为 SHAKE、迭代次数为 0）时返回 None。
+    pub fn to_encrypted_der(&self, password: &[u8], params: &Pbes2Params) -> Option<Vec<u8>> {
+        let der = self.to_der(KeyFormat::Pkcs8).ok()?;
+        encrypt_private_key_info(&der, password, params)
+    }
+
+    /// 导出口令加密的 PKCS#8 私钥（PEM，标签为 "ENCRYPTED PRIVATE KEY"）
+    pub fn to_encrypted_pem(&self, password: &[u8], params: &Pbes2Params) -> Option<String> {
+        let der = self.to_encrypted_der(password, params)?;
+        Some(pem::encode(ENCRYPTED_PRIVATE_KEY_LABEL, &der))
+    }
+
+    /// 导入口令加密的 PKCS#8 私钥（DER），口令错误时返回 None
+    pub fn from_encrypted_der(der: &[u8], password: &[u8]) -> Option<Self> {
+        let der = decrypt_private_key_info(der, password)?;
+        RsaPrivateKey::from_pkcs8_der(&der).map(Self::from_crt_key)
+    }
+
+    /// 导入口令加密的 PKCS#8 私钥（PEM），口令错误时返回 None
+    pub fn from_encrypted_pem(text: &str, password: &[u8]) -> Option<Self> {
+        let der = pem::decode_with_label(text, ENCRYPTED_PRIVATE_KEY_LABEL)?;
+        Self::from_encrypted_der(&der, password)
+    }
+}
+
+fn private_key_label(format: KeyFormat) -> &'static str {
+    match format {
+        KeyFormat::Pkcs1 => PKCS1_PRIVATE_KEY_LABEL,
+        KeyFormat::Pkcs8 => PKCS8_LABEL,
+    }
+}
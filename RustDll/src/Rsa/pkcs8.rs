@@ -0,0 +1,105 @@
+//! RSA 密钥的 PKCS#8 PrivateKeyInfo (RFC 5208) 与 X.509 SubjectPublicKeyInfo (RFC 5280) 编码
+//!
+//! ```text
+//! SubjectPublicKeyInfo ::= SEQUENCE { algorithm AlgorithmIdentifier, subjectPublicKey BIT STRING }
+//! PrivateKeyInfo ::= SEQUENCE {
+//!     version              INTEGER,           -- 0（RFC 5958 的 OneAsymmetricKey 为 1）
+//!     privateKeyAlgorithm  AlgorithmIdentifier,
+//!     privateKey           OCTET STRING,      -- PKCS#1 RSAPrivateKey
+//!     attributes           [0] IMPLICIT Attributes OPTIONAL,
+//!     publicKey            [1] IMPLICIT BIT STRING OPTIONAL
+//! }
+//! ```
+//!
+//! 算法标识为 rsaEncryption (1.2.840.113549.1.1.1)，参数为 NULL。
+
+use super::pkcs1::{public_key_from_pkcs1_der, public_key_to_pkcs1_der};
+use super::private_key::RsaPrivateKey;
+use crate::encoding::der::{
+    encode_integer, encode_sequence, encode_tlv, DerReader, TAG_BIT_STRING, TAG_NULL, TAG_OCTET_STRING, TAG_OID,
+};
+use num_bigint::BigUint;
+use num_traits::Zero;
+
+/// PEM 标签
+pub const SPKI_LABEL: &str = "PUBLIC KEY";
+pub const PKCS8_LABEL: &str = "PRIVATE KEY";
+
+// rsaEncryption 的 OID 内容字节
+const RSA_ENCRYPTION_OID: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01];
+
+// PrivateKeyInfo 之后可选的 attributes [0] 与 publicKey [1]
+const TAG_ATTRIBUTES: u8 = 0xa0;
+const TAG_PUBLIC_KEY: u8 = 0x81;
+
+fn rsa_algorithm_identifier() -> Vec<u8> {
+    encode_sequence(&[encode_tlv(TAG_OID, RSA_ENCRYPTION_OID), encode_tlv(TAG_NULL, &[])])
+}
+
+// 读取 AlgorithmIdentifier 并要求为 rsaEncryption，参数可以是 NULL 或省略
+fn read_rsa_algorithm_identifier(reader: &mut DerReader) -> Option<()> {
+    let mut alg = reader.read_sequence()?;
+    if alg.read(TAG_OID)? != RSA_ENCRYPTION_OID {
+        return None;
+    }
+    if !alg.is_empty() && !alg.read(TAG_NULL)?.is_empty() {
+        return None;
+    }
+    alg.is_empty().then_some(())
+}
+
+/// 编码为 SubjectPublicKeyInfo（DER）
+pub fn public_key_to_spki_der(n: &BigUint, e: &BigUint) -> Vec<u8> {
+    let mut bits = vec![0u8];
+    bits.extend_from_slice(&public_key_to_pkcs1_der(n, e));
+    encode_sequence(&[rsa_algorithm_identifier(), encode_tlv(TAG_BIT_STRING, &bits)])
+}
+
+/// 解析 SubjectPublicKeyInfo（DER），返回 (n, e)
+pub fn public_key_from_spki_der(der: &[u8]) -> Option<(BigUint, BigUint)> {
+    let mut outer = DerReader::new(der);
+    let mut seq = outer.read_sequence()?;
+    read_rsa_algorithm_identifier(&mut seq)?;
+    let bits = seq.read(TAG_BIT_STRING)?;
+    if !seq.is_empty() || !outer.is_empty() {
+        return None;
+    }
+    // 第一个字节为未用位数，必须为 0
+    match bits.split_first()? {
+        (0, key) => public_key_from_pkcs1_der(key),
+        _ => None,
+    }
+}
+
+impl RsaPrivateKey {
+    /// 编码为 PKCS#8 PrivateKeyInfo（DER，version 0）
+    pub fn to_pkcs8_der(&self) -> Vec<u8> {
+        encode_sequence(&[
+            encode_integer(&BigUint::zero()),
+            rsa_algorithm_identifier(),
+            encode_tlv(TAG_OCTET_STRING, &self.to_pkcs1_der()),
+        ])
+    }
+
+    /// 解析 PKCS#8 PrivateKeyInfo（DER），忽略可选的 attributes 与 publicKey
+    pub fn from_pkcs8_der(der: &[u8]) -> Option<Self> {
+        let mut outer = DerReader::new(der);
+        let mut seq = outer.read_sequence()?;
+        if !outer.is_empty() || seq.read_integer()? > BigUint::from(1u8) {
+            return None;
+        }
+        read_rsa_algorithm_identifier(&mut seq)?;
+        let key = seq.read(TAG_OCTET_STRING)?;
+
+        if seq.peek_tag() == Some(TAG_ATTRIBUTES) {
+            seq.read(TAG_ATTRIBUTES)?;
+        }
+        if seq.peek_tag() == Some(TAG_PUBLIC_KEY) {
+            seq.read(TAG_PUBLIC_KEY)?;
+        }
+        if !seq.is_empty() {
+            return None;
+        }
+        Self::from_pkcs1_der(key)
+    }
+}
@@ -0,0 +1,546 @@
+//! 带 CRT 参数的 RSA 私钥 (RFC 8017 3.2)
+//!
+//! 私钥运算按中国剩余定理分别在模 p、模 q 下进行，约比直接使用 d 快 4 倍；
+//! 每次运算后都用公钥指数验算结果，防止故障攻击泄露素因子。
+//!
+//! 也支持多素数 RSA（RFC 8017 3.2 的 otherPrimeInfos）：n 由 3 到 4 个素数相乘，
+//! 每个素数更短，私钥运算进一步加快。
+
+use super::blinding::Blinding;
+use super::rsa::search_large_prime;
+use super::validation::{check_private_key, check_public_exponent, KeyError, MAX_MODULUS_BITS};
+use crate::numtheory::mod_inverse;
+use crate::numtheory::search::{parallel_search, SearchOptions, SearchState};
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+
+/// 默认公钥指数 F4 = 65537
+pub const DEFAULT_PUBLIC_EXPONENT: u32 = 65537;
+
+/// 允许生成的最小模数位数
+pub const MIN_MODULUS_BITS: usize = 32;
+
+/// 多素数 RSA 允许的最多素因子个数
+pub const MAX_PRIMES: usize = 4;
+
+/// 给定模数位数时允许的最多素因子个数（与 OpenSSL 相同）：
+/// 小于 1024 位只允许 2 个，小于 4096 位最多 3 个，其余最多 4 个
+pub fn max_prime_count(bit_length: usize) -> usize {
+    match bit_length {
+        0..1024 => 2,
+        1024..4096 => 3,
+        _ => MAX_PRIMES,
+    }
+}
+
+/// 第三个及以后素因子的 CRT 参数（RFC 8017 OtherPrimeInfo）
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OtherPrimeInfo {
+    // 素因子 r_i
+    prime: BigUint,
+    // d_i = d mod (r_i - 1)
+    exponent: BigUint,
+    // t_i = (r_1 * ... * r_(i-1))^-1 mod r_i
+    coefficient: BigUint,
+}
+
+impl OtherPrimeInfo {
+    pub fn prime(&self) -> &BigUint {
+        &self.prime
+    }
+
+    pub fn exponent(&self) -> &BigUint {
+        &self.exponent
+    }
+
+    pub fn coefficient(&self) -> &BigUint {
+        &self.coefficient
+    }
+}
+
+/// RSA 私钥：n、e、d、CRT 参数 p、q、dP、dQ、qInv，以及多素数时的其余素因子
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RsaPrivateKey {
+    n: BigUint,
+    e: BigUint,
+    d: BigUint,
+    p: BigUint,
+    q: BigUint,
+    dp: BigUint,
+    dq: BigUint,
+    qinv: BigUint,
+    other_primes: Vec<OtherPrimeInfo>,
+    // 私钥运算是否使用基数盲化（默认开启）
+    blinding: bool,
+}
+
+impl RsaPrivateKey {
+    /// 生成新的 RSA 私钥，bit_length 为模数 n 的位数
+    pub fn generate(bit_length: usize) -> Result<Self, KeyError> {
+        Self::generate_with_rng(bit_length, &mut OsRng)
+    }
+
+    /// 使用指定的随机数生成器生成私钥（FIPS 186-5 A.1.3 的约束）
+    ///
+    /// - 两个素数各占模数一半的位数，且最高两位为 1，保证 n 恰好为 bit_length 位
+    /// - e 与 p-1、q-1 互素，否则重新生成该素数
+    /// - |p - q| > 2^(bit_length/2 - 100)
+    /// - d = e^-1 mod λ(n)，且 d > 2^(bit_length/2)
+    ///
+    /// bit_length 小于 `MIN_MODULUS_BITS` 时返回 `KeyError::ModulusTooSmall`，超过上限时返回 `KeyError::ModulusTooLarge`。
+    pub fn generate_with_rng<R: RngCore + CryptoRng + ?Sized>(bit_length: usize, rng: &mut R) -> Result<Self, KeyError> {
+        Self::generate_with_exponent(bit_length, &BigUint::from(DEFAULT_PUBLIC_EXPONENT), rng)
+    }
+
+    /// 使用指定的公钥指数生成私钥，e 必须是 3 <= e < 2^256 的奇数
+    pub fn generate_with_exponent<R: RngCore + CryptoRng + ?Sized>(
+        bit_length: usize,
+        e: &BigUint,
+        rng: &mut R,
+    ) -> Result<Self, KeyError> {
+        check_generation_params(bit_length, e)?;
+        Self::generate_two_prime(bit_length, e, |bits| Ok(generate_rsa_prime(bits, e, rng)))
+    }
+
+    /// 多线程生成私钥，约束与 `generate_with_exponent` 相同
+    ///
+    /// 每个素数都由 options.threads 个线程同时搜索；options.cancel 置位后返回 `KeyError::Cancelled`。
+    pub fn generate_parallel<R: RngCore + CryptoRng + ?Sized>(
+        bit_length: usize,
+        e: &BigUint,
+        options: &mut SearchOptions<'_>,
+        rng: &mut R,
+    ) -> Result<Self, KeyError> {
+        check_generation_params(bit_length, e)?;
+        Self::generate_two_prime(bit_length, e, |bits| {
+            Ok(parallel_search(options, rng, |worker, state| search_rsa_prime(bits, e, worker, state))?)
+        })
+    }
+
+    // 由 next_prime 依次取得 p、q，直到满足距离和 d 的约束
+    fn generate_two_prime(
+        bit_length: usize,
+        e: &BigUint,
+        mut next_prime: impl FnMut(usize) -> Result<BigUint, KeyError>,
+    ) -> Result<Self, KeyError> {
+        let min_distance = BigUint::one() << (bit_length / 2).saturating_sub(100);
+        loop {
+            let p = next_prime(bit_length.div_ceil(2))?;
+            let q = next_prime(bit_length / 2)?;
+
+            let distance = if p > q { &p - &q } else { &q - &p };
+            if distance <= min_distance {
+                continue;
+            }
+
+            let key = match Self::from_primes(p, q, e.clone()) {
+                Some(key) => key,
+                None => continue,
+            };
+            if key.d.bits() as usize <= bit_length / 2 {
+                continue;
+            }
+            return Ok(key);
+        }
+    }
+
+    /// 生成 prime_count 个素因子的多素数私钥（prime_count 为 2 时与 `generate_with_exponent` 相同）
+    ///
+    /// prime_count 不能超过 `max_prime_count(bit_length)`；前面的素数各占 bit_length / prime_count 位，
+    /// 最后一个素数取剩余位数，任意两个素数之差大于 2^(每个素数的位数 - 100)。
+    pub fn generate_multi_prime<R: RngCore + CryptoRng + ?Sized>(
+        bit_length: usize,
+        prime_count: usize,
+        e: &BigUint,
+        rng: &mut R,
+    ) -> Result<Self, KeyError> {
+        check_generation_params(bit_length, e)?;
+        if prime_count < 2 || prime_count > max_prime_count(bit_length) {
+            return Err(KeyError::InvalidPrimeCount);
+        }
+        if prime_count == 2 {
+            return Self::generate_with_exponent(bit_length, e, rng);
+        }
+
+        let prime_bits = bit_length / prime_count;
+        let min_distance = BigUint::one() << prime_bits.saturating_sub(100);
+        loop {
+            let mut primes = Vec::with_capacity(prime_count);
+            for i in 0..prime_count {
+                let bits = if i + 1 == prime_count { bit_length - prime_bits * i } else { prime_bits };
+                primes.push(generate_rsa_prime(bits, e, rng));
+            }
+
+            // 各素数最高两位为 1 也不能保证三个以上素数的乘积恰好为 bit_length 位
+            let n: BigUint = primes.iter().product();
+            if n.bits() as usize != bit_length {
+                continue;
+            }
+            let too_close = primes.iter().enumerate().any(|(i, a)| {
+                primes[i + 1..].iter().any(|b| (if a > b { a - b } else { b - a }) <= min_distance)
+            });
+            if too_close {
+                continue;
+            }
+
+            let key = match Self::from_multi_primes(primes, e.clone()) {
+                Some(key) => key,
+                None => continue,
+            };
+            if key.d.bits() as usize <= bit_length / 2 {
+                continue;
+            }
+            return Ok(key);
+        }
+    }
+
+    /// 由两个素数和公钥指数构造私钥（d 取 e 模 λ(n) 的逆），e 与 λ(n) 不互素时返回 None
+    pub fn from_primes(p: BigUint, q: BigUint, e: BigUint) -> Option<Self> {
+        Self::from_multi_primes(vec![p, q], e)
+    }
+
+    /// 由全部素因子（至少 2 个，互不相同）和公钥指数构造私钥，d 取 e 模 λ(n) = lcm(r_i - 1) 的逆
+    pub fn from_multi_primes(primes: Vec<BigUint>, e: BigUint) -> Option<Self> {
+        let one = BigUint::one();
+        let lambda = primes.iter().fold(one.clone(), |acc, r| {
+            if r <= &one { acc } else { acc.lcm(&(r - &one)) }
+        });
+        let d = mod_inverse(&e, &lambda)?;
+        let n = primes.iter().product();
+        Self::from_multi_prime_components(n, e, d, primes)
+    }
+
+    /// 由 n、e、d、p、q 构造私钥并计算 CRT 参数，分量不一致时返回 None
+    pub fn from_components(n: BigUint, e: BigUint, d: BigUint, p: BigUint, q: BigUint) -> Option<Self> {
+        Self::from_multi_prime_components(n, e, d, vec![p, q])
+    }
+
+    /// 由 n、e、d 和全部素因子（依次为 p、q、r_3 ...）构造私钥并计算 CRT 参数
+    ///
+    /// 素因子有重复、素因子之积不等于 n、或者 e·d ≢ 1 (mod lcm(r_i - 1)) 时返回 None。
+    pub fn from_multi_prime_components(n: BigUint, e: BigUint, d: BigUint, primes: Vec<BigUint>) -> Option<Self> {
+        let one = BigUint::one();
+        if primes.len() < 2 || primes.len() > MAX_PRIMES || primes.iter().any(|r| r <= &one) {
+            return None;
+        }
+        // n 含平方因子时不是合法的 RSA 模数，CRT 重组也无从谈起
+        if primes.iter().enumerate().any(|(i, r)| primes[i + 1..].contains(r)) {
+            return None;
+        }
+        if primes.iter().product::<BigUint>() != n {
+            return None;
+        }
+        let lambda = primes.iter().fold(one.clone(), |acc, r| acc.lcm(&(r - &one)));
+        if e.is_zero() || d.is_zero() || !(&e * &d - &one).is_multiple_of(&lambda) {
+            return None;
+        }
+
+        let mut primes = primes.into_iter();
+        let p = primes.next()?;
+        let q = primes.next()?;
+        let dp = &d % (&p - &one);
+        let dq = &d % (&q - &one);
+        let qinv = mod_inverse(&q, &p)?;
+
+        let mut product = &p * &q;
+        let mut other_primes = Vec::new();
+        for prime in primes {
+            let exponent = &d % (&prime - &one);
+            let coefficient = mod_inverse(&(&product % &prime), &prime)?;
+            product *= &prime;
+            other_primes.push(OtherPrimeInfo { prime, exponent, coefficient });
+        }
+        Some(RsaPrivateKey { n, e, d, p, q, dp, dq, qinv, other_primes, blinding: true })
+    }
+
+    /// 由完整的 CRT 参数构造私钥（d 取 e 模 λ(n) 的逆），分量不一致时返回 None
+    pub fn from_crt_components(
+        n: BigUint,
+        e: BigUint,
+        p: BigUint,
+        q: BigUint,
+        dp: BigUint,
+        dq: BigUint,
+        qinv: BigUint,
+    ) -> Option<Self> {
+        let one = BigUint::one();
+        if p <= one || q <= one || &p * &q != n {
+            return None;
+        }
+        let p1 = &p - &one;
+        let q1 = &q - &one;
+        let lambda = p1.lcm(&q1);
+        let d = mod_inverse(&e, &lambda)?;
+        if &d % &p1 != dp || &d % &q1 != dq || (&q * &qinv) % &p != one {
+            return None;
+        }
+        Some(RsaPrivateKey { n, e, d, p, q, dp, dq, qinv, other_primes: Vec::new(), blinding: true })
+    }
+
+    pub fn n(&self) -> &BigUint {
+        &self.n
+    }
+
+    pub fn e(&self) -> &BigUint {
+        &self.e
+    }
+
+    pub fn d(&self) -> &BigUint {
+        &self.d
+    }
+
+    pub fn p(&self) -> &BigUint {
+        &self.p
+    }
+
+    pub fn q(&self) -> &BigUint {
+        &self.q
+    }
+
+    pub fn dp(&self) -> &BigUint {
+        &self.dp
+    }
+
+    pub fn dq(&self) -> &BigUint {
+        &self.dq
+    }
+
+    pub fn qinv(&self) -> &BigUint {
+        &self.qinv
+    }
+
+    /// 第三个及以后素因子的 CRT 参数，双素数密钥为空
+    pub fn other_primes(&self) -> &[OtherPrimeInfo] {
+        &self.other_primes
+    }
+
+    /// 全部素因子：p、q 以及其余素因子
+    pub fn primes(&self) -> Vec<&BigUint> {
+        let mut primes = vec![&self.p, &self.q];
+        primes.extend(self.other_primes.iter().map(|info| &info.prime));
+        primes
+    }
+
+    /// 开启或关闭私钥运算的基数盲化（默认开启，关闭后运算时间可能泄露私钥信息）
+    pub fn with_blinding(mut self, enabled: bool) -> Self {
+        self.blinding = enabled;
+        self
+    }
+
+    /// 校验私钥的各分量是否合法且一致
+    pub fn validate(&self) -> Result<(), KeyError> {
+        check_private_key(self)
+    }
+
+    /// RSADP：c 必须小于 n；CRT 结果验算失败时返回 None
+    pub fn decrypt_raw(&self, c: &BigUint) -> Option<BigUint> {
+        if c >= &self.n {
+            return None;
+        }
+
+        let blinding = if self.blinding {
+            Some(Blinding::new(&self.e, &self.n)?)
+        } else {
+            None
+        };
+        let input = match &blinding {
+            Some(b) => b.blind(c, &self.n),
+            None => c.clone(),
+        };
+
+        let m = self.crt_exp(&input);
+        // 验算 m^e ≡ c (mod n)，故障导致的错误结果绝不能输出
+        if m.modpow(&self.e, &self.n) != input {
+            return None;
+        }
+
+        Some(match &blinding {
+            Some(b) => b.unblind(&m, &self.n),
+            None => m,
+        })
+    }
+
+    /// RSASP1：与 RSADP 相同的私钥运算，用于签名
+    pub fn sign_raw(&self, m: &BigUint) -> Option<BigUint> {
+        self.decrypt_raw(m)
+    }
+
+    /// 解密数据（无填充），密文按模 n 约化，与 `RSA::decrypt` 行为一致
+    pub fn decrypt(&self, data: &[u8]) -> Option<Vec<u8>> {
+        let c = BigUint::from_bytes_be(data) % &self.n;
+        self.decrypt_raw(&c).map(|m| m.to_bytes_be())
+    }
+
+    // m1 = c^dP mod p，m2 = c^dQ mod q，h = qInv (m1 - m2) mod p，m = m2 + q h；
+    // 多素数时再依次合并 m_i = c^d_i mod r_i：h = t_i (m_i - m) mod r_i，m = m + R h，R 为之前素数之积
+    fn crt_exp(&self, c: &BigUint) -> BigUint {
+        let m1 = c.modpow(&self.dp, &self.p);
+        let m2 = c.modpow(&self.dq, &self.q);
+        let diff = (&m1 + &self.p - (&m2 % &self.p)) % &self.p;
+        let h = (&self.qinv * diff) % &self.p;
+        let mut m = m2 + &self.q * h;
+
+        let mut r = &self.p * &self.q;
+        for info in &self.other_primes {
+            let mi = c.modpow(&info.exponent, &info.prime);
+            let diff = (&mi + &info.prime - (&m % &info.prime)) % &info.prime;
+            let h = (&info.coefficient * diff) % &info.prime;
+            m += &r * h;
+            r *= &info.prime;
+        }
+        m
+    }
+}
+
+// 检查密钥生成参数：模数位数在允许范围内，e 合法且小于 n
+fn check_generation_params(bit_length: usize, e: &BigUint) -> Result<(), KeyError> {
+    if bit_length < MIN_MODULUS_BITS {
+        return Err(KeyError::ModulusTooSmall);
+    }
+    if bit_length > MAX_MODULUS_BITS {
+        return Err(KeyError::ModulusTooLarge);
+    }
+    check_public_exponent(e)?;
+    // e 必须小于 n，n 至少为 2^(bit_length-1)
+    if e.bits() as usize >= bit_length {
+        return Err(KeyError::InvalidPublicExponent);
+    }
+    Ok(())
+}
+
+// 生成满足 gcd(p - 1, e) = 1 的素数
+fn generate_rsa_prime<R: RngCore + ?Sized>(bits: usize, e: &BigUint, rng: &mut R) -> BigUint {
+    search_rsa_prime(bits, e, rng, &SearchState::new()).expect("search is never stopped")
+}
+
+// 同 generate_rsa_prime，state 被停止时返回 None
+fn search_rsa_prime<R: RngCore + ?Sized>(bits: usize, e: &BigUint, rng: &mut R, state: &SearchState) -> Option<BigUint> {
+    loop {
+        let p = search_large_prime(bits, rng, state)?;
+        if (&p - BigUint::one()).gcd(e).is_one() {
+            return Some(p);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drbg::HmacDrbg;
+
+    fn test_rng(label: &[u8]) -> HmacDrbg {
+        HmacDrbg::new(&[0x40; 32], b"nonce", label).unwrap()
+    }
+
+    fn lambda(key: &RsaPrivateKey) -> BigUint {
+        key.primes().into_iter().fold(BigUint::one(), |acc, r| acc.lcm(&(r - 1u32)))
+    }
+
+    #[test]
+    fn generation_rejects_out_of_range_modulus_sizes() {
+        let mut rng = test_rng(b"sizes");
+        for bits in [0, 1, MIN_MODULUS_BITS - 1] {
+            assert_eq!(RsaPrivateKey::generate_with_rng(bits, &mut rng).err(), Some(KeyError::ModulusTooSmall));
+        }
+        let too_large = RsaPrivateKey::generate_with_rng(MAX_MODULUS_BITS + 1, &mut rng);
+        assert_eq!(too_large.err(), Some(KeyError::ModulusTooLarge));
+        let smallest = RsaPrivateKey::generate_with_rng(MIN_MODULUS_BITS, &mut rng).unwrap();
+        assert_eq!(smallest.n().bits() as usize, MIN_MODULUS_BITS);
+    }
+
+    #[test]
+    fn components_require_consistent_private_exponent() {
+        let key = RsaPrivateKey::generate_with_rng(1024, &mut test_rng(b"two primes")).unwrap();
+        let parts = |d: BigUint| {
+            RsaPrivateKey::from_components(key.n().clone(), key.e().clone(), d, key.p().clone(), key.q().clone())
+        };
+        assert_eq!(parts(key.d().clone()).as_ref(), Some(&key));
+
+        // d 加上 λ(n) 的倍数仍然满足 e·d ≡ 1 (mod λ(n))
+        let phi = (key.p() - 1u32) * (key.q() - 1u32);
+        assert!(parts(key.d() + lambda(&key)).is_some());
+        assert!(parts(mod_inverse(key.e(), &phi).unwrap()).is_some());
+
+        for d in [key.d() + 1u32, key.d() - 2u32, BigUint::one(), BigUint::zero(), key.d() + lambda(&key) / 2u32] {
+            assert!(parts(d).is_none());
+        }
+        let wrong_e = RsaPrivateKey::from_components(
+            key.n().clone(),
+            BigUint::from(3u32),
+            key.d().clone(),
+            key.p().clone(),
+            key.q().clone(),
+        );
+        assert!(wrong_e.is_none());
+    }
+
+    #[test]
+    fn multi_prime_components_require_consistent_private_exponent() {
+        let key = RsaPrivateKey::generate_multi_prime(1024, 3, &BigUint::from(65537u32), &mut test_rng(b"three primes"))
+            .unwrap();
+        let primes: Vec<BigUint> = key.primes().into_iter().cloned().collect();
+        let parts = |d: BigUint| {
+            RsaPrivateKey::from_multi_prime_components(key.n().clone(), key.e().clone(), d, primes.clone())
+        };
+        assert_eq!(parts(key.d().clone()).as_ref(), Some(&key));
+
+        // 只对前两个素因子一致的 d 也要拒绝
+        let r3 = &primes[2] - 1u32;
+        let lambda_pq = (&primes[0] - 1u32).lcm(&(&primes[1] - 1u32));
+        let partial = mod_inverse(key.e(), &lambda_pq).unwrap();
+        assert_ne!(&partial % &r3, key.d() % &r3);
+        assert!(parts(partial).is_none());
+        assert!(parts(key.d() + 2u32).is_none());
+    }
+
+    #[test]
+    fn multi_prime_keys_decrypt_and_sign() {
+        let e = BigUint::from(65537u32);
+        for (bits, count) in [(1024, 3), (4096, 4)] {
+            let label = format!("{} primes", count);
+            let key = RsaPrivateKey::generate_multi_prime(bits, count, &e, &mut test_rng(label.as_bytes())).unwrap();
+            assert_eq!(key.n().bits() as usize, bits);
+            assert_eq!(key.primes().len(), count);
+            assert_eq!(key.other_primes().len(), count - 2);
+            assert_eq!(key.validate(), Ok(()));
+
+            // CRT 结果与直接用 d 做模幂一致，解密和签名都能被公钥还原
+            for value in [2u32, 0xdead_beef] {
+                let m = BigUint::from(value);
+                let c = m.modpow(key.e(), key.n());
+                assert_eq!(key.decrypt_raw(&c), Some(m.clone()));
+                let s = key.sign_raw(&m).unwrap();
+                assert_eq!(s, m.modpow(key.d(), key.n()));
+                assert_eq!(s.modpow(key.e(), key.n()), m);
+            }
+            let blinded = key.clone().with_blinding(false);
+            let c = BigUint::from(42u32).modpow(key.e(), key.n());
+            assert_eq!(blinded.decrypt_raw(&c), key.decrypt_raw(&c));
+        }
+    }
+
+    #[test]
+    fn duplicate_primes_are_rejected() {
+        let key = RsaPrivateKey::generate_multi_prime(1024, 3, &BigUint::from(65537u32), &mut test_rng(b"three primes"))
+            .unwrap();
+        let (p, q, r) = (key.p().clone(), key.q().clone(), key.other_primes()[0].prime().clone());
+        let e = key.e().clone();
+        assert!(RsaPrivateKey::from_multi_primes(vec![p.clone(), q.clone(), r.clone()], e.clone()).is_some());
+
+        for primes in [
+            vec![p.clone(), p.clone()],
+            vec![p.clone(), q.clone(), p.clone()],
+            vec![p.clone(), q.clone(), r.clone(), r.clone()],
+        ] {
+            assert!(RsaPrivateKey::from_multi_primes(primes.clone(), e.clone()).is_none());
+            // 即使 d 按 λ(n) 算得一致，重复素因子的分量也要拒绝
+            let n: BigUint = primes.iter().product();
+            let lambda = primes.iter().fold(BigUint::one(), |acc, r| acc.lcm(&(r - 1u32)));
+            let d = mod_inverse(&e, &lambda).unwrap();
+            assert!(RsaPrivateKey::from_multi_prime_components(n, e.clone(), d, primes).is_none());
+        }
+    }
+}
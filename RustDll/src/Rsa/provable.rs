@@ -0,0 +1,197 @@
+//! 可证明素数的 RSA 密钥生成 (FIPS 186-5 A.1.2.2)
+//!
+//! p、q 由种子经 Shawe-Taylor 构造（`numtheory::provable`）得到，构造过程本身就是素性证明。
+//! 同一个种子总是得到同一个密钥；发布种子和计数器后，第三方可以用 `verify_provable` 重新构造并核对。
+
+use super::private_key::RsaPrivateKey;
+use super::validation::{KeyError, MAX_MODULUS_BITS, MAX_PUBLIC_EXPONENT_BITS};
+use crate::hash::HashAlgorithm;
+use crate::numtheory::provable::{provable_prime_construction, ProvablePrime};
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::One;
+use rand::{CryptoRng, RngCore};
+
+/// 可证明素数生成允许的最小模数位数
+pub const MIN_PROVABLE_MODULUS_BITS: usize = 2048;
+
+// 构造素数使用的哈希函数，128 位安全强度足以覆盖允许的全部模数长度
+const PROVABLE_HASH: HashAlgorithm = HashAlgorithm::Sha256;
+
+/// 可证明素数的生成记录，与公钥一起发布即可供第三方核对
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProvableSeed {
+    /// 初始种子，长度为安全强度的两倍
+    pub seed: Vec<u8>,
+    /// 构造 p 时的生成计数器
+    pub p_counter: usize,
+    /// 构造 q 时的生成计数器
+    pub q_counter: usize,
+}
+
+/// 模数位数对应的安全强度（SP 800-56B 表 2），介于两档之间时取较低的一档；小于 2048 位时返回 None
+pub fn security_strength(bit_length: usize) -> Option<usize> {
+    match bit_length {
+        0..2048 => None,
+        2048..3072 => Some(112),
+        3072..4096 => Some(128),
+        4096..6144 => Some(152),
+        6144..8192 => Some(176),
+        _ => Some(200),
+    }
+}
+
+/// 生成 bit_length 位模数所需的种子长度（字节），为安全强度的两倍
+pub fn seed_len(bit_length: usize) -> Option<usize> {
+    security_strength(bit_length).map(|strength| strength * 2 / 8)
+}
+
+impl RsaPrivateKey {
+    /// 由种子生成可证明素数的私钥
+    ///
+    /// bit_length 必须是 2048 到 `MAX_MODULUS_BITS` 之间的偶数，e 为 2^16 < e < 2^256 的奇数，
+    /// seed 的长度为 `seed_len(bit_length)` 字节。极少数种子会使构造失败或得到过小的 d，
+    /// 此时返回 `KeyError::PrimeGenerationFailed`，换一个种子即可。
+    pub fn generate_provable(bit_length: usize, e: &BigUint, seed: &[u8]) -> Result<(Self, ProvableSeed), KeyError> {
+        let (p, q) = construct_provable_primes(bit_length, e, seed)?;
+        let record = ProvableSeed { seed: seed.to_vec(), p_counter: p.counter, q_counter: q.counter };
+
+        let key = Self::from_primes(p.prime, q.prime, e.clone()).ok_or(KeyError::PrimeGenerationFailed)?;
+        if key.d().bits() as usize <= bit_length / 2 {
+            return Err(KeyError::PrimeGenerationFailed);
+        }
+        Ok((key, record))
+    }
+
+    /// 使用指定的随机数生成器生成种子；构造失败时自动换种子重试
+    pub fn generate_provable_with_rng<R: RngCore + CryptoRng + ?Sized>(
+        bit_length: usize,
+        e: &BigUint,
+        rng: &mut R,
+    ) -> Result<(Self, ProvableSeed), KeyError> {
+        let mut seed = vec![0u8; seed_len(bit_length).ok_or(KeyError::InvalidModulusSize)?];
+        loop {
+            rng.fill_bytes(&mut seed);
+            match Self::generate_provable(bit_length, e, &seed) {
+                Err(KeyError::PrimeGenerationFailed) => continue,
+                result => return result,
+            }
+        }
+    }
+
+    /// 用生成记录重新构造素因子，与本密钥的 p、q 及计数器一致时返回 true
+    pub fn verify_provable(&self, record: &ProvableSeed) -> bool {
+        if !self.other_primes().is_empty() {
+            return false;
+        }
+        let Ok((p, q)) = construct_provable_primes(self.n().bits() as usize, self.e(), &record.seed) else {
+            return false;
+        };
+        if p.counter != record.p_counter || q.counter != record.q_counter {
+            return false;
+        }
+        // from_primes 可能交换 p、q 的顺序
+        (self.p() == &p.prime && self.q() == &q.prime) || (self.p() == &q.prime && self.q() == &p.prime)
+    }
+}
+
+// A.1.2.2 步骤 1-10：依次由种子构造 p、q，|p - q| 过小时继续用 q 的种子重新构造 q
+fn construct_provable_primes(
+    bit_length: usize,
+    e: &BigUint,
+    seed: &[u8],
+) -> Result<(ProvablePrime, ProvablePrime), KeyError> {
+    if bit_length < MIN_PROVABLE_MODULUS_BITS || bit_length.is_odd() {
+        return Err(KeyError::InvalidModulusSize);
+    }
+    if bit_length > MAX_MODULUS_BITS {
+        return Err(KeyError::ModulusTooLarge);
+    }
+    if e.bits() <= 16 || e.is_even() || e.bits() > MAX_PUBLIC_EXPONENT_BITS {
+        return Err(KeyError::InvalidPublicExponent);
+    }
+    if Some(seed.len()) != seed_len(bit_length) {
+        return Err(KeyError::InvalidSeed);
+    }
+
+    let half = bit_length / 2;
+    let p = provable_prime_construction(PROVABLE_HASH, half, seed, e).ok_or(KeyError::PrimeGenerationFailed)?;
+    let min_distance = BigUint::one() << (half - 100);
+    let mut working_seed = p.seed.clone();
+    loop {
+        let q = provable_prime_construction(PROVABLE_HASH, half, &working_seed, e)
+            .ok_or(KeyError::PrimeGenerationFailed)?;
+        let distance = if p.prime > q.prime { &p.prime - &q.prime } else { &q.prime - &p.prime };
+        if distance > min_distance {
+            return Ok((p, q));
+        }
+        working_seed = q.seed;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::numtheory::is_probable_prime;
+
+    fn e() -> BigUint {
+        BigUint::from(65537u32)
+    }
+
+    fn seed() -> Vec<u8> {
+        (1..=28).collect()
+    }
+
+    #[test]
+    fn same_seed_gives_same_key() {
+        let (key, record) = RsaPrivateKey::generate_provable(2048, &e(), &seed()).unwrap();
+        assert_eq!(key.n().bits(), 2048);
+        assert!(is_probable_prime(key.p()) && is_probable_prime(key.q()));
+        assert_eq!(record.seed, seed());
+
+        let (again, again_record) = RsaPrivateKey::generate_provable(2048, &e(), &seed()).unwrap();
+        assert_eq!(again, key);
+        assert_eq!(again_record, record);
+
+        let mut other = seed();
+        other[0] ^= 0x80;
+        let (different, _) = RsaPrivateKey::generate_provable(2048, &e(), &other).unwrap();
+        assert_ne!(different.n(), key.n());
+    }
+
+    #[test]
+    fn verify_provable_checks_every_field() {
+        let (key, record) = RsaPrivateKey::generate_provable(2048, &e(), &seed()).unwrap();
+        assert!(key.verify_provable(&record));
+
+        let mut tampered = record.clone();
+        tampered.p_counter += 1;
+        assert!(!key.verify_provable(&tampered));
+
+        let mut tampered = record.clone();
+        tampered.q_counter -= 1;
+        assert!(!key.verify_provable(&tampered));
+
+        let mut tampered = record.clone();
+        tampered.seed[27] ^= 0x01;
+        assert!(!key.verify_provable(&tampered));
+
+        let mut tampered = record;
+        tampered.seed.pop();
+        assert!(!key.verify_provable(&tampered));
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        assert_eq!(RsaPrivateKey::generate_provable(2048, &e(), &seed()[..27]).err(), Some(KeyError::InvalidSeed));
+        assert_eq!(RsaPrivateKey::generate_provable(1024, &e(), &seed()).err(), Some(KeyError::InvalidModulusSize));
+        assert_eq!(RsaPrivateKey::generate_provable(2049, &e(), &seed()).err(), Some(KeyError::InvalidModulusSize));
+        assert_eq!(
+            RsaPrivateKey::generate_provable(2048, &BigUint::from(3u32), &seed()).err(),
+            Some(KeyError::InvalidPublicExponent)
+        );
+        assert_eq!(seed_len(2048), Some(28));
+        assert_eq!(seed_len(3072), Some(32));
+        assert_eq!(seed_len(2047), None);
+    }
+}
@@ -1,55 +1,114 @@
-use num_bigint::{BigUint, BigInt, RandBigInt, ToBigUint, ToBigInt};
-use num_integer::Integer;
-use num_traits::{One, Zero};
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::Zero;
 use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
 use std::io;
 use std::str::FromStr;
 
+use super::blinding::Blinding;
+use super::padding::{
+    i2osp, implicit_rejection_kdk, oaep_decode, oaep_encode, pkcs1v15_decode_implicit, pkcs1v15_encode,
+    RsaPadding,
+};
+use super::private_key::{RsaPrivateKey, DEFAULT_PUBLIC_EXPONENT};
+use super::signature::{pkcs1v15_sign_encode, pss_encode, pss_verify, SignatureScheme};
+use super::validation::{check_modulus, check_public_key, pairwise_test, KeyError};
+use super::RsaError;
+use crate::hash::{constant_time_eq, HashAlgorithm};
+use crate::numtheory::is_prime;
+use crate::numtheory::search::SearchState;
+use crate::numtheory::sieve::Candidates;
+
 pub struct RSA {
-    n: BigUint,
-    e: BigUint,
-    d: Option<BigUint>,
+    pub(super) n: BigUint,
+    pub(super) e: BigUint,
+    pub(super) d: Option<BigUint>,
+    // 持有素因子时使用 CRT 加速私钥运算
+    crt: Option<RsaPrivateKey>,
+    // 私钥运算是否使用基数盲化（默认开启，需要已知 e）
+    blinding: bool,
 }
 
 impl RSA {
-    /// 生成新的 Rsa 密钥对
-    pub fn new(bit_length: usize) -> Self {
-        // 生成两个大素数
-        let p = generate_large_prime(bit_length);
-        let q = generate_large_prime(bit_length);
-        let n = &p * &q;
+    /// 生成新的 Rsa 密钥对，bit_length 为模数 n 的位数（例如 2048），位数不在允许范围内时返回错误
+    pub fn new(bit_length: usize) -> Result<Self, KeyError> {
+        Self::generate_with_rng(bit_length, &mut OsRng)
+    }
 
-        // 计算欧拉函数 φ(n)
-        let phi_n = (p - BigUint::one()) * (q - BigUint::one());
+    /// 使用指定的随机数生成器生成密钥对（例如传入 DRBG 以获得可复现的密钥）
+    pub fn generate_with_rng<R: RngCore + CryptoRng + ?Sized>(bit_length: usize, rng: &mut R) -> Result<Self, KeyError> {
+        RsaPrivateKey::generate_with_rng(bit_length, rng).map(Self::from_crt_key)
+    }
 
-        // 选择公钥指数 e (这里先用 65537，后续有机会再改)
-        let e = BigUint::from(65537u32);
+    /// 使用指定的公钥指数生成密钥对，e 必须是 3 <= e < 2^256 的奇数
+    pub fn generate_with_exponent<R: RngCore + CryptoRng + ?Sized>(
+        bit_length: usize,
+        e: &BigUint,
+        rng: &mut R,
+    ) -> Result<Self, KeyError> {
+        RsaPrivateKey::generate_with_exponent(bit_length, e, rng).map(Self::from_crt_key)
+    }
 
-        // 计算私钥指数 d (e 的模反元素)
-        let d = mod_inverse(&e, &phi_n).expect("Failed to compute modular inverse");
+    /// 生成多素数密钥对（prime_count 个素因子，公钥指数 65537），私钥运算使用全部素因子做 CRT
+    pub fn generate_multi_prime(bit_length: usize, prime_count: usize) -> Result<Self, KeyError> {
+        let e = BigUint::from(DEFAULT_PUBLIC_EXPONENT);
+        RsaPrivateKey::generate_multi_prime(bit_length, prime_count, &e, &mut OsRng).map(Self::from_crt_key)
+    }
 
-        RSA { n, e, d: Some(d) }
+    /// 从带 CRT 参数的私钥创建 Rsa 实例
+    pub fn from_crt_key(key: RsaPrivateKey) -> Self {
+        RSA {
+            n: key.n().clone(),
+            e: key.e().clone(),
+            d: Some(key.d().clone()),
+            crt: Some(key),
+            blinding: true,
+        }
     }
 
     /// 从公钥创建 Rsa 实例
     pub fn from_public_key(n: &str, e: &str) -> Self {
-        RSA {
-            n: BigUint::from_str(n).expect("Invalid n"),
-            e: BigUint::from_str(e).expect("Invalid e"),
-            d: None,
-        }
+        Self::from_public_parts(
+            BigUint::from_str(n).expect("Invalid n"),
+            BigUint::from_str(e).expect("Invalid e"),
+        )
+    }
+
+    /// 由已解析的 n、e 创建公钥实例
+    pub fn from_public_parts(n: BigUint, e: BigUint) -> Self {
+        RSA { n, e, d: None, crt: None, blinding: true }
     }
 
     /// 从私钥创建 Rsa 实例
+    ///
+    /// 实例不含 e，无法做默认开启的基数盲化，私钥运算会返回 `RsaError::PrivateKeyUnavailable`；
+    /// 应改用 `from_parts`，确需不盲化的运算时显式调用 `with_blinding(false)`。
     pub fn from_private_key(n: &str, d: &str) -> Self {
+        Self::from_private_parts(
+            BigUint::from_str(n).expect("Invalid n"),
+            BigUint::from_str(d).expect("Invalid d"),
+        )
+    }
+
+    /// 由已解析的 n、d 创建私钥实例，限制同 `from_private_key`
+    pub fn from_private_parts(n: BigUint, d: BigUint) -> Self {
         RSA {
-            n: BigUint::from_str(n).expect("Invalid n"),
+            n,
             e: BigUint::zero(), // 私钥不需要 e
-            d: Some(BigUint::from_str(d).expect("Invalid d")),
+            d: Some(d),
+            crt: None,
+            blinding: true,
         }
     }
 
-    /// 加密数据
+    /// 由已解析的 n、e、d 创建私钥实例（没有 CRT 参数，私钥运算直接使用 d）
+    pub fn from_parts(n: BigUint, e: BigUint, d: BigUint) -> Self {
+        RSA { n, e, d: Some(d), crt: None, blinding: true }
+    }
+
+    /// 加密数据（教科书 RSA）
+    ///
+    /// 数据按一个整数处理，不得超过模长；任意长度的消息请使用 `seal`。
     pub fn encrypt(&self, data: &[u8]) -> Vec<u8> {
         let m = BigUint::from_bytes_be(data);
         let c = m.modpow(&self.e, &self.n);
@@ -58,148 +117,271 @@ impl RSA {
 
     /// 解密数据
     pub fn decrypt(&self, data: &[u8]) -> Vec<u8> {
-        let c = BigUint::from_bytes_be(data);
-        let m = match &self.d {
-            Some(d) => c.modpow(d, &self.n),
-            None => panic!("Private key not available for decryption"),
-        };
-        m.to_bytes_be()
+        self.try_decrypt(data).expect("Private key operation failed")
     }
 
-    /// 获取公钥
-    pub fn public_key(&self) -> (String, String) {
-        (self.n.to_str_radix(10), self.e.to_str_radix(10))
+    /// 解密数据（无填充），缺少私钥、无法盲化或结果验算失败时返回 `RsaError::PrivateKeyUnavailable`
+    pub fn try_decrypt(&self, data: &[u8]) -> Result<Vec<u8>, RsaError> {
+        if self.n.bits() == 0 {
+            return Err(RsaError::KeyTooSmall);
+        }
+        let c = BigUint::from_bytes_be(data) % &self.n;
+        self.private_op(&c).map(|m| m.to_bytes_be())
     }
 
-    /// 获取私钥
-    pub fn private_key(&self) -> (String, String) {
-        (
-            self.n.to_str_radix(10),
-            self.d.as_ref().unwrap().to_str_radix(10),
-        )
+    /// 开启或关闭私钥运算的基数盲化（默认开启）
+    ///
+    /// 盲化需要公钥指数 e，由 `from_private_key` 创建、不含 e 的实例在开启盲化时拒绝私钥运算，
+    /// 应尽量使用带 e 的私钥（例如 `from_parts`、`from_crt_key`）。
+    pub fn with_blinding(mut self, enabled: bool) -> Self {
+        self.blinding = enabled;
+        self.crt = self.crt.map(|key| key.with_blinding(enabled));
+        self
     }
-}
 
-/// 生成指定位数的大素数
-fn generate_large_prime(bit_length: usize) -> BigUint {
-    let mut rng = OsRng;
-    loop {
-        let mut candidate = rng.gen_biguint(bit_length as u64);
+    /// 模长（字节）
+    pub fn modulus_len(&self) -> usize {
+        (self.n.bits() as usize).div_ceil(8)
+    }
 
-        // 确保是奇数
-        if candidate.is_even() {
-            candidate += BigUint::one();
-        }
+    /// RSAES-OAEP 加密，输出与模等长的密文
+    pub fn encrypt_oaep(&self, algorithm: HashAlgorithm, message: &[u8], label: &[u8]) -> Result<Vec<u8>, RsaError> {
+        let k = self.modulus_len();
+        let em = oaep_encode(algorithm, message, label, k, &mut OsRng)?;
+        let c = BigUint::from_bytes_be(&em).modpow(&self.e, &self.n);
+        Ok(i2osp(&c, k))
+    }
 
-        // 确保位数正确
-        if candidate.bits() < bit_length as u64 {
-            candidate.set_bit((bit_length - 1) as u64, true);
+    /// RSAES-OAEP 解密，任何失败都返回同一个 `RsaError::Decryption`
+    pub fn decrypt_oaep(&self, algorithm: HashAlgorithm, ciphertext: &[u8], label: &[u8]) -> Result<Vec<u8>, RsaError> {
+        let k = self.modulus_len();
+        if ciphertext.len() != k {
+            return Err(RsaError::Decryption);
         }
-
-        // 使用概率性测试检查素数
-        if is_prime(&candidate, 20) {
-            return candidate;
+        let c = BigUint::from_bytes_be(ciphertext);
+        if c >= self.n {
+            return Err(RsaError::Decryption);
         }
+        let m = self.private_op(&c).map_err(|_| RsaError::Decryption)?;
+        oaep_decode(algorithm, &i2osp(&m, k), label, k)
     }
-}
 
-/// Miller-Rabin 素数测试
-fn is_prime(n: &BigUint, k: usize) -> bool {
-    if n <= &BigUint::one() {
-        return false;
-    }
-    if n == &BigUint::from(2u8) || n == &BigUint::from(3u8) {
-        return true;
-    }
-    if n.is_even() {
-        return false;
+    /// RSAES-PKCS1-v1_5 加密，仅用于与旧系统互通，新协议请使用 OAEP
+    pub fn encrypt_pkcs1v15(&self, message: &[u8]) -> Result<Vec<u8>, RsaError> {
+        let k = self.modulus_len();
+        let em = pkcs1v15_encode(message, k, &mut OsRng)?;
+        let c = BigUint::from_bytes_be(&em).modpow(&self.e, &self.n);
+        Ok(i2osp(&c, k))
     }
 
-    // 将 n-1 分解为 d * 2^s
-    let mut d = n - BigUint::one();
-    let mut s = 0;
-    while d.is_even() {
-        d /= BigUint::from(2u8);
-        s += 1;
+    /// RSAES-PKCS1-v1_5 解密（隐式拒绝）
+    ///
+    /// 填充错误时返回由私钥和密文确定的伪随机消息而不是错误，上层协议应通过后续的
+    /// 完整性校验（例如 MAC 或 AEAD 解密失败）发现问题。只有密文长度错误或私钥不可用时返回 Err。
+    pub fn decrypt_pkcs1v15(&self, ciphertext: &[u8]) -> Result<Vec<u8>, RsaError> {
+        let k = self.modulus_len();
+        if ciphertext.len() != k {
+            return Err(RsaError::Decryption);
+        }
+        let d = self.d.as_ref().ok_or(RsaError::PrivateKeyUnavailable)?;
+        let kdk = implicit_rejection_kdk(d, ciphertext, k);
+
+        let c = BigUint::from_bytes_be(ciphertext);
+        if c >= self.n {
+            return Err(RsaError::Decryption);
+        }
+        let m = self.private_op(&c)?;
+        Ok(pkcs1v15_decode_implicit(&i2osp(&m, k), &kdk))
     }
 
-    let mut rng = OsRng;
-    for _ in 0..k {
-        let a = rng.gen_biguint_range(&BigUint::from(2u8), &(n - BigUint::one()));
-        let mut x = a.modpow(&d, n);
+    /// 按指定填充方式加密（OAEP 使用空标签）
+    pub fn encrypt_padded(&self, padding: RsaPadding, message: &[u8]) -> Result<Vec<u8>, RsaError> {
+        match padding {
+            RsaPadding::Pkcs1v15 => self.encrypt_pkcs1v15(message),
+            RsaPadding::Oaep(algorithm) => self.encrypt_oaep(algorithm, message, &[]),
+        }
+    }
 
-        if x == BigUint::one() || x == n - BigUint::one() {
-            continue;
+    /// 按指定填充方式解密（OAEP 使用空标签）
+    pub fn decrypt_padded(&self, padding: RsaPadding, ciphertext: &[u8]) -> Result<Vec<u8>, RsaError> {
+        match padding {
+            RsaPadding::Pkcs1v15 => self.decrypt_pkcs1v15(ciphertext),
+            RsaPadding::Oaep(algorithm) => self.decrypt_oaep(algorithm, ciphertext, &[]),
         }
+    }
 
-        let mut found = false;
-        for _ in 0..s - 1 {
-            x = x.modpow(&BigUint::from(2u8), n);
-            if x == n - BigUint::one() {
-                found = true;
-                break;
-            }
+    /// 对消息签名（消息由签名方案中的摘要算法先做哈希），签名长度等于模长
+    pub fn sign(&self, scheme: SignatureScheme, message: &[u8]) -> Result<Vec<u8>, RsaError> {
+        let k = self.modulus_len();
+        if k == 0 {
+            return Err(RsaError::KeyTooSmall);
         }
+        let em = match scheme {
+            SignatureScheme::Pkcs1v15(hash) => pkcs1v15_sign_encode(hash, message, k)?,
+            SignatureScheme::Pss { hash, salt_len } => {
+                pss_encode(hash, message, salt_len, self.n.bits() as usize - 1, &mut OsRng)?
+            }
+        };
+        let s = self.private_op(&BigUint::from_bytes_be(&em))?;
+        Ok(i2osp(&s, k))
+    }
 
-        if !found {
+    /// 验证签名，签名长度必须等于模长
+    pub fn verify(&self, scheme: SignatureScheme, message: &[u8], signature: &[u8]) -> bool {
+        let k = self.modulus_len();
+        if k == 0 || signature.len() != k {
+            return false;
+        }
+        let s = BigUint::from_bytes_be(signature);
+        if s >= self.n {
             return false;
         }
+        let m = s.modpow(&self.e, &self.n);
+
+        match scheme {
+            SignatureScheme::Pkcs1v15(hash) => match pkcs1v15_sign_encode(hash, message, k) {
+                Ok(expected) => constant_time_eq(&i2osp(&m, k), &expected),
+                Err(_) => false,
+            },
+            SignatureScheme::Pss { hash, salt_len } => {
+                let em_bits = self.n.bits() as usize - 1;
+                let em_len = em_bits.div_ceil(8);
+                if m.bits() as usize > 8 * em_len {
+                    return false;
+                }
+                pss_verify(hash, message, &i2osp(&m, em_len), salt_len, em_bits)
+            }
+        }
     }
 
-    true
-}
+    // 私钥运算：持有 CRT 参数时走 CRT，否则直接使用 d
+    //
+    // 开启盲化时必须有 e，不含 e 的实例返回错误而不是退回不盲化的运算；
+    // 盲化后的结果同样用 e 验算，e 与 d 不匹配时不会输出错误的结果
+    pub(super) fn private_op(&self, c: &BigUint) -> Result<BigUint, RsaError> {
+        if let Some(key) = &self.crt {
+            return key.decrypt_raw(c).ok_or(RsaError::PrivateKeyUnavailable);
+        }
+        let d = self.d.as_ref().ok_or(RsaError::PrivateKeyUnavailable)?;
+        if !self.blinding {
+            return Ok(c.modpow(d, &self.n));
+        }
+        if self.e.bits() == 0 {
+            return Err(RsaError::PrivateKeyUnavailable);
+        }
 
-/// 扩展欧几里得算法求模反元素
-fn mod_inverse(a: &BigUint, m: &BigUint) -> Option<BigUint> {
-    let zero = BigInt::zero();
-    let one = BigInt::one();
+        let blinding = Blinding::new(&self.e, &self.n).ok_or(RsaError::PrivateKeyUnavailable)?;
+        let blinded = blinding.blind(c, &self.n);
+        let m = blinded.modpow(d, &self.n);
+        if m.modpow(&self.e, &self.n) != blinded {
+            return Err(RsaError::PrivateKeyUnavailable);
+        }
+        Ok(blinding.unblind(&m, &self.n))
+    }
 
-    let (mut old_r, mut r) = (a.to_bigint().unwrap(), m.to_bigint().unwrap());
-    let (mut old_s, mut s) = (one.clone(), zero.clone());
-    let (mut old_t, mut t) = (zero.clone(), one.clone());
+    /// 校验导入的密钥
+    ///
+    /// 检查模数和公钥指数；持有 d 时做成对一致性测试，持有 CRT 参数时再检查素因子和 CRT 参数。
+    /// 由 `from_private_key` 创建的实例没有 e，只检查模数并要求 d < n。
+    pub fn validate(&self) -> Result<(), KeyError> {
+        if let Some(key) = &self.crt {
+            return key.validate();
+        }
+        if self.e.bits() == 0 {
+            check_modulus(&self.n)?;
+            return match &self.d {
+                Some(d) if d.bits() > 0 && d < &self.n => Ok(()),
+                _ => Err(KeyError::InvalidPrivateExponent),
+            };
+        }
 
-    while !r.is_zero() {
-        let quotient = &old_r / &r;
+        check_public_key(&self.n, &self.e)?;
+        match &self.d {
+            Some(d) => pairwise_test(&self.n, &self.e, d),
+            None => Ok(()),
+        }
+    }
 
-        let new_r = &old_r - &quotient * &r;
-        old_r = r;
-        r = new_r;
+    /// 获取公钥
+    pub fn public_key(&self) -> (String, String) {
+        (self.n.to_str_radix(10), self.e.to_str_radix(10))
+    }
 
-        let new_s = &old_s - &quotient * &s;
-        old_s = s;
-        s = new_s;
+    /// 模数 n
+    pub fn n(&self) -> &BigUint {
+        &self.n
+    }
 
-        let new_t = &old_t - &quotient * &t;
-        old_t = t;
-        t = new_t;
+    /// 公钥指数 e（由 `from_private_key` 创建的实例为 0）
+    pub fn e(&self) -> &BigUint {
+        &self.e
     }
 
-    if old_r != one {
-        return None; // 没有逆元
+    /// 是否持有私钥
+    pub fn has_private_key(&self) -> bool {
+        self.d.is_some()
     }
 
-    // 保证结果是正的 BigUint
-    let result = if old_s < zero {
-        old_s + m.to_bigint().unwrap()
-    } else {
-        old_s
-    };
+    /// 获取私钥
+    pub fn private_key(&self) -> (String, String) {
+        (
+            self.n.to_str_radix(10),
+            self.d.as_ref().unwrap().to_str_radix(10),
+        )
+    }
 
-    result.to_biguint()
+    /// 获取 CRT 私钥（仅当实例由素因子生成或导入时存在）
+    pub fn crt_key(&self) -> Option<&RsaPrivateKey> {
+        self.crt.as_ref()
+    }
 }
 
+/// 生成指定位数的大素数（最高两位为 1），state 被停止（多线程搜索中其他线程已找到结果或已取消）时返回 None
+///
+/// 从随机起点开始增量筛选，只对没有小素因子的候选做 Miller-Rabin。
+pub(crate) fn search_large_prime<R: RngCore + ?Sized>(bit_length: usize, rng: &mut R, state: &SearchState) -> Option<BigUint> {
+    while !state.is_stopped() {
+        let mut base = rng.gen_biguint(bit_length as u64);
+
+        // 确保是奇数
+        base.set_bit(0, true);
+
+        // 最高两位置 1，两个这样的素数相乘恰好得到 2 * bit_length 位的模数
+        base.set_bit((bit_length - 1) as u64, true);
+        base.set_bit((bit_length - 2) as u64, true);
+
+        // 候选超出 bit_length 位时重新选取起点
+        for candidate in Candidates::new(base, false) {
+            if candidate.bits() as usize != bit_length || state.is_stopped() {
+                break;
+            }
+            state.record_candidate();
+            // 使用概率性测试检查素数
+            if is_prime(&candidate, 20, rng) {
+                return Some(candidate);
+            }
+        }
+    }
+    None
+}
 
 fn generate_and_use_keys() {
-    println!("输入素数位数 (留空默认为1024):");
+    println!("输入模数位数 (留空默认为2048):");
     let mut bit_length = String::new();
     io::stdin().read_line(&mut bit_length).expect("读取输入失败");
 
     let bit_length = match bit_length.trim() {
-        "" => 1024,
+        "" => 2048,
         s => s.parse().expect("请输入有效数字"),
     };
 
-    let rsa = RSA::new(bit_length);
+    let rsa = match RSA::new(bit_length) {
+        Ok(rsa) => rsa,
+        Err(err) => {
+            println!("无法生成密钥：{}", err);
+            return;
+        }
+    };
     let (n, e) = rsa.public_key();
     let (_, d) = rsa.private_key();
 
@@ -238,11 +420,16 @@ fn decrypt_with_private_key() {
     let mut n = String::new();
     io::stdin().read_line(&mut n).expect("读取输入失败");
 
+    println!("输入公钥 e:");
+    let mut e = String::new();
+    io::stdin().read_line(&mut e).expect("读取输入失败");
+
     println!("输入私钥 d:");
     let mut d = String::new();
     io::stdin().read_line(&mut d).expect("读取输入失败");
 
-    let rsa = RSA::from_private_key(n.trim(), d.trim());
+    let parse = |s: &str| BigUint::from_str(s.trim()).expect("无效的十进制整数");
+    let rsa = RSA::from_parts(parse(&n), parse(&e), parse(&d));
 
     println!("输入要解密的密文 (十六进制):");
     let mut ciphertext = String::new();
@@ -280,4 +467,51 @@ fn test_encryption_decryption(rsa: RSA) {
     } else {
         println!("验证失败: 原始消息与解密消息不一致");
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drbg::HmacDrbg;
+
+    fn test_key() -> RSA {
+        let mut rng = HmacDrbg::new(&[0x38; 32], b"nonce", b"rsa blinding test").unwrap();
+        RSA::generate_with_rng(1024, &mut rng).unwrap()
+    }
+
+    #[test]
+    fn blinded_and_unblinded_results_match() {
+        let rsa = test_key();
+        let (n, e, d) = (rsa.n.clone(), rsa.e.clone(), rsa.d.clone().unwrap());
+        let blinded = RSA::from_parts(n.clone(), e.clone(), d.clone());
+        let unblinded = RSA::from_parts(n.clone(), e.clone(), d.clone()).with_blinding(false);
+        let crt_unblinded = test_key().with_blinding(false);
+
+        for value in [1u32, 2, 0xdead_beef] {
+            let c = BigUint::from(value).modpow(&e, &n);
+            let expected = blinded.private_op(&c).unwrap();
+            assert_eq!(expected, BigUint::from(value));
+            assert_eq!(unblinded.private_op(&c).unwrap(), expected);
+            assert_eq!(rsa.private_op(&c).unwrap(), expected);
+            assert_eq!(crt_unblinded.private_op(&c).unwrap(), expected);
+        }
+
+        let scheme = SignatureScheme::Pkcs1v15(HashAlgorithm::Sha256);
+        let signature = blinded.sign(scheme, b"message").unwrap();
+        assert_eq!(unblinded.sign(scheme, b"message").unwrap(), signature);
+        assert_eq!(rsa.sign(scheme, b"message").unwrap(), signature);
+    }
+
+    #[test]
+    fn blinding_without_public_exponent_is_refused() {
+        let rsa = test_key();
+        let c = BigUint::from(42u32).modpow(&rsa.e, &rsa.n);
+
+        let without_e = RSA::from_private_parts(rsa.n.clone(), rsa.d.clone().unwrap());
+        assert_eq!(without_e.private_op(&c), Err(RsaError::PrivateKeyUnavailable));
+        assert!(without_e.try_decrypt(&c.to_bytes_be()).is_err());
+        assert_eq!(without_e.with_blinding(false).private_op(&c).unwrap(), BigUint::from(42u32));
+
+        let wrong_e = RSA::from_parts(rsa.n.clone(), BigUint::from(3u32), rsa.d.clone().unwrap());
+        assert_eq!(wrong_e.private_op(&c), Err(RsaError::PrivateKeyUnavailable));
+    }
+}
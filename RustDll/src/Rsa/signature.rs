@@ -0,0 +1,345 @@
+//! RSA 签名编码 (RFC 8017 第 9 节)：EMSA-PSS 与 EMSA-PKCS1-v1_5
+
+use super::padding::mgf1;
+use super::RsaError;
+use crate::hash::{constant_time_eq, HashAlgorithm};
+use rand::RngCore;
+
+/// 签名方案
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureScheme {
+    /// RSASSA-PKCS1-v1_5，摘要以 DigestInfo 形式编码
+    Pkcs1v15(HashAlgorithm),
+    /// RSASSA-PSS，MGF1 使用同一摘要算法，salt_len 为盐长度（字节）
+    Pss { hash: HashAlgorithm, salt_len: usize },
+}
+
+impl SignatureScheme {
+    /// 由 FFI 的签名方案编号（CRYPTO_RSA_SIGNATURE_*）、摘要算法编号和盐长度得到签名方案
+    pub fn from_id(scheme: i32, hash_algorithm: i32, salt_len: usize) -> Option<Self> {
+        let hash = HashAlgorithm::from_id(hash_algorithm)?;
+        match scheme {
+            1 => Some(SignatureScheme::Pkcs1v15(hash)),
+            2 => Some(SignatureScheme::Pss { hash, salt_len }),
+            _ => None,
+        }
+    }
+
+    pub fn hash(&self) -> HashAlgorithm {
+        match self {
+            SignatureScheme::Pkcs1v15(hash) => *hash,
+            SignatureScheme::Pss { hash, .. } => *hash,
+        }
+    }
+}
+
+// 各摘要算法在 NIST 算法弧 2.16.840.1.101.3.4.2 下的编号
+fn nist_hash_arc(algorithm: HashAlgorithm) -> Option<u8> {
+    match algorithm {
+        HashAlgorithm::Sha256 => Some(1),
+        HashAlgorithm::Sha384 => Some(2),
+        HashAlgorithm::Sha512 => Some(3),
+        HashAlgorithm::Sha224 => Some(4),
+        HashAlgorithm::Sha512_224 => Some(5),
+        HashAlgorithm::Sha512_256 => Some(6),
+        HashAlgorithm::Sha3_224 => Some(7),
+        HashAlgorithm::Sha3_256 => Some(8),
+        HashAlgorithm::Sha3_384 => Some(9),
+        HashAlgorithm::Sha3_512 => Some(10),
+        // SHA-1 不抗碰撞，不用于签名
+        HashAlgorithm::Shake128 | HashAlgorithm::Shake256 | HashAlgorithm::Sha1 => None,
+    }
+}
+
+/// DigestInfo 的 DER 编码：SEQUENCE { AlgorithmIdentifier { OID, NULL }, OCTET STRING digest }
+pub fn digest_info(algorithm: HashAlgorithm, digest: &[u8]) -> Result<Vec<u8>, RsaError> {
+    let arc = nist_hash_arc(algorithm).ok_or(RsaError::UnsupportedHash)?;
+    let mut t = vec![
+        0x30, (0x11 + digest.len()) as u8,
+        0x30, 0x0d,
+        0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, arc,
+        0x05, 0x00,
+        0x04, digest.len() as u8,
+    ];
+    t.extend_from_slice(digest);
+    Ok(t)
+}
+
+/// EMSA-PKCS1-v1_5 编码：EM = 0x00 || 0x01 || PS (0xff) || 0x00 || DigestInfo
+pub fn pkcs1v15_sign_encode(algorithm: HashAlgorithm, message: &[u8], em_len: usize) -> Result<Vec<u8>, RsaError> {
+    let t = digest_info(algorithm, &algorithm.digest(message))?;
+    if em_len < t.len() + 11 {
+        return Err(RsaError::KeyTooSmall);
+    }
+
+    let mut em = vec![0x00, 0x01];
+    em.resize(em_len - t.len() - 1, 0xff);
+    em.push(0x00);
+    em.extend_from_slice(&t);
+    Ok(em)
+}
+
+/// EMSA-PSS 编码，em_bits 为模的位数减 1
+pub fn pss_encode<R: RngCore + ?Sized>(
+    algorithm: HashAlgorithm,
+    message: &[u8],
+    salt_len: usize,
+    em_bits: usize,
+    rng: &mut R,
+) -> Result<Vec<u8>, RsaError> {
+    // SHA-1 不抗碰撞，与 DigestInfo 一样不用于签名
+    if algorithm.is_xof() || algorithm == HashAlgorithm::Sha1 {
+        return Err(RsaError::UnsupportedHash);
+    }
+    let h_len = algorithm.output_len();
+    let em_len = em_bits.div_ceil(8);
+    if em_len < h_len + salt_len + 2 {
+        return Err(RsaError::KeyTooSmall);
+    }
+
+    let mut salt = vec![0u8; salt_len];
+    rng.fill_bytes(&mut salt);
+    let h = pss_hash(algorithm, &algorithm.digest(message), &salt);
+
+    // DB = PS || 0x01 || salt
+    let mut db = vec![0u8; em_len - salt_len - h_len - 2];
+    db.push(0x01);
+    db.extend_from_slice(&salt);
+    for (b, m) in db.iter_mut().zip(mgf1(algorithm, &h, em_len - h_len - 1)) {
+        *b ^= m;
+    }
+    db[0] &= 0xff >> (8 * em_len - em_bits);
+
+    let mut em = db;
+    em.extend_from_slice(&h);
+    em.push(0xbc);
+    Ok(em)
+}
+
+/// EMSA-PSS 验证，em 为 ceil(em_bits / 8) 字节
+pub fn pss_verify(algorithm: HashAlgorithm, message: &[u8], em: &[u8], salt_len: usize, em_bits: usize) -> bool {
+    if algorithm.is_xof() || algorithm == HashAlgorithm::Sha1 {
+        return false;
+    }
+    let h_len = algorithm.output_len();
+    let em_len = em_bits.div_ceil(8);
+    if em.len() != em_len || em_len < h_len + salt_len + 2 || em[em_len - 1] != 0xbc {
+        return false;
+    }
+
+    let (masked_db, rest) = em.split_at(em_len - h_len - 1);
+    let h = &rest[..h_len];
+    let top_mask = !(0xffu8 >> (8 * em_len - em_bits));
+    if masked_db[0] & top_mask != 0 {
+        return false;
+    }
+
+    let mut db: Vec<u8> = masked_db
+        .iter()
+        .zip(mgf1(algorithm, h, em_len - h_len - 1))
+        .map(|(a, b)| a ^ b)
+        .collect();
+    db[0] &= !top_mask;
+
+    // DB 必须是 PS (全零) || 0x01 || salt
+    let ps_len = em_len - h_len - salt_len - 2;
+    if db[..ps_len].iter().any(|&b| b != 0) || db[ps_len] != 0x01 {
+        return false;
+    }
+    let salt = &db[ps_len + 1..];
+
+    constant_time_eq(&pss_hash(algorithm, &algorithm.digest(message), salt), h)
+}
+
+// H = Hash(0x00 * 8 || mHash || salt)
+fn pss_hash(algorithm: HashAlgorithm, m_hash: &[u8], salt: &[u8]) -> Vec<u8> {
+    let mut hasher = algorithm.hasher();
+    hasher.update(&[0u8; 8]);
+    hasher.update(m_hash);
+    hasher.update(salt);
+    hasher.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rsa::padding::i2osp;
+    use crate::Rsa::rsa::RSA;
+    use crate::drbg::HmacDrbg;
+    use num_bigint::BigUint;
+
+    const MESSAGE: &[u8] = b"Wycheproof-style negative test";
+    const PKCS1: SignatureScheme = SignatureScheme::Pkcs1v15(HashAlgorithm::Sha256);
+    const PSS: SignatureScheme = SignatureScheme::Pss { hash: HashAlgorithm::Sha256, salt_len: 32 };
+
+    fn test_key() -> RSA {
+        let mut rng = HmacDrbg::new(&[0x41; 32], b"nonce", b"rsa signature test").unwrap();
+        RSA::generate_with_rng(1024, &mut rng).unwrap()
+    }
+
+    // 对任意编码做私钥运算，得到“签名”
+    fn raw_sign(rsa: &RSA, em: &[u8]) -> Vec<u8> {
+        let s = rsa.private_op(&BigUint::from_bytes_be(em)).unwrap();
+        i2osp(&s, rsa.modulus_len())
+    }
+
+    // 由 DigestInfo 之类的 T 手工构造 0x00 || 0x01 || PS || 0x00 || T
+    fn pkcs1_em(t: &[u8], k: usize) -> Vec<u8> {
+        let mut em = vec![0x00, 0x01];
+        em.resize(k - t.len() - 1, 0xff);
+        em.push(0x00);
+        em.extend_from_slice(t);
+        em
+    }
+
+    // 合法的 PSS 编码，返回 (EM, 去掩码后的 DB)，便于篡改后重新掩码
+    fn pss_em(rsa: &RSA) -> (Vec<u8>, Vec<u8>) {
+        let em_bits = rsa.n().bits() as usize - 1;
+        let mut rng = HmacDrbg::new(&[0x42; 32], b"", b"salt").unwrap();
+        let em = pss_encode(HashAlgorithm::Sha256, MESSAGE, 32, em_bits, &mut rng).unwrap();
+        let h_len = HashAlgorithm::Sha256.output_len();
+        let db_len = em.len() - h_len - 1;
+        let mut db = em[..db_len].to_vec();
+        xor(&mut db, &mgf1(HashAlgorithm::Sha256, &em[db_len..db_len + h_len], db_len));
+        db[0] &= 0xff >> (8 * em.len() - em_bits);
+        (em, db)
+    }
+
+    fn remask(em: &mut [u8], db: &[u8]) {
+        let h_len = HashAlgorithm::Sha256.output_len();
+        let db_len = db.len();
+        let mut masked = db.to_vec();
+        xor(&mut masked, &mgf1(HashAlgorithm::Sha256, &em[db_len..db_len + h_len], db_len));
+        em[..db_len].copy_from_slice(&masked);
+    }
+
+    fn xor(data: &mut [u8], mask: &[u8]) {
+        for (d, m) in data.iter_mut().zip(mask) {
+            *d ^= m;
+        }
+    }
+
+    #[test]
+    fn well_formed_signatures_verify() {
+        let rsa = test_key();
+        let k = rsa.modulus_len();
+        let digest_info = digest_info(HashAlgorithm::Sha256, &HashAlgorithm::Sha256.digest(MESSAGE)).unwrap();
+        assert!(rsa.verify(PKCS1, MESSAGE, &raw_sign(&rsa, &pkcs1_em(&digest_info, k))));
+        assert!(rsa.verify(PSS, MESSAGE, &raw_sign(&rsa, &pss_em(&rsa).0)));
+    }
+
+    #[test]
+    fn wrong_digest_info_is_rejected() {
+        let rsa = test_key();
+        let k = rsa.modulus_len();
+        let digest = HashAlgorithm::Sha256.digest(MESSAGE);
+
+        // SHA-256 摘要配 SHA-384 的 OID
+        let mut wrong_oid = digest_info(HashAlgorithm::Sha256, &digest).unwrap();
+        wrong_oid[14] = 2;
+        // 省略 AlgorithmIdentifier 中的 NULL 参数
+        let mut missing_null = vec![0x30, 0x2f, 0x30, 0x0b];
+        missing_null.extend_from_slice(&digest_info(HashAlgorithm::Sha256, &digest).unwrap()[4..15]);
+        missing_null.extend_from_slice(&[0x04, 0x20]);
+        missing_null.extend_from_slice(&digest);
+        // 其他消息的摘要、裸摘要
+        let other = digest_info(HashAlgorithm::Sha256, &HashAlgorithm::Sha256.digest(b"other")).unwrap();
+
+        for t in [wrong_oid, missing_null, other, digest] {
+            assert!(!rsa.verify(PKCS1, MESSAGE, &raw_sign(&rsa, &pkcs1_em(&t, k))));
+        }
+    }
+
+    #[test]
+    fn trailing_bytes_are_rejected() {
+        let rsa = test_key();
+        let k = rsa.modulus_len();
+        let mut t = digest_info(HashAlgorithm::Sha256, &HashAlgorithm::Sha256.digest(MESSAGE)).unwrap();
+        t.extend_from_slice(&[0x00, 0x00]);
+        assert!(!rsa.verify(PKCS1, MESSAGE, &raw_sign(&rsa, &pkcs1_em(&t, k))));
+
+        let signature = rsa.sign(PKCS1, MESSAGE).unwrap();
+        assert!(!rsa.verify(PKCS1, MESSAGE, &[signature.as_slice(), &[0]].concat()));
+        assert!(!rsa.verify(PKCS1, MESSAGE, &[&[0], signature.as_slice()].concat()));
+        assert!(!rsa.verify(PKCS1, MESSAGE, &signature[1..]));
+    }
+
+    #[test]
+    fn signature_not_less_than_modulus_is_rejected() {
+        let rsa = test_key();
+        let k = rsa.modulus_len();
+        for scheme in [PKCS1, PSS] {
+            let signature = rsa.sign(scheme, MESSAGE).unwrap();
+            assert!(rsa.verify(scheme, MESSAGE, &signature));
+            // s + n 与 s 模 n 同余，必须按范围检查拒绝
+            let wrapped = BigUint::from_bytes_be(&signature) + rsa.n();
+            if wrapped.bits() as usize <= 8 * k {
+                assert!(!rsa.verify(scheme, MESSAGE, &i2osp(&wrapped, k)));
+            }
+            assert!(!rsa.verify(scheme, MESSAGE, &i2osp(rsa.n(), k)));
+            assert!(!rsa.verify(scheme, MESSAGE, &vec![0xff; k]));
+        }
+    }
+
+    #[test]
+    fn wrong_salt_length_is_rejected() {
+        let rsa = test_key();
+        let signature = rsa.sign(PSS, MESSAGE).unwrap();
+        for salt_len in [0, 20, 31, 33, 64] {
+            let scheme = SignatureScheme::Pss { hash: HashAlgorithm::Sha256, salt_len };
+            assert!(!rsa.verify(scheme, MESSAGE, &signature));
+        }
+        let other_hash = SignatureScheme::Pss { hash: HashAlgorithm::Sha384, salt_len: 32 };
+        assert!(!rsa.verify(other_hash, MESSAGE, &signature));
+    }
+
+    #[test]
+    fn flipped_trailer_is_rejected() {
+        let rsa = test_key();
+        let (em, _) = pss_em(&rsa);
+        for trailer in [0xbd, 0x3c, 0x00, 0xcc] {
+            let mut modified = em.clone();
+            *modified.last_mut().unwrap() = trailer;
+            assert!(!rsa.verify(PSS, MESSAGE, &raw_sign(&rsa, &modified)));
+        }
+    }
+
+    #[test]
+    fn non_zero_padding_byte_is_rejected() {
+        let rsa = test_key();
+        let k = rsa.modulus_len();
+        let t = digest_info(HashAlgorithm::Sha256, &HashAlgorithm::Sha256.digest(MESSAGE)).unwrap();
+
+        // PKCS#1 v1.5：PS 中出现非 0xff 字节、块类型不是 0x01、首字节非零
+        let em = pkcs1_em(&t, k);
+        for (index, value) in [(10, 0xfe), (k - t.len() - 2, 0x00), (1, 0x02), (0, 0x01)] {
+            let mut modified = em.clone();
+            modified[index] = value;
+            if BigUint::from_bytes_be(&modified) < *rsa.n() {
+                assert!(!rsa.verify(PKCS1, MESSAGE, &raw_sign(&rsa, &modified)));
+            }
+        }
+
+        // PSS：PS 中出现非零字节、0x01 分隔符被替换、最高位未清零
+        let (em, db) = pss_em(&rsa);
+        let separator = db.len() - 32 - 1;
+        for (index, value) in [(3, 0x01), (separator, 0x02), (separator - 1, 0x01)] {
+            let mut modified_db = db.clone();
+            modified_db[index] = value;
+            let mut modified = em.clone();
+            remask(&mut modified, &modified_db);
+            assert!(!rsa.verify(PSS, MESSAGE, &raw_sign(&rsa, &modified)));
+        }
+    }
+
+    #[test]
+    fn sha1_is_not_used_for_signatures() {
+        let rsa = test_key();
+        for scheme in [
+            SignatureScheme::Pkcs1v15(HashAlgorithm::Sha1),
+            SignatureScheme::Pss { hash: HashAlgorithm::Sha1, salt_len: 20 },
+        ] {
+            assert_eq!(rsa.sign(scheme, MESSAGE), Err(RsaError::UnsupportedHash));
+        }
+    }
+}
@@ -0,0 +1,175 @@
+//! RSA 密钥校验（参考 FIPS 186-5 与 SP 800-56B 的部分检查），用于导入的外部密钥
+
+use super::private_key::{max_prime_count, RsaPrivateKey, MIN_MODULUS_BITS};
+use crate::numtheory::is_prime;
+use crate::numtheory::search::SearchError;
+use num_bigint::{BigUint, RandBigInt};
+use num_integer::Integer;
+use num_traits::One;
+use rand::rngs::OsRng;
+use std::fmt;
+
+/// 允许的最大模数位数
+pub const MAX_MODULUS_BITS: usize = 16384;
+
+/// 公钥指数的上界（不含）：2^256
+pub const MAX_PUBLIC_EXPONENT_BITS: u64 = 256;
+
+// Miller-Rabin 轮数
+const PRIMALITY_ROUNDS: usize = 40;
+
+/// 密钥生成与校验错误
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyError {
+    /// 模数位数小于允许的最小值
+    ModulusTooSmall = 1,
+    /// 模数位数超过允许的最大值
+    ModulusTooLarge = 2,
+    /// 模数为偶数或素数，不可能是两个奇素数之积
+    ModulusNotComposite = 3,
+    /// 公钥指数不是 3 <= e < 2^256 的奇数
+    InvalidPublicExponent = 4,
+    /// 私钥指数为 0 或不小于 n
+    InvalidPrivateExponent = 5,
+    /// 成对一致性测试失败：用 e 加密再用 d 解密得不到原文
+    PairwiseTestFailed = 6,
+    /// 素因子不是素数
+    FactorNotPrime = 7,
+    /// CRT 参数与 n、d 不一致
+    InconsistentCrt = 8,
+    /// 素因子个数不在 2 到允许的最大值之间
+    InvalidPrimeCount = 9,
+    /// 可证明素数生成要求模数为不小于 2048 的偶数位
+    InvalidModulusSize = 10,
+    /// 种子长度不等于安全强度的两倍
+    InvalidSeed = 11,
+    /// 可证明素数构造的计数器超出上限，需要换一个种子
+    PrimeGenerationFailed = 12,
+    /// 密钥生成被调用方取消
+    Cancelled = 13,
+    /// 无法创建密钥生成的工作线程
+    ThreadSpawnFailed = 14,
+}
+
+impl KeyError {
+    /// FFI 使用的错误编号
+    pub fn code(&self) -> i32 {
+        *self as i32
+    }
+}
+
+impl fmt::Display for KeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyError::ModulusTooSmall => write!(f, "模数过小"),
+            KeyError::ModulusTooLarge => write!(f, "模数过大"),
+            KeyError::ModulusNotComposite => write!(f, "模数不是合数"),
+            KeyError::InvalidPublicExponent => write!(f, "公钥指数无效"),
+            KeyError::InvalidPrivateExponent => write!(f, "私钥指数无效"),
+            KeyError::PairwiseTestFailed => write!(f, "成对一致性测试失败"),
+            KeyError::FactorNotPrime => write!(f, "素因子不是素数"),
+            KeyError::InconsistentCrt => write!(f, "CRT 参数不一致"),
+            KeyError::InvalidPrimeCount => write!(f, "素因子个数无效"),
+            KeyError::InvalidModulusSize => write!(f, "模数位数无效"),
+            KeyError::InvalidSeed => write!(f, "种子长度无效"),
+            KeyError::PrimeGenerationFailed => write!(f, "素数构造失败"),
+            KeyError::Cancelled => write!(f, "密钥生成已取消"),
+            KeyError::ThreadSpawnFailed => write!(f, "无法创建工作线程"),
+        }
+    }
+}
+
+impl std::error::Error for KeyError {}
+
+impl From<SearchError> for KeyError {
+    fn from(error: SearchError) -> Self {
+        match error {
+            SearchError::Cancelled => KeyError::Cancelled,
+            SearchError::ThreadSpawnFailed => KeyError::ThreadSpawnFailed,
+        }
+    }
+}
+
+/// 检查公钥指数：奇数且 3 <= e < 2^256
+pub fn check_public_exponent(e: &BigUint) -> Result<(), KeyError> {
+    if e < &BigUint::from(3u8) || e.is_even() || e.bits() > MAX_PUBLIC_EXPONENT_BITS {
+        return Err(KeyError::InvalidPublicExponent);
+    }
+    Ok(())
+}
+
+/// 检查模数：位数在允许范围内、为奇数且不是素数
+pub fn check_modulus(n: &BigUint) -> Result<(), KeyError> {
+    let bits = n.bits() as usize;
+    if bits < MIN_MODULUS_BITS {
+        return Err(KeyError::ModulusTooSmall);
+    }
+    if bits > MAX_MODULUS_BITS {
+        return Err(KeyError::ModulusTooLarge);
+    }
+    if n.is_even() || is_prime(n, PRIMALITY_ROUNDS, &mut OsRng) {
+        return Err(KeyError::ModulusNotComposite);
+    }
+    Ok(())
+}
+
+/// 校验公钥 (n, e)
+pub fn check_public_key(n: &BigUint, e: &BigUint) -> Result<(), KeyError> {
+    check_modulus(n)?;
+    check_public_exponent(e)?;
+    if e >= n {
+        return Err(KeyError::InvalidPublicExponent);
+    }
+    Ok(())
+}
+
+/// 成对一致性测试：随机 m，检查 (m^e)^d ≡ m (mod n)
+pub fn pairwise_test(n: &BigUint, e: &BigUint, d: &BigUint) -> Result<(), KeyError> {
+    if d.bits() == 0 || d >= n {
+        return Err(KeyError::InvalidPrivateExponent);
+    }
+    let m = OsRng.gen_biguint_range(&BigUint::from(2u8), &(n - BigUint::one()));
+    if m.modpow(e, n).modpow(d, n) != m {
+        return Err(KeyError::PairwiseTestFailed);
+    }
+    Ok(())
+}
+
+/// 校验带 CRT 参数的私钥：公钥、成对一致性、素因子和 CRT 参数
+pub fn check_private_key(key: &RsaPrivateKey) -> Result<(), KeyError> {
+    check_public_key(key.n(), key.e())?;
+    pairwise_test(key.n(), key.e(), key.d())?;
+
+    let one = BigUint::one();
+    let primes = key.primes();
+    if primes.len() > max_prime_count(key.n().bits() as usize) {
+        return Err(KeyError::InvalidPrimeCount);
+    }
+    if primes.iter().copied().product::<BigUint>() != *key.n() {
+        return Err(KeyError::InconsistentCrt);
+    }
+    if primes.iter().any(|r| !is_prime(r, PRIMALITY_ROUNDS, &mut OsRng)) {
+        return Err(KeyError::FactorNotPrime);
+    }
+
+    // e * d ≡ 1 (mod λ(n))，λ(n) = lcm(r_i - 1)
+    let lambda = primes.iter().fold(one.clone(), |acc, r| acc.lcm(&(*r - &one)));
+    if (key.e() * key.d()) % &lambda != one {
+        return Err(KeyError::InconsistentCrt);
+    }
+    let (p, q) = (key.p(), key.q());
+    if key.d() % (p - &one) != *key.dp() || key.d() % (q - &one) != *key.dq() || (q * key.qinv()) % p != one {
+        return Err(KeyError::InconsistentCrt);
+    }
+
+    // t_i * (r_1 * ... * r_(i-1)) ≡ 1 (mod r_i)
+    let mut product = p * q;
+    for info in key.other_primes() {
+        let r = info.prime();
+        if key.d() % (r - &one) != *info.exponent() || (&product * info.coefficient()) % r != one {
+            return Err(KeyError::InconsistentCrt);
+        }
+        product *= r;
+    }
+    Ok(())
+}
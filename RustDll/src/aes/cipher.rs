@@ -0,0 +1,105 @@
+//! 支持 128 / 192 / 256 位密钥的 AES 分组密码 (FIPS 197)，轮密钥只在创建时扩展一次
+
+use super::modes::{
+    add_round_key, bytes2state, inv_mix_columns, inv_shift_rows, inv_sub_bytes, mix_columns, rot_word, shift_rows,
+    state2bytes, sub_bytes, sub_word, xor_block, Nb, RCON,
+};
+
+pub struct AesCipher {
+    w: Vec<[u8; 4]>,
+    rounds: usize,
+}
+
+impl AesCipher {
+    /// 密钥必须为 16、24 或 32 字节，否则返回 None
+    pub fn new(key: &[u8]) -> Option<Self> {
+        let nk = match key.len() {
+            16 | 24 | 32 => key.len() / 4,
+            _ => return None,
+        };
+        let rounds = nk + 6;
+
+        let mut w: Vec<[u8; 4]> = key.chunks(4).map(|c| [c[0], c[1], c[2], c[3]]).collect();
+        for i in nk..Nb * (rounds + 1) {
+            let mut temp = w[i - 1];
+            if i % nk == 0 {
+                temp = sub_word(rot_word(temp));
+                temp[0] ^= RCON[i / nk];
+            } else if nk > 6 && i % nk == 4 {
+                temp = sub_word(temp);
+            }
+            let prev = w[i - nk];
+            w.push([prev[0] ^ temp[0], prev[1] ^ temp[1], prev[2] ^ temp[2], prev[3] ^ temp[3]]);
+        }
+        Some(AesCipher { w, rounds })
+    }
+
+    pub fn encrypt_block(&self, input: &[u8; 16]) -> [u8; 16] {
+        let mut state = bytes2state(input);
+        add_round_key(&mut state, &self.w, 0);
+        for round in 1..self.rounds {
+            sub_bytes(&mut state);
+            shift_rows(&mut state);
+            mix_columns(&mut state);
+            add_round_key(&mut state, &self.w, round);
+        }
+        sub_bytes(&mut state);
+        shift_rows(&mut state);
+        add_round_key(&mut state, &self.w, self.rounds);
+        state2bytes(&state)
+    }
+
+    pub fn decrypt_block(&self, input: &[u8; 16]) -> [u8; 16] {
+        let mut state = bytes2state(input);
+        add_round_key(&mut state, &self.w, self.rounds);
+        for round in (1..self.rounds).rev() {
+            inv_shift_rows(&mut state);
+            inv_sub_bytes(&mut state);
+            add_round_key(&mut state, &self.w, round);
+            inv_mix_columns(&mut state);
+        }
+        inv_shift_rows(&mut state);
+        inv_sub_bytes(&mut state);
+        add_round_key(&mut state, &self.w, 0);
+        state2bytes(&state)
+    }
+
+    /// CBC 加密，使用 PKCS#7 填充
+    pub fn encrypt_cbc(&self, data: &[u8], iv: &[u8; 16]) -> Vec<u8> {
+        let pad_len = 16 - data.len() % 16;
+        let mut padded = data.to_vec();
+        padded.resize(data.len() + pad_len, pad_len as u8);
+
+        let mut prev = *iv;
+        let mut out = Vec::with_capacity(padded.len());
+        for block in padded.chunks(16) {
+            let mut b = [0u8; 16];
+            b.copy_from_slice(block);
+            prev = self.encrypt_block(&xor_block(&b, &prev));
+            out.extend_from_slice(&prev);
+        }
+        out
+    }
+
+    /// CBC 解密并去除 PKCS#7 填充，长度不是分组整数倍或填充错误时返回 None
+    pub fn decrypt_cbc(&self, data: &[u8], iv: &[u8; 16]) -> Option<Vec<u8>> {
+        if data.is_empty() || !data.len().is_multiple_of(16) {
+            return None;
+        }
+        let mut prev = *iv;
+        let mut out = Vec::with_capacity(data.len());
+        for block in data.chunks(16) {
+            let mut b = [0u8; 16];
+            b.copy_from_slice(block);
+            out.extend_from_slice(&xor_block(&self.decrypt_block(&b), &prev));
+            prev = b;
+        }
+
+        let pad_len = *out.last()? as usize;
+        if pad_len == 0 || pad_len > 16 || out[out.len() - pad_len..].iter().any(|&b| b as usize != pad_len) {
+            return None;
+        }
+        out.truncate(out.len() - pad_len);
+        Some(out)
+    }
+}
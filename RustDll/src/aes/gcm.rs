@@ -0,0 +1,182 @@
+//! AES-GCM 认证加密 (NIST SP 800-38D)，支持 128 / 192 / 256 位密钥，仅支持 96 位 nonce
+
+use super::cipher::AesCipher;
+use super::modes::inc_block;
+use crate::hash::constant_time_eq;
+
+/// nonce 长度（字节）
+pub const NONCE_LEN: usize = 12;
+
+/// 认证标签长度（字节）
+pub const TAG_LEN: usize = 16;
+
+pub struct AesGcm {
+    cipher: AesCipher,
+    // 哈希子密钥 H = E(K, 0^128)
+    h: u128,
+}
+
+impl AesGcm {
+    /// 密钥必须为 16、24 或 32 字节，否则返回 None
+    pub fn new(key: &[u8]) -> Option<Self> {
+        let cipher = AesCipher::new(key)?;
+        let h = u128::from_be_bytes(cipher.encrypt_block(&[0u8; 16]));
+        Some(Self { cipher, h })
+    }
+
+    /// 加密并认证，返回 密文 || 16 字节标签
+    ///
+    /// 同一密钥下 nonce 绝不能重复使用。
+    pub fn encrypt(&self, nonce: &[u8; NONCE_LEN], aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        let mut out = self.ctr(nonce, plaintext);
+        let tag = self.tag(nonce, aad, &out);
+        out.extend_from_slice(&tag);
+        out
+    }
+
+    /// 校验标签并解密，标签不匹配时返回 None
+    pub fn decrypt(&self, nonce: &[u8; NONCE_LEN], aad: &[u8], data: &[u8]) -> Option<Vec<u8>> {
+        self.decrypt_with_tag_len(nonce, aad, data, TAG_LEN)
+    }
+
+    /// 与 `decrypt` 相同，但 data 末尾是截短为 tag_len 字节（12 到 16）的标签
+    pub fn decrypt_with_tag_len(&self, nonce: &[u8; NONCE_LEN], aad: &[u8], data: &[u8], tag_len: usize) -> Option<Vec<u8>> {
+        if !(12..=TAG_LEN).contains(&tag_len) || data.len() < tag_len {
+            return None;
+        }
+        let (ciphertext, tag) = data.split_at(data.len() - tag_len);
+        if !constant_time_eq(&self.tag(nonce, aad, ciphertext)[..tag_len], tag) {
+            return None;
+        }
+        Some(self.ctr(nonce, ciphertext))
+    }
+
+    // J0 = nonce || 0^31 || 1
+    fn j0(nonce: &[u8; NONCE_LEN]) -> [u8; 16] {
+        let mut j0 = [0u8; 16];
+        j0[..NONCE_LEN].copy_from_slice(nonce);
+        j0[15] = 1;
+        j0
+    }
+
+    // GCTR，计数器从 inc32(J0) 开始
+    fn ctr(&self, nonce: &[u8; NONCE_LEN], data: &[u8]) -> Vec<u8> {
+        let mut counter = Self::j0(nonce);
+        let mut out = Vec::with_capacity(data.len() + TAG_LEN);
+        for block in data.chunks(16) {
+            inc32(&mut counter);
+            let keystream = self.cipher.encrypt_block(&counter);
+            out.extend(block.iter().zip(keystream.iter()).map(|(a, b)| a ^ b));
+        }
+        out
+    }
+
+    // T = E(K, J0) xor GHASH(A || 0* || C || 0* || len(A) || len(C))
+    fn tag(&self, nonce: &[u8; NONCE_LEN], aad: &[u8], ciphertext: &[u8]) -> [u8; TAG_LEN] {
+        let mut y = 0u128;
+        for data in [aad, ciphertext] {
+            for block in data.chunks(16) {
+                let mut b = [0u8; 16];
+                b[..block.len()].copy_from_slice(block);
+                y = gf_mul(y ^ u128::from_be_bytes(b), self.h);
+            }
+        }
+        let lengths = ((aad.len() as u128 * 8) << 64) | (ciphertext.len() as u128 * 8);
+        y = gf_mul(y ^ lengths, self.h);
+
+        let s = u128::from_be_bytes(self.cipher.encrypt_block(&Self::j0(nonce)));
+        (y ^ s).to_be_bytes()
+    }
+}
+
+// 仅递增计数器块的低 32 位
+fn inc32(block: &mut [u8; 16]) {
+    let mut low = [0u8; 16];
+    low[12..].copy_from_slice(&block[12..]);
+    inc_block(&mut low);
+    block[12..].copy_from_slice(&low[12..]);
+}
+
+// GF(2^128) 乘法，位序按 GCM 规定（最高位为 x^0），不依赖数据分支
+fn gf_mul(x: u128, y: u128) -> u128 {
+    const R: u128 = 0xe1 << 120;
+    let mut z = 0u128;
+    let mut v = y;
+    for i in 0..128 {
+        let bit = (x >> (127 - i)) & 1;
+        z ^= v & 0u128.wrapping_sub(bit);
+        let lsb = v & 1;
+        v = (v >> 1) ^ (R & 0u128.wrapping_sub(lsb));
+    }
+    z
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unhex(s: &str) -> Vec<u8> {
+        hex::decode(s).unwrap()
+    }
+
+    // McGrew & Viega, "The Galois/Counter Mode of Operation"，测试用例 2、4、16
+    const CASES: &[(&str, &str, &str, &str, &str, &str)] = &[
+        (
+            "00000000000000000000000000000000",
+            "000000000000000000000000",
+            "",
+            "00000000000000000000000000000000",
+            "0388dace60b6a392f328c2b971b2fe78",
+            "ab6e47d42cec13bdf53a67b21257bddf",
+        ),
+        (
+            "feffe9928665731c6d6a8f9467308308",
+            "cafebabefacedbaddecaf888",
+            "feedfacedeadbeeffeedfacedeadbeefabaddad2",
+            "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39",
+            "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091",
+            "5bc94fbc3221a5db94fae95ae7121a47",
+        ),
+        (
+            "feffe9928665731c6d6a8f9467308308feffe9928665731c6d6a8f9467308308",
+            "cafebabefacedbaddecaf888",
+            "feedfacedeadbeeffeedfacedeadbeefabaddad2",
+            "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39",
+            "522dc1f099567d07f47f37a32a84427d643a8cdcbfe5c0c97598a2bd2555d1aa8cb08e48590dbb3da7b08b1056828838c5f61e6393ba7a0abcc9f662",
+            "76fc6ece0f4e1768cddf8853bb2d551b",
+        ),
+    ];
+
+    #[test]
+    fn known_answer_round_trip() {
+        for &(key, nonce, aad, plaintext, ciphertext, tag) in CASES {
+            let gcm = AesGcm::new(&unhex(key)).unwrap();
+            let nonce: [u8; NONCE_LEN] = unhex(nonce).try_into().unwrap();
+            let expected = [unhex(ciphertext), unhex(tag)].concat();
+
+            let sealed = gcm.encrypt(&nonce, &unhex(aad), &unhex(plaintext));
+            assert_eq!(sealed, expected);
+            assert_eq!(gcm.decrypt(&nonce, &unhex(aad), &sealed).unwrap(), unhex(plaintext));
+        }
+    }
+
+    #[test]
+    fn tampering_is_detected() {
+        let gcm = AesGcm::new(&[0x42; 16]).unwrap();
+        let nonce = [7u8; NONCE_LEN];
+        let sealed = gcm.encrypt(&nonce, b"header", b"payload");
+
+        for i in 0..sealed.len() {
+            let mut tampered = sealed.clone();
+            tampered[i] ^= 0x80;
+            assert!(gcm.decrypt(&nonce, b"header", &tampered).is_none(), "byte {i}");
+        }
+        assert!(gcm.decrypt(&nonce, b"Header", &sealed).is_none());
+        assert!(gcm.decrypt(&[8u8; NONCE_LEN], b"header", &sealed).is_none());
+        assert!(gcm.decrypt(&nonce, b"header", &sealed[..TAG_LEN - 1]).is_none());
+
+        let truncated = &sealed[..sealed.len() - 4];
+        assert_eq!(gcm.decrypt_with_tag_len(&nonce, b"header", truncated, 12).unwrap(), b"payload");
+        assert!(gcm.decrypt_with_tag_len(&nonce, b"header", &sealed[..sealed.len() - 5], 11).is_none());
+    }
+}
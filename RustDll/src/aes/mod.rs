@@ -98,4 +98,6 @@ pub fn generate_random_iv() -> [u8; 16] {
     iv
 }
 
+pub mod cipher;
+pub mod gcm;
 pub mod modes; 
\ No newline at end of file
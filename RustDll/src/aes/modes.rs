@@ -81,7 +81,7 @@ pub(crate) fn key_expansion(key: &[u8; 16]) -> [[u8; 4]; Nb * (Nr + 1)] {
     return  w;
 }
 
-pub(crate) fn add_round_key(state: &mut [[u8; 4]; 4], w: &[[u8; 4]; Nb * (Nr + 1)], round: usize) {
+pub(crate) fn add_round_key(state: &mut [[u8; 4]; 4], w: &[[u8; 4]], round: usize) {
     for c in 0..Nb {
         for r in 0..4 {
             state[r][c] ^= w[round * Nb + c][r];
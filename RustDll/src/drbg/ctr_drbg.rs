@@ -0,0 +1,496 @@
+//! CTR-DRBG (SP 800-90A 10.2.1)，底层使用本库的 AES-128 或 AES-256，可选是否使用派生函数 (Block_Cipher_df)
+
+use super::{os_entropy, DrbgError, MAX_BYTES_PER_REQUEST, MAX_RESEED_INTERVAL};
+use crate::aes::cipher::AesCipher;
+use crate::aes::modes::{inc_block, xor_block};
+
+const BLOCK_LEN: usize = 16;
+const MAX_SEED_LEN: usize = 32 + BLOCK_LEN;
+
+/// AES-128 实例的安全强度（字节），熵输入至少需要这么长
+pub const SECURITY_STRENGTH: usize = 16;
+
+/// CTR-DRBG 使用的分组密码
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CtrDrbgCipher {
+    Aes128,
+    Aes256,
+}
+
+impl CtrDrbgCipher {
+    /// 密钥长度（字节），同时也是安全强度
+    pub fn key_len(self) -> usize {
+        match self {
+            CtrDrbgCipher::Aes128 => 16,
+            CtrDrbgCipher::Aes256 => 32,
+        }
+    }
+
+    /// seedlen = keylen + outlen（字节）
+    pub fn seed_len(self) -> usize {
+        self.key_len() + BLOCK_LEN
+    }
+}
+
+/// AES CTR-DRBG
+pub struct CtrDrbg {
+    cipher: CtrDrbgCipher,
+    key: AesCipher,
+    v: [u8; BLOCK_LEN],
+    derivation_function: bool,
+    reseed_counter: u64,
+    reseed_interval: u64,
+    prediction_resistance: bool,
+    // 由系统熵源播种的实例在需要时可以自动重播种
+    os_seeded: bool,
+}
+
+impl CtrDrbg {
+    /// 使用给定的熵输入、nonce 和个性化字符串实例化带派生函数的 AES-128 CTR-DRBG（完全确定性）
+    pub fn new(entropy: &[u8], nonce: &[u8], personalization: &[u8]) -> Result<Self, DrbgError> {
+        Self::instantiate(CtrDrbgCipher::Aes128, true, entropy, nonce, personalization)
+    }
+
+    /// 指定分组密码和是否使用派生函数实例化（完全确定性）
+    ///
+    /// 使用派生函数时熵输入至少为安全强度；不使用派生函数时熵输入必须恰好为 seedlen 字节、
+    /// 个性化字符串不能超过 seedlen 字节，nonce 不参与运算。
+    pub fn instantiate(
+        cipher: CtrDrbgCipher,
+        derivation_function: bool,
+        entropy: &[u8],
+        nonce: &[u8],
+        personalization: &[u8],
+    ) -> Result<Self, DrbgError> {
+        let mut drbg = CtrDrbg {
+            cipher,
+            key: new_cipher(&[0u8; MAX_SEED_LEN][..cipher.key_len()]),
+            v: [0u8; BLOCK_LEN],
+            derivation_function,
+            reseed_counter: 1,
+            reseed_interval: MAX_RESEED_INTERVAL,
+            prediction_resistance: false,
+            os_seeded: false,
+        };
+        drbg.check_entropy(entropy)?;
+
+        let seed = if derivation_function {
+            drbg.block_cipher_df(&[entropy, nonce, personalization])
+        } else {
+            drbg.xor_seed(entropy, personalization)?
+        };
+        drbg.update(&seed);
+        Ok(drbg)
+    }
+
+    /// 从操作系统熵源实例化带派生函数的 AES-128 CTR-DRBG，之后可以自动重播种
+    pub fn from_entropy(personalization: &[u8]) -> Result<Self, DrbgError> {
+        let entropy = os_entropy(SECURITY_STRENGTH)?;
+        let nonce = os_entropy(SECURITY_STRENGTH / 2)?;
+        let mut drbg = Self::new(&entropy, &nonce, personalization)?;
+        drbg.os_seeded = true;
+        Ok(drbg)
+    }
+
+    /// 开启或关闭预测抗性：开启后每次 generate 前都会从系统熵源重播种
+    ///
+    /// 确定性实例没有熵源，开启后每次 generate 都会失败，作为 `RngCore` 使用时只能 panic，
+    /// 因此只有 `from_entropy` 创建的实例可以开启，否则返回 `ReseedRequired`。
+    /// 确定性实例需要预测抗性时，在每次 generate 之前自行调用 `reseed`。
+    pub fn with_prediction_resistance(mut self, enabled: bool) -> Result<Self, DrbgError> {
+        if enabled && !self.os_seeded {
+            return Err(DrbgError::ReseedRequired);
+        }
+        self.prediction_resistance = enabled;
+        Ok(self)
+    }
+
+    /// 设置重播种间隔（不能超过 2^48）
+    ///
+    /// 原因同上，只有 `from_entropy` 创建的实例可以缩短间隔，确定性实例返回 `ReseedRequired`。
+    pub fn with_reseed_interval(mut self, interval: u64) -> Result<Self, DrbgError> {
+        let interval = interval.clamp(1, MAX_RESEED_INTERVAL);
+        if interval < MAX_RESEED_INTERVAL && !self.os_seeded {
+            return Err(DrbgError::ReseedRequired);
+        }
+        self.reseed_interval = interval;
+        Ok(self)
+    }
+
+    /// 当前重播种计数器
+    pub fn reseed_counter(&self) -> u64 {
+        self.reseed_counter
+    }
+
+    /// 使用新的熵输入和附加输入重播种，长度要求与实例化时相同
+    pub fn reseed(&mut self, entropy: &[u8], additional: &[u8]) -> Result<(), DrbgError> {
+        self.check_entropy(entropy)?;
+        let seed = if self.derivation_function {
+            self.block_cipher_df(&[entropy, additional])
+        } else {
+            self.xor_seed(entropy, additional)?
+        };
+        self.update(&seed);
+        self.reseed_counter = 1;
+        Ok(())
+    }
+
+    /// 生成 out.len() 字节的伪随机数据
+    ///
+    /// 开启预测抗性或计数器到期时，系统播种的实例会自动重播种；
+    /// 确定性实例在 2^48 次 generate 之后返回 `ReseedRequired`，由调用方提供熵后调用 `reseed`。
+    pub fn generate(&mut self, out: &mut [u8], additional: &[u8]) -> Result<(), DrbgError> {
+        if out.len() > MAX_BYTES_PER_REQUEST {
+            return Err(DrbgError::RequestTooLarge);
+        }
+
+        let mut additional = additional;
+        if self.prediction_resistance || self.reseed_counter > self.reseed_interval {
+            if !self.os_seeded {
+                return Err(DrbgError::ReseedRequired);
+            }
+            let entropy = os_entropy(self.entropy_len())?;
+            self.reseed(&entropy, additional)?;
+            additional = &[];
+        }
+
+        let additional = if additional.is_empty() {
+            [0u8; MAX_SEED_LEN]
+        } else {
+            let seed = if self.derivation_function {
+                self.block_cipher_df(&[additional])
+            } else {
+                self.xor_seed(&[], additional)?
+            };
+            self.update(&seed);
+            seed
+        };
+
+        for chunk in out.chunks_mut(BLOCK_LEN) {
+            inc_block(&mut self.v);
+            let block = self.key.encrypt_block(&self.v);
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+
+        self.update(&additional);
+        self.reseed_counter += 1;
+        Ok(())
+    }
+
+    // 实例化、重播种所需的最短熵输入；不使用派生函数时也是允许的最大长度
+    fn entropy_len(&self) -> usize {
+        if self.derivation_function {
+            self.cipher.key_len()
+        } else {
+            self.cipher.seed_len()
+        }
+    }
+
+    fn check_entropy(&self, entropy: &[u8]) -> Result<(), DrbgError> {
+        if entropy.len() < self.entropy_len() {
+            return Err(DrbgError::InsufficientEntropy);
+        }
+        if !self.derivation_function && entropy.len() > self.entropy_len() {
+            return Err(DrbgError::InputTooLong);
+        }
+        Ok(())
+    }
+
+    // 不使用派生函数时的种子：熵输入与补零到 seedlen 的附加输入逐字节异或
+    fn xor_seed(&self, entropy: &[u8], additional: &[u8]) -> Result<[u8; MAX_SEED_LEN], DrbgError> {
+        if additional.len() > self.cipher.seed_len() {
+            return Err(DrbgError::InputTooLong);
+        }
+        let mut seed = [0u8; MAX_SEED_LEN];
+        seed[..entropy.len()].copy_from_slice(entropy);
+        for (s, a) in seed.iter_mut().zip(additional) {
+            *s ^= a;
+        }
+        Ok(seed)
+    }
+
+    // CTR_DRBG_Update，只使用 provided 的前 seedlen 字节
+    fn update(&mut self, provided: &[u8; MAX_SEED_LEN]) {
+        let key_len = self.cipher.key_len();
+        let seed_len = self.cipher.seed_len();
+
+        let mut temp = [0u8; MAX_SEED_LEN];
+        for chunk in temp[..seed_len].chunks_mut(BLOCK_LEN) {
+            inc_block(&mut self.v);
+            chunk.copy_from_slice(&self.key.encrypt_block(&self.v));
+        }
+        for (t, p) in temp.iter_mut().zip(provided.iter()) {
+            *t ^= p;
+        }
+        self.key = new_cipher(&temp[..key_len]);
+        self.v.copy_from_slice(&temp[key_len..seed_len]);
+    }
+
+    // Block_Cipher_df：把任意长度的输入压缩为 seedlen 字节
+    fn block_cipher_df(&self, inputs: &[&[u8]]) -> [u8; MAX_SEED_LEN] {
+        let key_len = self.cipher.key_len();
+        let seed_len = self.cipher.seed_len();
+        let input_len: usize = inputs.iter().map(|i| i.len()).sum();
+
+        // S = L || N || input || 0x80，补零到分组长度的整数倍
+        let mut s = Vec::with_capacity(input_len + 9 + BLOCK_LEN);
+        s.extend_from_slice(&(input_len as u32).to_be_bytes());
+        s.extend_from_slice(&(seed_len as u32).to_be_bytes());
+        for input in inputs {
+            s.extend_from_slice(input);
+        }
+        s.push(0x80);
+        while s.len() % BLOCK_LEN != 0 {
+            s.push(0x00);
+        }
+
+        // K = 0x00 01 02 ... 的前 keylen 字节
+        let df_key: Vec<u8> = (0..key_len as u8).collect();
+        let df_cipher = new_cipher(&df_key);
+
+        let mut temp = [0u8; MAX_SEED_LEN];
+        for (i, chunk) in temp[..seed_len].chunks_mut(BLOCK_LEN).enumerate() {
+            let mut iv = [0u8; BLOCK_LEN];
+            iv[..4].copy_from_slice(&(i as u32).to_be_bytes());
+            chunk.copy_from_slice(&bcc(&df_cipher, &iv, &s));
+        }
+
+        let cipher = new_cipher(&temp[..key_len]);
+        let mut x = [0u8; BLOCK_LEN];
+        x.copy_from_slice(&temp[key_len..seed_len]);
+
+        let mut out = [0u8; MAX_SEED_LEN];
+        for chunk in out[..seed_len].chunks_mut(BLOCK_LEN) {
+            x = cipher.encrypt_block(&x);
+            chunk.copy_from_slice(&x);
+        }
+        out
+    }
+}
+
+impl_drbg_rng!(CtrDrbg);
+
+// 密钥长度只会是 16 或 32 字节
+fn new_cipher(key: &[u8]) -> AesCipher {
+    AesCipher::new(key).expect("AES-128/256 key length")
+}
+
+// BCC：以 iv 为第一个分组的 CBC-MAC
+fn bcc(cipher: &AesCipher, iv: &[u8; BLOCK_LEN], data: &[u8]) -> [u8; BLOCK_LEN] {
+    let mut chaining = cipher.encrypt_block(iv);
+    for block in data.chunks(BLOCK_LEN) {
+        let mut b = [0u8; BLOCK_LEN];
+        b.copy_from_slice(block);
+        chaining = cipher.encrypt_block(&xor_block(&chaining, &b));
+    }
+    chaining
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn h(hex_str: &str) -> Vec<u8> {
+        hex::decode(hex_str).unwrap()
+    }
+
+    fn pattern(start: u8, len: usize) -> Vec<u8> {
+        (0..len).map(|i| start.wrapping_add(i as u8)).collect()
+    }
+
+    #[test]
+    fn cavp_aes256_df_no_reseed() {
+        // drbgvectors_no_reseed/CTR_DRBG.rsp [AES-256 use df] COUNT = 0
+        let mut drbg = CtrDrbg::instantiate(
+            CtrDrbgCipher::Aes256,
+            true,
+            &h("36401940fa8b1fba91a1661f211d78a0b9389a74e5bccfece8d766af1a6d3b14"),
+            &h("496f25b0f1301b4f501be30380a137eb"),
+            &[],
+        )
+        .unwrap();
+        let mut out = [0u8; 64];
+        drbg.generate(&mut out, &[]).unwrap();
+        drbg.generate(&mut out, &[]).unwrap();
+        assert_eq!(
+            out[..],
+            h(concat!(
+                "5862eb38bd558dd978a696e6df164782ddd887e7e9a6c9f3f1fbafb78941b535",
+                "a64912dfd224c6dc7454e5250b3d97165e16260c2faf1cc7735cb75fb4f07e1d",
+            ))
+        );
+    }
+
+    #[test]
+    fn cavp_aes128_no_df_reseed() {
+        // drbgvectors_pr_false/CTR_DRBG.rsp [AES-128 no df] COUNT = 0
+        let mut drbg = CtrDrbg::instantiate(
+            CtrDrbgCipher::Aes128,
+            false,
+            &h("ed1e7f21ef66ea5d8e2a85b9337245445b71d6393a4eecb0e63c193d0f72f9a9"),
+            &[],
+            &[],
+        )
+        .unwrap();
+        drbg.reseed(&h("303fb519f0a4e17d6df0b6426aa0ecb2a36079bd48be47ad2a8dbfe48da3efad"), &[]).unwrap();
+        let mut out = [0u8; 64];
+        drbg.generate(&mut out, &[]).unwrap();
+        drbg.generate(&mut out, &[]).unwrap();
+        assert_eq!(
+            out[..],
+            h(concat!(
+                "f80111d08e874672f32f42997133a5210f7a9375e22cea70587f9cfafebe0f6a",
+                "6aa2eb68e7dd9164536d53fa020fcab20f54caddfab7d6d91e5ffec1dfd8deaa",
+            ))
+        );
+    }
+
+    #[test]
+    fn cavp_aes128_df_reseed() {
+        // drbgvectors_pr_false/CTR_DRBG.rsp [AES-128 use df] COUNT = 0
+        let mut drbg =
+            CtrDrbg::new(&h("0f65da13dca407999d4773c2b4a11d85"), &h("5209e5b4ed82a234"), &[]).unwrap();
+        drbg.reseed(&h("1dea0a12c52bf64339dd291c80d8ca89"), &[]).unwrap();
+        let mut out = [0u8; 64];
+        drbg.generate(&mut out, &[]).unwrap();
+        drbg.generate(&mut out, &[]).unwrap();
+        assert_eq!(
+            out[..],
+            h(concat!(
+                "2859cc468a76b08661ffd23b28547ffd0997ad526a0f51261b99ed3a37bd407b",
+                "f418dbe6c6c3e26ed0ddefcb7474d899bd99f3655427519fc5b4057bcaf306d4",
+            ))
+        );
+    }
+
+    #[test]
+    fn cavp_aes256_no_df_reseed() {
+        // drbgvectors_pr_false/CTR_DRBG.rsp [AES-256 no df] COUNT = 0
+        let mut drbg = CtrDrbg::instantiate(
+            CtrDrbgCipher::Aes256,
+            false,
+            &h("e4bc23c5089a19d86f4119cb3fa08c0a4991e0a1def17e101e4c14d9c323460a7c2fb58e0b086c6c57b55f56cae25bad"),
+            &[],
+            &[],
+        )
+        .unwrap();
+        drbg.reseed(
+            &h("fd85a836bba85019881e8c6bad23c9061adc75477659acaea8e4a01dfe07a1832dad1c136f59d70f8653a5dc118663d6"),
+            &[],
+        )
+        .unwrap();
+        let mut out = [0u8; 64];
+        drbg.generate(&mut out, &[]).unwrap();
+        drbg.generate(&mut out, &[]).unwrap();
+        assert_eq!(
+            out[..],
+            h(concat!(
+                "b2cb8905c05e5950ca31895096be29ea3d5a3b82b269495554eb80fe07de43e1",
+                "93b9e7c3ece73b80e062b1c1f68202fbb1c52a040ea2478864295282234aaada",
+            ))
+        );
+    }
+
+    #[test]
+    fn cavp_aes128_df_prediction_resistance() {
+        // drbgvectors_pr_true/CTR_DRBG.rsp [AES-128 use df] [PersonalizationStringLen = 128]
+        // [AdditionalInputLen = 128] COUNT = 0
+        //
+        // 预测抗性下每次 generate 先用 EntropyInputPR 和附加输入重播种，再以空附加输入生成，
+        // 确定性实例没有熵源，这里显式调用 reseed 完成同样的步骤
+        let mut drbg = CtrDrbg::new(
+            &h("92898f31fa1cff6d182f260643dff818"),
+            &h("c2a4d972c3b9b697"),
+            &h("ea65ee60264e7eb60e8268c4373c5c0b"),
+        )
+        .unwrap();
+        let mut out = [0u8; 64];
+        drbg.reseed(&h("20728a06f86f8dd441e272b7c42ce810"), &h("1a40fae3cc6c7ca0f8daba59236dad1d")).unwrap();
+        drbg.generate(&mut out, &[]).unwrap();
+        drbg.reseed(&h("3db0f094f305503317863e2208f7a501"), &h("9f72766cc746e5ed2e532012bc59318c")).unwrap();
+        drbg.generate(&mut out, &[]).unwrap();
+        assert_eq!(
+            out[..],
+            h(concat!(
+                "5a3539870f4d22a40924ee71c96fac720ad6f08882d0832873ec3f93d8ab4523",
+                "f07eac45145e939fb1d676433db6e80888f6da89087742fe1af43fc423c51f68",
+            ))
+        );
+    }
+
+    // 以下两个用例覆盖 generate 时的附加输入，期望值与 OpenSSL 3 的 CTR-DRBG
+    // （EVP_RAND "CTR-DRBG"，熵和 nonce 由 "TEST-RAND" 提供）输出一致
+    #[test]
+    fn aes256_no_df_with_additional_input() {
+        let mut drbg =
+            CtrDrbg::instantiate(CtrDrbgCipher::Aes256, false, &pattern(0x00, 48), &[], &pattern(0x40, 48)).unwrap();
+        drbg.reseed(&pattern(0x80, 48), &pattern(0xb0, 16)).unwrap();
+        let mut out = [0u8; 64];
+        drbg.generate(&mut out, &pattern(0xc0, 48)).unwrap();
+        drbg.generate(&mut out, &pattern(0xf0, 48)).unwrap();
+        assert_eq!(
+            out[..],
+            h(concat!(
+                "965522d07d4af745e9fb7e5139b3b8935f680fc0100e6f060d0a37310be5ceb1",
+                "a68333325da1aa0563900827213082bc532b25ea3b692b818ef2f4b0dafe7d16",
+            ))
+        );
+    }
+
+    #[test]
+    fn aes256_df_with_additional_input() {
+        let mut drbg = CtrDrbg::instantiate(
+            CtrDrbgCipher::Aes256,
+            true,
+            &pattern(0x00, 32),
+            &pattern(0x20, 16),
+            &pattern(0x30, 32),
+        )
+        .unwrap();
+        let mut out = [0u8; 64];
+        drbg.generate(&mut out, &pattern(0x60, 32)).unwrap();
+        drbg.generate(&mut out, &pattern(0xa0, 32)).unwrap();
+        assert_eq!(
+            out[..],
+            h(concat!(
+                "93943392df0f0a8c1bf3560d04cb9cbd0a3fdf9093f149a760130cad85f15bff",
+                "c70a041c716bd352eeaf0ba0705eb5b0319b7476a40e605f942bfd71d33f142e",
+            ))
+        );
+    }
+
+    #[test]
+    fn deterministic_instance_cannot_require_a_reseed() {
+        let drbg = || CtrDrbg::new(&[1u8; 16], &[2u8; 8], &[]).unwrap();
+        assert_eq!(drbg().with_prediction_resistance(true).err(), Some(DrbgError::ReseedRequired));
+        assert_eq!(drbg().with_reseed_interval(1).err(), Some(DrbgError::ReseedRequired));
+
+        let mut drbg = CtrDrbg::from_entropy(&[])
+            .unwrap()
+            .with_prediction_resistance(true)
+            .unwrap()
+            .with_reseed_interval(1)
+            .unwrap();
+        let mut out = [0u8; 32];
+        drbg.generate(&mut out, &[]).unwrap();
+        drbg.generate(&mut out, &[]).unwrap();
+        assert_eq!(drbg.reseed_counter(), 2);
+    }
+
+    #[test]
+    fn no_df_input_lengths_are_checked() {
+        let instantiate = |entropy: &[u8], personalization: &[u8]| {
+            CtrDrbg::instantiate(CtrDrbgCipher::Aes256, false, entropy, &[], personalization).err()
+        };
+        assert_eq!(instantiate(&[0u8; 47], &[]), Some(DrbgError::InsufficientEntropy));
+        assert_eq!(instantiate(&[0u8; 49], &[]), Some(DrbgError::InputTooLong));
+        assert_eq!(instantiate(&[0u8; 48], &[0u8; 49]), Some(DrbgError::InputTooLong));
+
+        let mut drbg = CtrDrbg::instantiate(CtrDrbgCipher::Aes256, false, &[0u8; 48], &[], &[]).unwrap();
+        assert_eq!(drbg.generate(&mut [0u8; 16], &[0u8; 49]), Err(DrbgError::InputTooLong));
+        assert_eq!(
+            CtrDrbg::instantiate(CtrDrbgCipher::Aes256, true, &[0u8; 31], &[], &[]).err(),
+            Some(DrbgError::InsufficientEntropy)
+        );
+    }
+}
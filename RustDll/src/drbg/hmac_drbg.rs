@@ -0,0 +1,296 @@
+//! HMAC-DRBG (SP 800-90A 10.1.2)，底层使用 HMAC-SHA256
+
+use super::{os_entropy, DrbgError, MAX_BYTES_PER_REQUEST, MAX_RESEED_INTERVAL};
+use crate::hash::{HashAlgorithm, Hmac};
+
+const OUT_LEN: usize = 32;
+
+/// 安全强度（字节），熵输入至少需要这么长
+pub const SECURITY_STRENGTH: usize = 32;
+
+/// HMAC-SHA256 DRBG
+pub struct HmacDrbg {
+    k: [u8; OUT_LEN],
+    v: [u8; OUT_LEN],
+    reseed_counter: u64,
+    reseed_interval: u64,
+    prediction_resistance: bool,
+    // 由系统熵源播种的实例在需要时可以自动重播种
+    os_seeded: bool,
+}
+
+impl HmacDrbg {
+    /// 使用给定的熵输入、nonce 和个性化字符串实例化（完全确定性）
+    pub fn new(entropy: &[u8], nonce: &[u8], personalization: &[u8]) -> Result<Self, DrbgError> {
+        if entropy.len() < SECURITY_STRENGTH {
+            return Err(DrbgError::InsufficientEntropy);
+        }
+
+        let mut drbg = HmacDrbg {
+            k: [0x00; OUT_LEN],
+            v: [0x01; OUT_LEN],
+            reseed_counter: 1,
+            reseed_interval: MAX_RESEED_INTERVAL,
+            prediction_resistance: false,
+            os_seeded: false,
+        };
+        drbg.update(&[entropy, nonce, personalization]);
+        Ok(drbg)
+    }
+
+    /// 从操作系统熵源实例化，之后可以自动重播种
+    pub fn from_entropy(personalization: &[u8]) -> Result<Self, DrbgError> {
+        let entropy = os_entropy(SECURITY_STRENGTH)?;
+        let nonce = os_entropy(SECURITY_STRENGTH / 2)?;
+        let mut drbg = Self::new(&entropy, &nonce, personalization)?;
+        drbg.os_seeded = true;
+        Ok(drbg)
+    }
+
+    /// 开启或关闭预测抗性：开启后每次 generate 前都会从系统熵源重播种
+    ///
+    /// 确定性实例没有熵源，开启后每次 generate 都会失败，作为 `RngCore` 使用时只能 panic，
+    /// 因此只有 `from_entropy` 创建的实例可以开启，否则返回 `ReseedRequired`。
+    /// 确定性实例需要预测抗性时，在每次 generate 之前自行调用 `reseed`。
+    pub fn with_prediction_resistance(mut self, enabled: bool) -> Result<Self, DrbgError> {
+        if enabled && !self.os_seeded {
+            return Err(DrbgError::ReseedRequired);
+        }
+        self.prediction_resistance = enabled;
+        Ok(self)
+    }
+
+    /// 设置重播种间隔（不能超过 2^48）
+    ///
+    /// 原因同上，只有 `from_entropy` 创建的实例可以缩短间隔，确定性实例返回 `ReseedRequired`。
+    pub fn with_reseed_interval(mut self, interval: u64) -> Result<Self, DrbgError> {
+        let interval = interval.clamp(1, MAX_RESEED_INTERVAL);
+        if interval < MAX_RESEED_INTERVAL && !self.os_seeded {
+            return Err(DrbgError::ReseedRequired);
+        }
+        self.reseed_interval = interval;
+        Ok(self)
+    }
+
+    /// 当前重播种计数器
+    pub fn reseed_counter(&self) -> u64 {
+        self.reseed_counter
+    }
+
+    /// 使用新的熵输入和附加输入重播种
+    pub fn reseed(&mut self, entropy: &[u8], additional: &[u8]) -> Result<(), DrbgError> {
+        if entropy.len() < SECURITY_STRENGTH {
+            return Err(DrbgError::InsufficientEntropy);
+        }
+        self.update(&[entropy, additional]);
+        self.reseed_counter = 1;
+        Ok(())
+    }
+
+    /// 生成 out.len() 字节的伪随机数据
+    ///
+    /// 开启预测抗性或计数器到期时，系统播种的实例会自动重播种；
+    /// 确定性实例在 2^48 次 generate 之后返回 `ReseedRequired`，由调用方提供熵后调用 `reseed`。
+    pub fn generate(&mut self, out: &mut [u8], additional: &[u8]) -> Result<(), DrbgError> {
+        if out.len() > MAX_BYTES_PER_REQUEST {
+            return Err(DrbgError::RequestTooLarge);
+        }
+
+        let mut additional = additional;
+        if self.prediction_resistance || self.reseed_counter > self.reseed_interval {
+            if !self.os_seeded {
+                return Err(DrbgError::ReseedRequired);
+            }
+            let entropy = os_entropy(SECURITY_STRENGTH)?;
+            self.reseed(&entropy, additional)?;
+            additional = &[];
+        }
+
+        if !additional.is_empty() {
+            self.update(&[additional]);
+        }
+
+        for chunk in out.chunks_mut(OUT_LEN) {
+            self.v = hmac_sha256(&self.k, &[&self.v]);
+            chunk.copy_from_slice(&self.v[..chunk.len()]);
+        }
+
+        self.update(&[additional]);
+        self.reseed_counter += 1;
+        Ok(())
+    }
+
+    // HMAC_DRBG_Update，provided 以多个片段的形式传入以避免拼接
+    fn update(&mut self, provided: &[&[u8]]) {
+        let empty = provided.iter().all(|p| p.is_empty());
+
+        let mut parts: Vec<&[u8]> = vec![&self.v, &[0x00]];
+        parts.extend_from_slice(provided);
+        self.k = hmac_sha256(&self.k, &parts);
+        self.v = hmac_sha256(&self.k, &[&self.v]);
+
+        if empty {
+            return;
+        }
+
+        let mut parts: Vec<&[u8]> = vec![&self.v, &[0x01]];
+        parts.extend_from_slice(provided);
+        self.k = hmac_sha256(&self.k, &parts);
+        self.v = hmac_sha256(&self.k, &[&self.v]);
+    }
+}
+
+impl_drbg_rng!(HmacDrbg);
+
+// 32 字节密钥的 HMAC-SHA256，输入以多个片段给出
+fn hmac_sha256(key: &[u8; OUT_LEN], parts: &[&[u8]]) -> [u8; OUT_LEN] {
+    let mut hmac = Hmac::new(HashAlgorithm::Sha256, key).expect("SHA-256 supports HMAC");
+    for part in parts {
+        hmac.update(part);
+    }
+    hmac.finalize()
+        .try_into()
+        .expect("SHA-256 digest is 32 bytes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn h(hex_str: &str) -> Vec<u8> {
+        hex::decode(hex_str).unwrap()
+    }
+
+    fn pattern(start: u8, len: usize) -> Vec<u8> {
+        (0..len).map(|i| start.wrapping_add(i as u8)).collect()
+    }
+
+    // CAVP 的 ReturnedBits 是第二次 generate 的输出
+    fn second_output(drbg: &mut HmacDrbg, len: usize) -> Vec<u8> {
+        let mut out = vec![0u8; len];
+        drbg.generate(&mut out, &[]).unwrap();
+        drbg.generate(&mut out, &[]).unwrap();
+        out
+    }
+
+    #[test]
+    fn cavp_sha256_no_reseed() {
+        // drbgvectors_no_reseed/HMAC_DRBG.rsp [SHA-256] COUNT = 0
+        let mut drbg = HmacDrbg::new(
+            &h("ca851911349384bffe89de1cbdc46e6831e44d34a4fb935ee285dd14b71a7488"),
+            &h("659ba96c601dc69fc902940805ec0ca8"),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            second_output(&mut drbg, 128),
+            h(concat!(
+                "e528e9abf2dece54d47c7e75e5fe302149f817ea9fb4bee6f4199697d04d5b89d54fbb978a15b5c443c9ec21036d2460",
+                "b6f73ebad0dc2aba6e624abf07745bc107694bb7547bb0995f70de25d6b29e2d3011bb19d27676c07162c8b5ccde0668",
+                "961df86803482cb37ed6d5c0bb8d50cf1f50d476aa0458bdaba806f48be9dcb8",
+            ))
+        );
+    }
+
+    #[test]
+    fn cavp_sha256_reseed() {
+        // drbgvectors_pr_false/HMAC_DRBG.rsp [SHA-256] COUNT = 0
+        let mut drbg = HmacDrbg::new(
+            &h("06032cd5eed33f39265f49ecb142c511da9aff2af71203bffaf34a9ca5bd9c0d"),
+            &h("0e66f71edc43e42a45ad3c6fc6cdc4df"),
+            &[],
+        )
+        .unwrap();
+        drbg.reseed(&h("01920a4e669ed3a85ae8a33b35a74ad7fb2a6bb4cf395ce00334a9c9a5a5d552"), &[]).unwrap();
+        assert_eq!(drbg.reseed_counter(), 1);
+        assert_eq!(
+            second_output(&mut drbg, 128),
+            h(concat!(
+                "76fc79fe9b50beccc991a11b5635783a83536add03c157fb30645e611c2898bb2b1bc215000209208cd506cb28da2a51",
+                "bdb03826aaf2bd2335d576d519160842e7158ad0949d1a9ec3e66ea1b1a064b005de914eac2e9d4f2d72a8616a802254",
+                "22918250ff66a41bd2f864a6a38cc5b6499dc43f7f2bd09e1e0f8f5885935124",
+            ))
+        );
+        assert_eq!(drbg.reseed_counter(), 3);
+    }
+
+    #[test]
+    fn cavp_sha256_additional_input() {
+        // drbgvectors_no_reseed/HMAC_DRBG.rsp [SHA-256] [AdditionalInputLen = 256] COUNT = 0
+        let mut drbg = HmacDrbg::new(
+            &h("d3cc4d1acf3dde0c4bd2290d262337042dc632948223d3a2eaab87da44295fbd"),
+            &h("0109b0e729f457328aa18569a9224921"),
+            &[],
+        )
+        .unwrap();
+        let mut out = vec![0u8; 128];
+        drbg.generate(&mut out, &h("3c311848183c9a212a26f27f8c6647e40375e466a0857cc39c4e47575d53f1f6")).unwrap();
+        drbg.generate(&mut out, &h("fcb9abd19ccfbccef88c9c39bfb3dd7b1c12266c9808992e305bc3cff566e4e4")).unwrap();
+        assert_eq!(
+            out,
+            h(concat!(
+                "9c7b758b212cd0fcecd5daa489821712e3cdea4467b560ef5ddc24ab47749a1f1ffdbbb118f4e62fcfca3371b8fbfc5b",
+                "0646b83e06bfbbab5fac30ea09ea2bc76f1ea568c9be0444b2cc90517b20ca825f2d0eccd88e7175538b85d90ab39018",
+                "3ca6395535d34473af6b5a5b88f5a59ee7561573337ea819da0dcc3573a22974",
+            ))
+        );
+    }
+
+    #[test]
+    fn prediction_resistance_sequence() {
+        // 预测抗性的 generate 等价于先用 EntropyInputPR 和附加输入重播种，再以空附加输入生成；
+        // 期望值与 OpenSSL 3 的 HMAC-DRBG（EVP_RAND "HMAC-DRBG"，熵和 nonce 由 "TEST-RAND" 提供）开启预测抗性时的输出一致
+        let mut drbg = HmacDrbg::new(&pattern(0x00, 32), &pattern(0x20, 16), &pattern(0x30, 32)).unwrap();
+        let mut out = vec![0u8; 128];
+        drbg.reseed(&pattern(0x50, 32), &pattern(0x70, 32)).unwrap();
+        drbg.generate(&mut out, &[]).unwrap();
+        drbg.reseed(&pattern(0x90, 32), &pattern(0xb0, 32)).unwrap();
+        drbg.generate(&mut out, &[]).unwrap();
+        assert_eq!(
+            out,
+            h(concat!(
+                "2370281436d5d5a0be4bb99549dd3f8510c8f3c7ef82a5c4058012aaa141bd5fd28e57a2eac43586a29d619b3fb65420",
+                "1159ecd95477d2d18f7e82d016a2a1394df478e6f09cabae8beafb78a6dbb357105fa0f1c3317283257611035936078f",
+                "b6b9480c4679d8aefa31f416fd027315e609f06f555d32bc19699d734fc7f946",
+            ))
+        );
+    }
+
+    #[test]
+    fn deterministic_instance_cannot_require_a_reseed() {
+        // 确定性实例没有熵源，开启预测抗性或缩短间隔后作为 RngCore 使用会 panic，因此直接拒绝
+        let drbg = || HmacDrbg::new(&[1u8; 32], &[], &[]).unwrap();
+        assert_eq!(drbg().with_prediction_resistance(true).err(), Some(DrbgError::ReseedRequired));
+        assert_eq!(drbg().with_reseed_interval(1).err(), Some(DrbgError::ReseedRequired));
+        assert!(drbg().with_prediction_resistance(false).is_ok());
+        assert!(drbg().with_reseed_interval(MAX_RESEED_INTERVAL).is_ok());
+
+        // 确定性实例仍然可以随时显式重播种
+        let mut drbg = drbg();
+        let mut out = [0u8; 16];
+        drbg.generate(&mut out, &[]).unwrap();
+        drbg.reseed(&[2u8; 32], &[]).unwrap();
+        assert_eq!(drbg.reseed_counter(), 1);
+        drbg.generate(&mut out, &[]).unwrap();
+
+        assert_eq!(HmacDrbg::new(&[1u8; 31], &[], &[]).err(), Some(DrbgError::InsufficientEntropy));
+    }
+
+    #[test]
+    fn os_seeded_instance_reseeds_automatically() {
+        use rand::RngCore;
+
+        let mut drbg = HmacDrbg::from_entropy(b"test")
+            .unwrap()
+            .with_prediction_resistance(true)
+            .unwrap()
+            .with_reseed_interval(1)
+            .unwrap();
+        let mut out = [0u8; 64];
+        for _ in 0..3 {
+            drbg.fill_bytes(&mut out);
+            assert_eq!(drbg.reseed_counter(), 2);
+        }
+        assert!(drbg.try_fill_bytes(&mut vec![0u8; 3 * MAX_BYTES_PER_REQUEST + 1]).is_ok());
+    }
+}
@@ -0,0 +1,94 @@
+//! 确定性随机比特生成器 (NIST SP 800-90A)
+//!
+//! 提供 HMAC-DRBG 与 CTR-DRBG 两种实现，均实现了 `rand` 的 `RngCore + CryptoRng`，
+//! 可以直接传给 Rsa / ElGamal 的密钥生成函数，便于测试与可复现的模拟。
+
+// 为 DRBG 实现 rand 的 RngCore / CryptoRng，超长请求按单次上限分段生成
+//
+// 确定性实例不能开启预测抗性或缩短重播种间隔，需要重播种时它们无法继续输出，
+// 因此 fill_bytes 只会在系统熵源失败（与 OsRng 相同）或确定性实例用满 2^48 次 generate 时 panic；
+// 需要处理这些错误的调用方使用 try_fill_bytes。
+macro_rules! impl_drbg_rng {
+    ($t:ty) => {
+        impl rand::RngCore for $t {
+            fn next_u32(&mut self) -> u32 {
+                let mut buf = [0u8; 4];
+                self.fill_bytes(&mut buf);
+                u32::from_le_bytes(buf)
+            }
+
+            fn next_u64(&mut self) -> u64 {
+                let mut buf = [0u8; 8];
+                self.fill_bytes(&mut buf);
+                u64::from_le_bytes(buf)
+            }
+
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                self.try_fill_bytes(dest).expect("DRBG generate failed")
+            }
+
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+                for chunk in dest.chunks_mut($crate::drbg::MAX_BYTES_PER_REQUEST) {
+                    self.generate(chunk, &[]).map_err(rand::Error::new)?;
+                }
+                Ok(())
+            }
+        }
+
+        impl rand::CryptoRng for $t {}
+    };
+}
+
+pub mod ctr_drbg;
+pub mod hmac_drbg;
+
+pub use ctr_drbg::{CtrDrbg, CtrDrbgCipher};
+pub use hmac_drbg::HmacDrbg;
+
+use std::fmt;
+
+/// 两次重播种之间允许的最大 generate 调用次数（SP 800-90A 表 2/表 3 的上限 2^48）
+pub const MAX_RESEED_INTERVAL: u64 = 1 << 48;
+
+/// 单次 generate 请求允许输出的最大字节数（2^19 比特）
+pub const MAX_BYTES_PER_REQUEST: usize = 1 << 16;
+
+/// DRBG 错误类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrbgError {
+    /// 熵输入长度不足
+    InsufficientEntropy,
+    /// 请求的输出长度超过单次上限
+    RequestTooLarge,
+    /// 重播种计数器已到上限，且没有可用的熵源
+    ReseedRequired,
+    /// 从操作系统获取熵失败
+    EntropySourceFailure,
+    /// 不使用派生函数的 CTR-DRBG 收到超过 seedlen 的输入
+    InputTooLong,
+}
+
+impl fmt::Display for DrbgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DrbgError::InsufficientEntropy => write!(f, "熵输入长度不足"),
+            DrbgError::RequestTooLarge => write!(f, "请求的输出长度过大"),
+            DrbgError::ReseedRequired => write!(f, "需要重新播种"),
+            DrbgError::EntropySourceFailure => write!(f, "获取系统熵失败"),
+            DrbgError::InputTooLong => write!(f, "输入长度超过 seedlen"),
+        }
+    }
+}
+
+impl std::error::Error for DrbgError {}
+
+/// 从操作系统熵源读取指定字节数
+pub(crate) fn os_entropy(len: usize) -> Result<Vec<u8>, DrbgError> {
+    use rand::RngCore;
+
+    let mut buf = vec![0u8; len];
+    rand::rngs::OsRng
+        .try_fill_bytes(&mut buf)
+        .map_err(|_| DrbgError::EntropySourceFailure)?;
+    Ok(buf)
+}
@@ -0,0 +1,81 @@
+//! Base64 编解码 (RFC 4648)，支持标准字母表与 URL 安全字母表、带或不带填充
+
+const STANDARD_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const URL_SAFE_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Base64 变体
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Base64Variant {
+    /// 标准字母表，带 '=' 填充（PEM 使用）
+    Standard,
+    /// 标准字母表，不带填充（PHC 字符串使用）
+    StandardNoPad,
+    /// URL 安全字母表，不带填充（JOSE / JWK 使用）
+    UrlSafeNoPad,
+}
+
+impl Base64Variant {
+    fn alphabet(&self) -> &'static [u8; 64] {
+        match self {
+            Base64Variant::Standard | Base64Variant::StandardNoPad => STANDARD_ALPHABET,
+            Base64Variant::UrlSafeNoPad => URL_SAFE_ALPHABET,
+        }
+    }
+
+    fn padded(&self) -> bool {
+        matches!(self, Base64Variant::Standard)
+    }
+}
+
+/// 编码为 Base64 字符串
+pub fn encode(data: &[u8], variant: Base64Variant) -> String {
+    let alphabet = variant.alphabet();
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+        let chars = chunk.len() + 1;
+        for i in 0..4 {
+            if i < chars {
+                out.push(alphabet[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else if variant.padded() {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// 解码 Base64 字符串；填充可有可无，但不允许非法字符和多余的比特
+pub fn decode(text: &str, variant: Base64Variant) -> Option<Vec<u8>> {
+    let alphabet = variant.alphabet();
+    let text = text.trim_end_matches('=');
+    if text.len() % 4 == 1 {
+        return None;
+    }
+
+    let mut out = Vec::with_capacity(text.len() * 3 / 4);
+    let mut acc = 0u32;
+    let mut bits = 0u32;
+    for c in text.bytes() {
+        let value = alphabet.iter().position(|&a| a == c)? as u32;
+        acc = (acc << 6) | value;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+
+    // 剩余的比特必须全部为零（规范编码）
+    if acc != 0 {
+        return None;
+    }
+    Some(out)
+}
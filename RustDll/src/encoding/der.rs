@@ -0,0 +1,109 @@
+//! ASN.1 DER 编解码 (ITU-T X.690)，只实现密钥格式用到的类型
+
+use num_bigint::BigUint;
+
+pub const TAG_INTEGER: u8 = 0x02;
+pub const TAG_BIT_STRING: u8 = 0x03;
+pub const TAG_OCTET_STRING: u8 = 0x04;
+pub const TAG_NULL: u8 = 0x05;
+pub const TAG_OID: u8 = 0x06;
+pub const TAG_SEQUENCE: u8 = 0x30;
+
+/// 编码一个 TLV
+pub fn encode_tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut out = vec![tag];
+    let len = content.len();
+    if len < 0x80 {
+        out.push(len as u8);
+    } else {
+        let bytes: Vec<u8> = len.to_be_bytes().into_iter().skip_while(|&b| b == 0).collect();
+        out.push(0x80 | bytes.len() as u8);
+        out.extend_from_slice(&bytes);
+    }
+    out.extend_from_slice(content);
+    out
+}
+
+/// 编码非负 INTEGER，最高位为 1 时补 0x00
+pub fn encode_integer(value: &BigUint) -> Vec<u8> {
+    let mut content = value.to_bytes_be();
+    if content[0] & 0x80 != 0 {
+        content.insert(0, 0);
+    }
+    encode_tlv(TAG_INTEGER, &content)
+}
+
+/// 把已编码的元素依次拼接为 SEQUENCE
+pub fn encode_sequence(items: &[Vec<u8>]) -> Vec<u8> {
+    encode_tlv(TAG_SEQUENCE, &items.concat())
+}
+
+/// 顺序读取 DER 元素，任何不符合 DER 的编码都返回 None
+pub struct DerReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> DerReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        DerReader { data }
+    }
+
+    /// 是否已读完
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// 下一个元素的标签
+    pub fn peek_tag(&self) -> Option<u8> {
+        self.data.first().copied()
+    }
+
+    /// 读取一个标签为 tag 的元素，返回其内容
+    pub fn read(&mut self, tag: u8) -> Option<&'a [u8]> {
+        let (&actual, rest) = self.data.split_first()?;
+        if actual != tag {
+            return None;
+        }
+        let (&first, rest) = rest.split_first()?;
+        let (len, rest) = if first < 0x80 {
+            (first as usize, rest)
+        } else {
+            let count = (first & 0x7f) as usize;
+            if count == 0 || count > std::mem::size_of::<usize>() || rest.len() < count {
+                return None;
+            }
+            let (bytes, rest) = rest.split_at(count);
+            // 长度必须使用最短形式
+            if bytes[0] == 0 {
+                return None;
+            }
+            let len = bytes.iter().fold(0usize, |acc, &b| (acc << 8) | b as usize);
+            if len < 0x80 {
+                return None;
+            }
+            (len, rest)
+        };
+        if rest.len() < len {
+            return None;
+        }
+        let (content, rest) = rest.split_at(len);
+        self.data = rest;
+        Some(content)
+    }
+
+    /// 读取 SEQUENCE，返回读取其内容的 reader
+    pub fn read_sequence(&mut self) -> Option<DerReader<'a>> {
+        self.read(TAG_SEQUENCE).map(DerReader::new)
+    }
+
+    /// 读取非负 INTEGER，拒绝负数和非最短编码
+    pub fn read_integer(&mut self) -> Option<BigUint> {
+        let content = self.read(TAG_INTEGER)?;
+        match content {
+            [] => None,
+            [b, ..] if b & 0x80 != 0 => None,
+            [0, next, ..] if next & 0x80 == 0 => None,
+            _ => Some(BigUint::from_bytes_be(content)),
+        }
+    }
+}
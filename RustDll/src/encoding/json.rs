@@ -0,0 +1,367 @@
+//! 最小化的 JSON 解析与序列化 (RFC 8259)，供 JWK 等格式使用
+//!
+//! 数字保留原始文本，不做数值转换；对象成员保持原有顺序，成员名重复时解析失败。
+
+use std::fmt;
+
+// 对象和数组的嵌套层数上限，防止恶意输入耗尽栈空间
+const MAX_DEPTH: usize = 64;
+
+/// JSON 值
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    /// 数字的原始文本
+    Number(String),
+    String(String),
+    Array(Vec<JsonValue>),
+    /// 对象成员，按出现顺序排列
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    /// 解析完整的 JSON 文本，前后允许空白
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut parser = Parser { bytes: text.as_bytes(), pos: 0 };
+        let value = parser.value(0)?;
+        parser.skip_whitespace();
+        if parser.pos != parser.bytes.len() {
+            return None;
+        }
+        Some(value)
+    }
+
+    /// 对象成员查找，非对象或不存在时返回 None
+    pub fn get(&self, name: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(members) => members.iter().find(|(key, _)| key == name).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[JsonValue]> {
+        match self {
+            JsonValue::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for JsonValue {
+    /// 紧凑格式输出，不含多余空白
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonValue::Null => write!(f, "null"),
+            JsonValue::Bool(b) => write!(f, "{}", b),
+            JsonValue::Number(n) => write!(f, "{}", n),
+            JsonValue::String(s) => write_string(f, s),
+            JsonValue::Array(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+            JsonValue::Object(members) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in members.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while matches!(self.bytes.get(self.pos), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, b: u8) -> Option<()> {
+        if self.peek()? != b {
+            return None;
+        }
+        self.pos += 1;
+        Some(())
+    }
+
+    fn literal(&mut self, text: &[u8], value: JsonValue) -> Option<JsonValue> {
+        if !self.bytes[self.pos..].starts_with(text) {
+            return None;
+        }
+        self.pos += text.len();
+        Some(value)
+    }
+
+    // depth 为外层容器的层数，第 MAX_DEPTH 层以内的对象和数组才被接受
+    fn value(&mut self, depth: usize) -> Option<JsonValue> {
+        self.skip_whitespace();
+        match self.peek()? {
+            b'{' | b'[' if depth >= MAX_DEPTH => None,
+            b'{' => self.object(depth),
+            b'[' => self.array(depth),
+            b'"' => self.string().map(JsonValue::String),
+            b't' => self.literal(b"true", JsonValue::Bool(true)),
+            b'f' => self.literal(b"false", JsonValue::Bool(false)),
+            b'n' => self.literal(b"null", JsonValue::Null),
+            _ => self.number(),
+        }
+    }
+
+    fn object(&mut self, depth: usize) -> Option<JsonValue> {
+        self.expect(b'{')?;
+        let mut members: Vec<(String, JsonValue)> = Vec::new();
+        self.skip_whitespace();
+        if self.peek()? == b'}' {
+            self.pos += 1;
+            return Some(JsonValue::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            if members.iter().any(|(k, _)| *k == key) {
+                return None;
+            }
+            self.skip_whitespace();
+            self.expect(b':')?;
+            let value = self.value(depth + 1)?;
+            members.push((key, value));
+            self.skip_whitespace();
+            match self.peek()? {
+                b',' => self.pos += 1,
+                b'}' => {
+                    self.pos += 1;
+                    return Some(JsonValue::Object(members));
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> Option<JsonValue> {
+        self.expect(b'[')?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek()? == b']' {
+            self.pos += 1;
+            return Some(JsonValue::Array(items));
+        }
+        loop {
+            items.push(self.value(depth + 1)?);
+            self.skip_whitespace();
+            match self.peek()? {
+                b',' => self.pos += 1,
+                b']' => {
+                    self.pos += 1;
+                    return Some(JsonValue::Array(items));
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    fn string(&mut self) -> Option<String> {
+        self.expect(b'"')?;
+        let mut out = String::new();
+        loop {
+            let start = self.pos;
+            while !matches!(self.peek()?, b'"' | b'\\' | 0x00..=0x1f) {
+                self.pos += 1;
+            }
+            // 输入来自 &str，在 ASCII 字符处切分仍是合法的 UTF-8
+            out.push_str(std::str::from_utf8(&self.bytes[start..self.pos]).ok()?);
+            match self.peek()? {
+                b'"' => {
+                    self.pos += 1;
+                    return Some(out);
+                }
+                b'\\' => {
+                    self.pos += 1;
+                    let escaped = self.peek()?;
+                    self.pos += 1;
+                    match escaped {
+                        b'"' => out.push('"'),
+                        b'\\' => out.push('\\'),
+                        b'/' => out.push('/'),
+                        b'b' => out.push('\u{8}'),
+                        b'f' => out.push('\u{c}'),
+                        b'n' => out.push('\n'),
+                        b'r' => out.push('\r'),
+                        b't' => out.push('\t'),
+                        b'u' => out.push(self.unicode_escape()?),
+                        _ => return None,
+                    }
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    // \uXXXX，高代理项后必须紧跟低代理项
+    fn unicode_escape(&mut self) -> Option<char> {
+        let high = self.hex4()?;
+        if !(0xd800..0xdc00).contains(&high) {
+            return char::from_u32(high);
+        }
+        self.expect(b'\\')?;
+        self.expect(b'u')?;
+        let low = self.hex4()?;
+        if !(0xdc00..0xe000).contains(&low) {
+            return None;
+        }
+        char::from_u32(0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00))
+    }
+
+    fn hex4(&mut self) -> Option<u32> {
+        let digits = self.bytes.get(self.pos..self.pos + 4)?;
+        let text = std::str::from_utf8(digits).ok()?;
+        if !text.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        self.pos += 4;
+        u32::from_str_radix(text, 16).ok()
+    }
+
+    // -?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?
+    fn number(&mut self) -> Option<JsonValue> {
+        let start = self.pos;
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+        match self.peek()? {
+            b'0' => self.pos += 1,
+            b'1'..=b'9' => self.digits(),
+            _ => return None,
+        }
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            self.require_digits()?;
+        }
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            self.pos += 1;
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            self.require_digits()?;
+        }
+        let text = std::str::from_utf8(&self.bytes[start..self.pos]).ok()?;
+        Some(JsonValue::Number(text.to_string()))
+    }
+
+    fn digits(&mut self) {
+        while matches!(self.peek(), Some(b'0'..=b'9')) {
+            self.pos += 1;
+        }
+    }
+
+    fn require_digits(&mut self) -> Option<()> {
+        let start = self.pos;
+        self.digits();
+        if self.pos == start { None } else { Some(()) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nested(depth: usize) -> String {
+        format!("{}{}", "[".repeat(depth), "]".repeat(depth))
+    }
+
+    #[test]
+    fn parses_and_serializes_compactly() {
+        let text = " {\"a\" : [1, -0.5e+3, true, false, null], \"b\":\"x\\\"\\u00e9\\ud83d\\ude00\\n\"} ";
+        let value = JsonValue::parse(text).unwrap();
+        assert_eq!(value.get("b").and_then(JsonValue::as_str), Some("x\"\u{e9}\u{1f600}\n"));
+        assert_eq!(value.get("a").and_then(JsonValue::as_array).map(<[_]>::len), Some(5));
+        assert_eq!(value.to_string(), "{\"a\":[1,-0.5e+3,true,false,null],\"b\":\"x\\\"\u{e9}\u{1f600}\\n\"}");
+        assert_eq!(JsonValue::parse(&value.to_string()), Some(value));
+    }
+
+    #[test]
+    fn rejects_duplicate_members() {
+        assert!(JsonValue::parse(r#"{"kty":"RSA","kty":"RSA"}"#).is_none());
+        assert!(JsonValue::parse(r#"{"a":{"b":1,"b":2}}"#).is_none());
+        // 转义后相同的成员名也算重复
+        assert!(JsonValue::parse(r#"{"kty":1,"k\u0074y":2}"#).is_none());
+        assert!(JsonValue::parse(r#"[{"a":1},{"a":2}]"#).is_some());
+    }
+
+    #[test]
+    fn limits_nesting_depth() {
+        assert!(JsonValue::parse(&nested(MAX_DEPTH)).is_some());
+        assert!(JsonValue::parse(&nested(MAX_DEPTH + 1)).is_none());
+        assert!(JsonValue::parse(&format!("{}1{}", "[".repeat(MAX_DEPTH), "]".repeat(MAX_DEPTH))).is_some());
+        let objects = format!("{}1{}", "{\"a\":".repeat(MAX_DEPTH + 1), "}".repeat(MAX_DEPTH + 1));
+        assert!(JsonValue::parse(&objects).is_none());
+        // 远超上限的输入不能耗尽栈空间
+        assert!(JsonValue::parse(&"[".repeat(100_000)).is_none());
+    }
+
+    #[test]
+    fn rejects_lone_surrogates() {
+        for text in [r#""\ud800""#, r#""\udc00""#, r#""\ud800x""#, r#""\ud800\u0041""#, r#""\ud800\ud800""#] {
+            assert!(JsonValue::parse(text).is_none(), "{}", text);
+        }
+        assert_eq!(JsonValue::parse(r#""\ud834\udd1e""#), Some(JsonValue::String("\u{1d11e}".to_string())));
+    }
+
+    #[test]
+    fn rejects_trailing_data() {
+        for text in ["{} {}", "{}x", "[1],", "1 2", "\"a\"\"b\"", "null\u{0}", "{}]"] {
+            assert!(JsonValue::parse(text).is_none(), "{:?}", text);
+        }
+        assert!(JsonValue::parse("{} \r\n\t").is_some());
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        for text in ["", "{", r#"{"a"}"#, r#"{"a":1,}"#, "[1,]", "01", "1.", "-", "+1", "tru", "\"\u{1}\"", r#""\x""#, r#"{a:1}"#] {
+            assert!(JsonValue::parse(text).is_none(), "{:?}", text);
+        }
+    }
+}
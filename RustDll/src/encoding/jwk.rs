@@ -0,0 +1,100 @@
+//! JSON Web Key (RFC 7517) 的通用部分：成员读写与 Base64urlUInt 编码 (RFC 7518 §2)
+
+use super::base64::{self, Base64Variant};
+use super::json::JsonValue;
+use num_bigint::BigUint;
+
+/// JWK 对象，成员按写入顺序输出
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Jwk {
+    members: Vec<(String, JsonValue)>,
+}
+
+impl Jwk {
+    /// 创建只含 "kty" 成员的 JWK
+    pub fn new(kty: &str) -> Self {
+        Jwk { members: vec![("kty".to_string(), JsonValue::String(kty.to_string()))] }
+    }
+
+    /// 解析 JWK 文本，要求是 JSON 对象且 "kty" 为字符串；未知成员被保留但不解释
+    pub fn parse(text: &str) -> Option<Self> {
+        match JsonValue::parse(text)? {
+            JsonValue::Object(members) => {
+                let jwk = Jwk { members };
+                jwk.get("kty")?.as_str()?;
+                Some(jwk)
+            }
+            _ => None,
+        }
+    }
+
+    /// 密钥类型
+    pub fn kty(&self) -> &str {
+        self.get("kty").and_then(JsonValue::as_str).unwrap_or_default()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&JsonValue> {
+        self.members.iter().find(|(key, _)| key == name).map(|(_, value)| value)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// 追加成员，同名成员已存在时覆盖
+    pub fn set(&mut self, name: &str, value: JsonValue) {
+        match self.members.iter_mut().find(|(key, _)| key == name) {
+            Some((_, existing)) => *existing = value,
+            None => self.members.push((name.to_string(), value)),
+        }
+    }
+
+    /// 追加 Base64urlUInt 编码的整数成员
+    pub fn set_uint(&mut self, name: &str, value: &BigUint) {
+        self.set(name, JsonValue::String(encode_uint(value)));
+    }
+
+    /// 读取 Base64urlUInt 编码的整数成员，缺失或编码错误时返回 None
+    pub fn uint(&self, name: &str) -> Option<BigUint> {
+        decode_uint(self.get(name)?.as_str()?)
+    }
+
+    /// 读取数组成员，缺失或类型不符时返回 None
+    pub fn array(&self, name: &str) -> Option<&[JsonValue]> {
+        self.get(name)?.as_array()
+    }
+
+    /// 紧凑的 JSON 文本
+    pub fn to_json(&self) -> String {
+        JsonValue::Object(self.members.clone()).to_string()
+    }
+}
+
+/// 把非负整数编码为 Base64urlUInt：最少字节数的大端表示，0 编码为一个零字节
+pub fn encode_uint(value: &BigUint) -> String {
+    base64::encode(&value.to_bytes_be(), Base64Variant::UrlSafeNoPad)
+}
+
+/// 解码 Base64urlUInt；兼容部分实现输出的前导零字节
+pub fn decode_uint(text: &str) -> Option<BigUint> {
+    let bytes = base64::decode(text, Base64Variant::UrlSafeNoPad)?;
+    if bytes.is_empty() {
+        return None;
+    }
+    Some(BigUint::from_bytes_be(&bytes))
+}
+
+/// 读取 JSON 对象中 Base64urlUInt 编码的整数成员
+pub fn object_uint(object: &JsonValue, name: &str) -> Option<BigUint> {
+    decode_uint(object.get(name)?.as_str()?)
+}
+
+/// 由成员列表构造 JSON 对象（例如 RSA 多素数密钥的 "oth" 数组元素）
+pub fn uint_object(members: &[(&str, &BigUint)]) -> JsonValue {
+    JsonValue::Object(
+        members
+            .iter()
+            .map(|(name, value)| (name.to_string(), JsonValue::String(encode_uint(value))))
+            .collect(),
+    )
+}
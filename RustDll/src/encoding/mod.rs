@@ -0,0 +1,8 @@
+//! 编码工具模块
+
+pub mod base64;
+pub mod der;
+pub mod json;
+pub mod jwk;
+pub mod pem;
+pub mod pkcs8;
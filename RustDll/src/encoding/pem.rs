@@ -0,0 +1,50 @@
+//! PEM 封装 (RFC 7468)：`-----BEGIN 标签-----`、每行 64 字符的 Base64、`-----END 标签-----`
+
+use super::base64::{self, Base64Variant};
+
+// 每行 Base64 字符数
+const LINE_LEN: usize = 64;
+
+/// 把 DER 数据封装为 PEM 文本，以换行结尾
+pub fn encode(label: &str, der: &[u8]) -> String {
+    let body = base64::encode(der, Base64Variant::Standard);
+    let mut out = format!("-----BEGIN {}-----\n", label);
+    for line in body.as_bytes().chunks(LINE_LEN) {
+        // Base64 输出只含 ASCII
+        out.push_str(std::str::from_utf8(line).expect("base64 is ASCII"));
+        out.push('\n');
+    }
+    out.push_str(&format!("-----END {}-----\n", label));
+    out
+}
+
+/// 解析文本中的第一个 PEM 块，返回标签和 DER 数据
+///
+/// 块前后的其他文本会被忽略；BEGIN 与 END 的标签必须一致，带头部字段（如旧式加密 PEM）的块视为无效。
+pub fn decode(text: &str) -> Option<(String, Vec<u8>)> {
+    let start = text.find("-----BEGIN ")?;
+    let rest = &text[start + "-----BEGIN ".len()..];
+    let label_end = rest.find("-----")?;
+    let label = &rest[..label_end];
+    let rest = &rest[label_end + "-----".len()..];
+
+    let end_marker = format!("-----END {}-----", label);
+    let body_end = rest.find(&end_marker)?;
+    let body: String = rest[..body_end].chars().filter(|c| !c.is_ascii_whitespace()).collect();
+
+    let der = base64::decode(&body, Base64Variant::Standard)?;
+    Some((label.to_string(), der))
+}
+
+/// 解析 PEM 块并要求标签为 label
+pub fn decode_with_label(text: &str, label: &str) -> Option<Vec<u8>> {
+    match decode(text)? {
+        (actual, der) if actual == label => Some(der),
+        _ => None,
+    }
+}
+
+/// 文本是否看起来是 PEM（包含 BEGIN 行）
+pub fn is_pem(data: &[u8]) -> bool {
+    std::str::from_utf8(data).is_ok_and(|text| text.contains("-----BEGIN "))
+}
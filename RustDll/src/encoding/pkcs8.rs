@@ -0,0 +1,309 @@
+//! 加密的 PKCS#8 私钥 (RFC 5958 EncryptedPrivateKeyInfo)，使用 PBES2 (RFC 8018)
+//!
+//! ```text
+//! EncryptedPrivateKeyInfo ::= SEQUENCE { encryptionAlgorithm AlgorithmIdentifier, encryptedData OCTET STRING }
+//! PBES2-params ::= SEQUENCE { keyDerivationFunc AlgorithmIdentifier, encryptionScheme AlgorithmIdentifier }
+//! PBKDF2-params ::= SEQUENCE { salt OCTET STRING, iterationCount INTEGER, keyLength INTEGER OPTIONAL, prf AlgorithmIdentifier }
+//! ```
+//!
+//! 密钥派生固定为 PBKDF2，PRF 为 HMAC-SHA2 / SHA3；加密为 AES-CBC（参数为 IV）或 AES-GCM
+//! （参数为 RFC 5084 的 GCMParameters）。AES-CBC 与 `openssl pkcs8 -topk8` 互通，
+//! OpenSSL 不支持在 PBES2 中使用 AEAD，GCM 只能由本库或其他支持 RFC 5084 的实现读取。
+
+use super::der::{
+    encode_integer, encode_sequence, encode_tlv, DerReader, TAG_INTEGER, TAG_NULL, TAG_OCTET_STRING, TAG_OID,
+};
+use crate::aes::cipher::AesCipher;
+use crate::aes::gcm::{AesGcm, NONCE_LEN, TAG_LEN};
+use crate::hash::HashAlgorithm;
+use crate::kdf::password::PBKDF2_DEFAULT_ITERATIONS;
+use crate::kdf::pbkdf2;
+use num_bigint::BigUint;
+use rand::rngs::OsRng;
+use rand::RngCore;
+
+/// PEM 标签
+pub const ENCRYPTED_PRIVATE_KEY_LABEL: &str = "ENCRYPTED PRIVATE KEY";
+
+/// PBKDF2 迭代次数上限；导入时拒绝更大的值，避免恶意文件让解密耗费过长时间
+pub const PBKDF2_MAX_ITERATIONS: u32 = 10_000_000;
+
+// 随机盐长度（字节）
+const SALT_LEN: usize = 16;
+
+// 1.2.840.113549.1.5.13 / 1.2.840.113549.1.5.12
+const PBES2_OID: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x05, 0x0d];
+const PBKDF2_OID: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x05, 0x0c];
+
+/// PBES2 加密算法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pbes2Cipher {
+    Aes128Cbc,
+    Aes192Cbc,
+    Aes256Cbc,
+    Aes128Gcm,
+    Aes192Gcm,
+    Aes256Gcm,
+}
+
+impl Pbes2Cipher {
+    /// 由 FFI 的算法编号（CRYPTO_PBES2_*）得到加密算法
+    pub fn from_id(cipher: i32) -> Option<Self> {
+        match cipher {
+            1 => Some(Pbes2Cipher::Aes128Cbc),
+            2 => Some(Pbes2Cipher::Aes192Cbc),
+            3 => Some(Pbes2Cipher::Aes256Cbc),
+            4 => Some(Pbes2Cipher::Aes128Gcm),
+            5 => Some(Pbes2Cipher::Aes192Gcm),
+            6 => Some(Pbes2Cipher::Aes256Gcm),
+            _ => None,
+        }
+    }
+
+    pub fn key_len(&self) -> usize {
+        match self {
+            Pbes2Cipher::Aes128Cbc | Pbes2Cipher::Aes128Gcm => 16,
+            Pbes2Cipher::Aes192Cbc | Pbes2Cipher::Aes192Gcm => 24,
+            Pbes2Cipher::Aes256Cbc | Pbes2Cipher::Aes256Gcm => 32,
+        }
+    }
+
+    fn is_gcm(&self) -> bool {
+        matches!(self, Pbes2Cipher::Aes128Gcm | Pbes2Cipher::Aes192Gcm | Pbes2Cipher::Aes256Gcm)
+    }
+
+    // NIST 算法弧 2.16.840.1.101.3.4.1 下的编号
+    fn oid(&self) -> [u8; 9] {
+        let arc = match self {
+            Pbes2Cipher::Aes128Cbc => 2,
+            Pbes2Cipher::Aes192Cbc => 22,
+            Pbes2Cipher::Aes256Cbc => 42,
+            Pbes2Cipher::Aes128Gcm => 6,
+            Pbes2Cipher::Aes192Gcm => 26,
+            Pbes2Cipher::Aes256Gcm => 46,
+        };
+        [0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x01, arc]
+    }
+
+    fn from_oid(oid: &[u8]) -> Option<Self> {
+        [
+            Pbes2Cipher::Aes128Cbc,
+            Pbes2Cipher::Aes192Cbc,
+            Pbes2Cipher::Aes256Cbc,
+            Pbes2Cipher::Aes128Gcm,
+            Pbes2Cipher::Aes192Gcm,
+            Pbes2Cipher::Aes256Gcm,
+        ]
+        .into_iter()
+        .find(|cipher| cipher.oid() == oid)
+    }
+}
+
+/// PBES2 参数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pbes2Params {
+    pub cipher: Pbes2Cipher,
+    /// PBKDF2 使用的 HMAC 摘要算法
+    pub prf: HashAlgorithm,
+    pub iterations: u32,
+}
+
+impl Default for Pbes2Params {
+    /// AES-256-CBC + PBKDF2-HMAC-SHA256，迭代次数与口令哈希的默认值相同
+    fn default() -> Self {
+        Pbes2Params {
+            cipher: Pbes2Cipher::Aes256Cbc,
+            prf: HashAlgorithm::Sha256,
+            iterations: PBKDF2_DEFAULT_ITERATIONS,
+        }
+    }
+}
+
+// HMAC 算法标识：SHA-2 在 1.2.840.113549.2 下，SHA-3 在 2.16.840.1.101.3.4.2 下
+fn prf_oid(algorithm: HashAlgorithm) -> Option<Vec<u8>> {
+    let rsadsi = |arc: u8| vec![0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x02, arc];
+    let nist = |arc: u8| vec![0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, arc];
+    match algorithm {
+        HashAlgorithm::Sha224 => Some(rsadsi(8)),
+        HashAlgorithm::Sha256 => Some(rsadsi(9)),
+        HashAlgorithm::Sha384 => Some(rsadsi(10)),
+        HashAlgorithm::Sha512 => Some(rsadsi(11)),
+        HashAlgorithm::Sha512_224 => Some(rsadsi(12)),
+        HashAlgorithm::Sha512_256 => Some(rsadsi(13)),
+        HashAlgorithm::Sha3_224 => Some(nist(13)),
+        HashAlgorithm::Sha3_256 => Some(nist(14)),
+        HashAlgorithm::Sha3_384 => Some(nist(15)),
+        HashAlgorithm::Sha3_512 => Some(nist(16)),
+        // HMAC-SHA1 只出现在旧的加密私钥中，这里不支持
+        HashAlgorithm::Shake128 | HashAlgorithm::Shake256 | HashAlgorithm::Sha1 => None,
+    }
+}
+
+fn prf_from_oid(oid: &[u8]) -> Option<HashAlgorithm> {
+    [
+        HashAlgorithm::Sha224,
+        HashAlgorithm::Sha256,
+        HashAlgorithm::Sha384,
+        HashAlgorithm::Sha512,
+        HashAlgorithm::Sha512_224,
+        HashAlgorithm::Sha512_256,
+        HashAlgorithm::Sha3_224,
+        HashAlgorithm::Sha3_256,
+        HashAlgorithm::Sha3_384,
+        HashAlgorithm::Sha3_512,
+    ]
+    .into_iter()
+    .find(|&algorithm| prf_oid(algorithm).as_deref() == Some(oid))
+}
+
+/// 用口令加密 PrivateKeyInfo（DER），返回 EncryptedPrivateKeyInfo（DER）
+///
+/// PRF 为 SHAKE 或 SHA-1、迭代次数为 0 或超过 `PBKDF2_MAX_ITERATIONS` 时返回 None。
+pub fn encrypt_private_key_info(private_key_info: &[u8], password: &[u8], params: &Pbes2Params) -> Option<Vec<u8>> {
+    if params.iterations > PBKDF2_MAX_ITERATIONS {
+        return None;
+    }
+    let prf = prf_oid(params.prf)?;
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let key = pbkdf2(params.prf, password, &salt, params.iterations, params.cipher.key_len()).ok()?;
+
+    let (scheme_params, encrypted) = if params.cipher.is_gcm() {
+        let mut nonce = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce);
+        let gcm = AesGcm::new(&key)?;
+        let gcm_params = encode_sequence(&[
+            encode_tlv(TAG_OCTET_STRING, &nonce),
+            encode_integer(&BigUint::from(TAG_LEN)),
+        ]);
+        (gcm_params, gcm.encrypt(&nonce, &[], private_key_info))
+    } else {
+        let mut iv = [0u8; 16];
+        OsRng.fill_bytes(&mut iv);
+        let cipher = AesCipher::new(&key)?;
+        (encode_tlv(TAG_OCTET_STRING, &iv), cipher.encrypt_cbc(private_key_info, &iv))
+    };
+
+    let kdf = encode_sequence(&[
+        encode_tlv(TAG_OID, PBKDF2_OID),
+        encode_sequence(&[
+            encode_tlv(TAG_OCTET_STRING, &salt),
+            encode_integer(&BigUint::from(params.iterations)),
+            encode_sequence(&[encode_tlv(TAG_OID, &prf), encode_tlv(TAG_NULL, &[])]),
+        ]),
+    ]);
+    let scheme = encode_sequence(&[encode_tlv(TAG_OID, &params.cipher.oid()), scheme_params]);
+    let algorithm = encode_sequence(&[encode_tlv(TAG_OID, PBES2_OID), encode_sequence(&[kdf, scheme])]);
+    Some(encode_sequence(&[algorithm, encode_tlv(TAG_OCTET_STRING, &encrypted)]))
+}
+
+/// 用口令解密 EncryptedPrivateKeyInfo（DER），返回 PrivateKeyInfo（DER）
+///
+/// 格式错误、算法不支持、迭代次数超过 `PBKDF2_MAX_ITERATIONS` 或口令错误都返回 None。
+pub fn decrypt_private_key_info(encrypted_private_key_info: &[u8], password: &[u8]) -> Option<Vec<u8>> {
+    let mut outer = DerReader::new(encrypted_private_key_info);
+    let mut seq = outer.read_sequence()?;
+    let mut algorithm = seq.read_sequence()?;
+    let encrypted = seq.read(TAG_OCTET_STRING)?;
+    if !seq.is_empty() || !outer.is_empty() || algorithm.read(TAG_OID)? != PBES2_OID {
+        return None;
+    }
+    let mut pbes2 = algorithm.read_sequence()?;
+    let mut kdf = pbes2.read_sequence()?;
+    let mut scheme = pbes2.read_sequence()?;
+    if !pbes2.is_empty() || !algorithm.is_empty() {
+        return None;
+    }
+
+    // PBKDF2-params；省略 prf 时默认为 HMAC-SHA1，本库不支持
+    if kdf.read(TAG_OID)? != PBKDF2_OID {
+        return None;
+    }
+    let mut kdf_params = kdf.read_sequence()?;
+    let salt = kdf_params.read(TAG_OCTET_STRING)?;
+    let iterations = u32::try_from(kdf_params.read_integer()?).ok()?;
+    if iterations > PBKDF2_MAX_ITERATIONS {
+        return None;
+    }
+    let key_len = match kdf_params.peek_tag() {
+        Some(TAG_INTEGER) => Some(kdf_params.read_integer()?),
+        _ => None,
+    };
+    let mut prf = kdf_params.read_sequence()?;
+    let prf = prf_from_oid(prf.read(TAG_OID)?)?;
+    if !kdf_params.is_empty() {
+        return None;
+    }
+
+    let cipher = Pbes2Cipher::from_oid(scheme.read(TAG_OID)?)?;
+    if key_len.is_some_and(|len| len != BigUint::from(cipher.key_len())) {
+        return None;
+    }
+    let key = pbkdf2(prf, password, salt, iterations, cipher.key_len()).ok()?;
+
+    if cipher.is_gcm() {
+        let mut gcm_params = scheme.read_sequence()?;
+        let nonce: [u8; NONCE_LEN] = gcm_params.read(TAG_OCTET_STRING)?.try_into().ok()?;
+        // aes-ICVlen 默认为 12
+        let tag_len = match gcm_params.peek_tag() {
+            Some(_) => usize::try_from(gcm_params.read_integer()?).ok()?,
+            None => 12,
+        };
+        AesGcm::new(&key)?.decrypt_with_tag_len(&nonce, &[], encrypted, tag_len)
+    } else {
+        let iv: [u8; 16] = scheme.read(TAG_OCTET_STRING)?.try_into().ok()?;
+        AesCipher::new(&key)?.decrypt_cbc(encrypted, &iv)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PASSWORD: &[u8] = b"password";
+    const PRIVATE_KEY_INFO: &[u8] = b"not really a PrivateKeyInfo, but any bytes will do";
+
+    // 与 encrypt_private_key_info 结构相同，但盐、IV 和迭代次数由调用方指定
+    fn encrypted_with_iterations(iterations: &BigUint, encrypted: &[u8]) -> Vec<u8> {
+        let kdf = encode_sequence(&[
+            encode_tlv(TAG_OID, PBKDF2_OID),
+            encode_sequence(&[
+                encode_tlv(TAG_OCTET_STRING, &[0x5a; SALT_LEN]),
+                encode_integer(iterations),
+                encode_sequence(&[encode_tlv(TAG_OID, &prf_oid(HashAlgorithm::Sha256).unwrap()), encode_tlv(TAG_NULL, &[])]),
+            ]),
+        ]);
+        let scheme = encode_sequence(&[encode_tlv(TAG_OID, &Pbes2Cipher::Aes128Cbc.oid()), encode_tlv(TAG_OCTET_STRING, &[0xa5; 16])]);
+        let algorithm = encode_sequence(&[encode_tlv(TAG_OID, PBES2_OID), encode_sequence(&[kdf, scheme])]);
+        encode_sequence(&[algorithm, encode_tlv(TAG_OCTET_STRING, encrypted)])
+    }
+
+    #[test]
+    fn round_trip_all_ciphers() {
+        for id in 1..=6 {
+            let params = Pbes2Params { cipher: Pbes2Cipher::from_id(id).unwrap(), iterations: 1000, ..Default::default() };
+            let encrypted = encrypt_private_key_info(PRIVATE_KEY_INFO, PASSWORD, &params).unwrap();
+            assert_eq!(decrypt_private_key_info(&encrypted, PASSWORD).as_deref(), Some(PRIVATE_KEY_INFO));
+            assert_eq!(decrypt_private_key_info(&encrypted, b"wrong password"), None);
+        }
+    }
+
+    #[test]
+    fn iteration_count_is_capped() {
+        let key = pbkdf2(HashAlgorithm::Sha256, PASSWORD, &[0x5a; SALT_LEN], 1000, 16).unwrap();
+        let encrypted = AesCipher::new(&key).unwrap().encrypt_cbc(PRIVATE_KEY_INFO, &[0xa5; 16]);
+        let accepted = encrypted_with_iterations(&BigUint::from(1000u32), &encrypted);
+        assert_eq!(decrypt_private_key_info(&accepted, PASSWORD).as_deref(), Some(PRIVATE_KEY_INFO));
+
+        // 超过上限时在派生密钥之前就拒绝，不会执行这么多次迭代
+        for iterations in [PBKDF2_MAX_ITERATIONS as u64 + 1, u32::MAX as u64, 1 << 40] {
+            let rejected = encrypted_with_iterations(&BigUint::from(iterations), &encrypted);
+            assert_eq!(decrypt_private_key_info(&rejected, PASSWORD), None);
+        }
+
+        let params = Pbes2Params { iterations: PBKDF2_MAX_ITERATIONS + 1, ..Default::default() };
+        assert_eq!(encrypt_private_key_info(PRIVATE_KEY_INFO, PASSWORD, &params), None);
+        let params = Pbes2Params { iterations: 0, ..Default::default() };
+        assert_eq!(encrypt_private_key_info(PRIVATE_KEY_INFO, PASSWORD, &params), None);
+    }
+}
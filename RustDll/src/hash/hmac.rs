@@ -0,0 +1,223 @@
+//! HMAC (RFC 2104)，可使用本模块中任意定长摘要算法（SHA-2 / SHA-3）
+
+use super::{HashAlgorithm, Hasher};
+
+/// `verify_truncated` 允许的最短截断标签长度（RFC 2104 第 5 节建议不少于 80 比特）
+pub const MIN_TAG_LEN: usize = 10;
+
+/// 流式 HMAC 上下文
+#[derive(Clone)]
+pub struct Hmac {
+    inner: Hasher,
+    outer: Hasher,
+}
+
+impl Hmac {
+    /// 创建 HMAC 上下文；SHAKE 等可扩展输出函数不支持 HMAC，返回 None
+    pub fn new(algorithm: HashAlgorithm, key: &[u8]) -> Option<Self> {
+        if algorithm.is_xof() {
+            return None;
+        }
+
+        // 密钥长于分组时先做一次哈希，然后补零到分组长度
+        let block_len = algorithm.block_len();
+        let mut block_key = if key.len() > block_len {
+            algorithm.digest(key)
+        } else {
+            key.to_vec()
+        };
+        block_key.resize(block_len, 0);
+
+        let ipad: Vec<u8> = block_key.iter().map(|b| b ^ 0x36).collect();
+        let opad: Vec<u8> = block_key.iter().map(|b| b ^ 0x5c).collect();
+
+        let mut inner = algorithm.hasher();
+        inner.update(&ipad);
+        let mut outer = algorithm.hasher();
+        outer.update(&opad);
+
+        Some(Hmac { inner, outer })
+    }
+
+    /// 一次性计算 HMAC 标签
+    pub fn mac(algorithm: HashAlgorithm, key: &[u8], data: &[u8]) -> Option<Vec<u8>> {
+        let mut hmac = Self::new(algorithm, key)?;
+        hmac.update(data);
+        Some(hmac.finalize())
+    }
+
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.inner.algorithm()
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    pub fn finalize(self) -> Vec<u8> {
+        let inner_hash = self.inner.finalize();
+        let mut outer = self.outer;
+        outer.update(&inner_hash);
+        outer.finalize()
+    }
+
+    /// 以常数时间比较标签，标签必须是完整长度
+    pub fn verify(self, tag: &[u8]) -> bool {
+        let expected = self.finalize();
+        constant_time_eq(&expected, tag)
+    }
+
+    /// 以常数时间比较截断到 tag_len 字节的标签
+    ///
+    /// tag_len 由调用方事先约定，不能取自收到的标签；它不能少于 MIN_TAG_LEN 或超过摘要长度，
+    /// tag 的长度也必须恰好等于 tag_len，否则返回 false。
+    pub fn verify_truncated(self, tag: &[u8], tag_len: usize) -> bool {
+        let expected = self.finalize();
+        if tag_len < MIN_TAG_LEN || tag_len > expected.len() || tag.len() != tag_len {
+            return false;
+        }
+        constant_time_eq(&expected[..tag_len], tag)
+    }
+}
+
+/// 常数时间比较两个字节串，运行时间只和长度有关
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let diff = a.iter().zip(b.iter()).fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    std::hint::black_box(diff) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALGORITHMS: [HashAlgorithm; 4] =
+        [HashAlgorithm::Sha224, HashAlgorithm::Sha256, HashAlgorithm::Sha384, HashAlgorithm::Sha512];
+
+    const LONG_DATA: &[u8] = b"This is a test using a larger than block-size key and a larger than block-size data. \
+The key needs to be hashed before being used by the HMAC algorithm.";
+
+    // RFC 4231 第 4 节的测试用例，标签依次对应 HMAC-SHA-224/256/384/512；用例 5 截断为 128 比特
+    fn rfc4231_cases() -> Vec<(Vec<u8>, Vec<u8>, [&'static str; 4])> {
+        vec![
+            // 测试用例 1
+            (vec![0x0b; 20], b"Hi There".to_vec(), [
+                "896fb1128abbdf196832107cd49df33f47b4b1169912ba4f53684b22",
+                "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+                "afd03944d84895626b0825f4ab46907f15f9dadbe4101ec682aa034c7cebc59cfaea9ea9076ede7f4af152e8b2fa9cb6",
+                concat!(
+                    "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4",
+                    "be9d914eeb61f1702e696c203a126854",
+                ),
+            ]),
+            // 测试用例 2
+            (b"Jefe".to_vec(), b"what do ya want for nothing?".to_vec(), [
+                "a30e01098bc6dbbf45690f3a7e9e6d0f8bbea2a39e6148008fd05e44",
+                "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+                "af45d2e376484031617f78d2b58a6b1b9c7ef464f5a01b47e42ec3736322445e8e2240ca5e69e2c78b3239ecfab21649",
+                concat!(
+                    "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fd",
+                    "caeab1a34d4a6b4b636e070a38bce737",
+                ),
+            ]),
+            // 测试用例 3
+            (vec![0xaa; 20], vec![0xdd; 50], [
+                "7fb3cb3588c6c1f6ffa9694d7d6ad2649365b0c1f65d69d1ec8333ea",
+                "773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe",
+                "88062608d3e6ad8a0aa2ace014c8a86f0aa635d947ac9febe83ef4e55966144b2a5ab39dc13814b94e3ab6e101a34f27",
+                concat!(
+                    "fa73b0089d56a284efb0f0756c890be9b1b5dbdd8ee81a3655f83e33b2279d39bf3e848279a722c806b485a47e67c807",
+                    "b946a337bee8942674278859e13292fb",
+                ),
+            ]),
+            // 测试用例 4
+            ((0x01..=0x19).collect(), vec![0xcd; 50], [
+                "6c11506874013cac6a2abc1bb382627cec6a90d86efc012de7afec5a",
+                "82558a389a443c0ea4cc819899f2083a85f0faa3e578f8077a2e3ff46729665b",
+                "3e8a69b7783c25851933ab6290af6ca77a9981480850009cc5577c6e1f573b4e6801dd23c4a7d679ccf8a386c674cffb",
+                concat!(
+                    "b0ba465637458c6990e5a8c5f61d4af7e576d97ff94b872de76f8050361ee3dba91ca5c11aa25eb4d679275cc5788063",
+                    "a5f19741120c4f2de2adebeb10a298dd",
+                ),
+            ]),
+            // 测试用例 5
+            (vec![0x0c; 20], b"Test With Truncation".to_vec(), [
+                "0e2aea68a90c8d37c988bcdb9fca6fa8",
+                "a3b6167473100ee06e0c796c2955552b",
+                "3abf34c3503b2a23a46efc619baef897",
+                "415fad6271580a531d4179bc891d87a6",
+            ]),
+            // 测试用例 6
+            (vec![0xaa; 131], b"Test Using Larger Than Block-Size Key - Hash Key First".to_vec(), [
+                "95e9a0db962095adaebe9b2d6f0dbce2d499f112f2d2b7273fa6870e",
+                "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+                "4ece084485813e9088d2c63a041bc5b44f9ef1012a2b588f3cd11f05033ac4c60c2ef6ab4030fe8296248df163f44952",
+                concat!(
+                    "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f3526b56d037e05f2598bd0fd2215d6a1e52",
+                    "95e64f73f63f0aec8b915a985d786598",
+                ),
+            ]),
+            // 测试用例 7
+            (vec![0xaa; 131], LONG_DATA.to_vec(), [
+                "3a854166ac5d9f023f54d517d0b39dbd946770db9c2b95c9f6f565d1",
+                "9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2",
+                "6617178e941f020d351e2f254e8fd32c602420feb0b8fb9adccebb82461e99c5a678cc31e799176d3860e6110c46523e",
+                concat!(
+                    "e37b6a775dc87dbaa4dfa9f96e5e3ffddebd71f8867289865df5a32d20cdc944b6022cac3c4982b10d5eeb55c3e4de15",
+                    "134676fb6de0446065c97440fa8c6a58",
+                ),
+            ]),
+        ]
+    }
+
+    #[test]
+    fn rfc4231_vectors() {
+        for (index, (key, data, tags)) in rfc4231_cases().into_iter().enumerate() {
+            for (algorithm, tag) in ALGORITHMS.into_iter().zip(tags) {
+                let tag = hex::decode(tag).unwrap();
+                let full = Hmac::mac(algorithm, &key, &data).unwrap();
+                assert_eq!(full[..tag.len()], tag, "case {} {:?}", index + 1, algorithm);
+
+                let mut hmac = Hmac::new(algorithm, &key).unwrap();
+                hmac.update(&data);
+                if tag.len() == full.len() {
+                    assert!(hmac.verify(&tag));
+                } else {
+                    assert!(hmac.verify_truncated(&tag, tag.len()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn verify_requires_full_length_tag() {
+        let (key, data, _) = &rfc4231_cases()[0];
+        let tag = Hmac::mac(HashAlgorithm::Sha256, key, data).unwrap();
+        let mut hmac = Hmac::new(HashAlgorithm::Sha256, key).unwrap();
+        hmac.update(data);
+
+        assert!(hmac.clone().verify(&tag));
+        assert!(!hmac.clone().verify(&tag[..16]));
+        assert!(!hmac.clone().verify(&tag[..1]));
+        assert!(!hmac.clone().verify(&[]));
+        let mut flipped = tag.clone();
+        flipped[31] ^= 1;
+        assert!(!hmac.verify(&flipped));
+    }
+
+    #[test]
+    fn verify_truncated_checks_agreed_length() {
+        let (key, data, _) = &rfc4231_cases()[4];
+        let mut hmac = Hmac::new(HashAlgorithm::Sha256, key).unwrap();
+        hmac.update(data);
+        let tag = hmac.clone().finalize();
+
+        assert!(hmac.clone().verify_truncated(&tag[..16], 16));
+        assert!(hmac.clone().verify_truncated(&tag, 32));
+        assert!(!hmac.clone().verify_truncated(&tag[..12], 16));
+        assert!(!hmac.clone().verify_truncated(&tag[..MIN_TAG_LEN - 1], MIN_TAG_LEN - 1));
+        assert!(!hmac.verify_truncated(&[tag.as_slice(), &[0]].concat(), 33));
+    }
+}
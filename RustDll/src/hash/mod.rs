@@ -0,0 +1,311 @@
+//! 哈希函数模块
+//!
+//! 原生实现 SHA-2 系列 (FIPS 180-4) 与 SHA-3 / SHAKE (FIPS 202)，另有仅供旧协议互通的 SHA-1；既可以通过 `HashFunction` 静态使用，
+//! 也可以通过 `HashAlgorithm` / `Hasher` 在运行时选择算法（FFI 使用后者）。
+
+pub mod hmac;
+pub mod sha1;
+pub mod sha256;
+pub mod sha3;
+pub mod sha512;
+
+pub use hmac::{constant_time_eq, Hmac};
+pub use sha1::Sha1;
+pub use sha256::{Sha224, Sha256};
+pub use sha3::{Sha3_224, Sha3_256, Sha3_384, Sha3_512, Shake128, Shake256};
+pub use sha512::{Sha384, Sha512, Sha512_224, Sha512_256};
+
+use std::fmt;
+
+/// 哈希函数的统一接口，支持流式 update / finalize
+pub trait HashFunction: Clone {
+    /// 摘要长度（字节）
+    const OUTPUT_LEN: usize;
+    /// 分组长度（字节），HMAC 等构造需要用到
+    const BLOCK_LEN: usize;
+
+    fn new() -> Self;
+
+    fn update(&mut self, data: &[u8]);
+
+    fn finalize(self) -> Vec<u8>;
+
+    /// 一次性计算摘要
+    fn digest(data: &[u8]) -> Vec<u8> {
+        let mut hasher = Self::new();
+        hasher.update(data);
+        hasher.finalize()
+    }
+}
+
+/// 可扩展输出函数 (XOF) 的接口，可以输出任意长度
+pub trait ExtendableOutput: HashFunction {
+    /// 挤出 `out.len()` 字节直接写入调用方的缓冲区，不做额外分配
+    fn finalize_xof_into(self, out: &mut [u8]);
+
+    fn finalize_xof(self, out_len: usize) -> Vec<u8>
+    where
+        Self: Sized,
+    {
+        let mut out = vec![0u8; out_len];
+        self.finalize_xof_into(&mut out);
+        out
+    }
+}
+
+/// 运行时可选择的哈希算法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+    Sha512_256,
+    Sha3_224,
+    Sha3_256,
+    Sha3_384,
+    Sha3_512,
+    Shake128,
+    Shake256,
+    /// 仅用于 OAEP、HMAC 等旧协议互通，签名会拒绝 SHA-1
+    Sha1,
+    Sha512_224,
+}
+
+impl HashAlgorithm {
+    /// 由 FFI 的算法编号（CRYPTO_HASH_*）得到算法
+    pub fn from_id(id: i32) -> Option<Self> {
+        match id {
+            1 => Some(HashAlgorithm::Sha224),
+            2 => Some(HashAlgorithm::Sha256),
+            3 => Some(HashAlgorithm::Sha384),
+            4 => Some(HashAlgorithm::Sha512),
+            5 => Some(HashAlgorithm::Sha512_256),
+            6 => Some(HashAlgorithm::Sha3_224),
+            7 => Some(HashAlgorithm::Sha3_256),
+            8 => Some(HashAlgorithm::Sha3_384),
+            9 => Some(HashAlgorithm::Sha3_512),
+            10 => Some(HashAlgorithm::Shake128),
+            11 => Some(HashAlgorithm::Shake256),
+            12 => Some(HashAlgorithm::Sha1),
+            13 => Some(HashAlgorithm::Sha512_224),
+            _ => None,
+        }
+    }
+
+    /// 是否为可扩展输出函数 (SHAKE)
+    pub fn is_xof(&self) -> bool {
+        matches!(self, HashAlgorithm::Shake128 | HashAlgorithm::Shake256)
+    }
+
+    /// 摘要长度（字节），SHAKE 为默认输出长度
+    pub fn output_len(&self) -> usize {
+        match self {
+            HashAlgorithm::Sha224 => Sha224::OUTPUT_LEN,
+            HashAlgorithm::Sha256 => Sha256::OUTPUT_LEN,
+            HashAlgorithm::Sha384 => Sha384::OUTPUT_LEN,
+            HashAlgorithm::Sha512 => Sha512::OUTPUT_LEN,
+            HashAlgorithm::Sha512_256 => Sha512_256::OUTPUT_LEN,
+            HashAlgorithm::Sha3_224 => Sha3_224::OUTPUT_LEN,
+            HashAlgorithm::Sha3_256 => Sha3_256::OUTPUT_LEN,
+            HashAlgorithm::Sha3_384 => Sha3_384::OUTPUT_LEN,
+            HashAlgorithm::Sha3_512 => Sha3_512::OUTPUT_LEN,
+            HashAlgorithm::Shake128 => Shake128::OUTPUT_LEN,
+            HashAlgorithm::Shake256 => Shake256::OUTPUT_LEN,
+            HashAlgorithm::Sha1 => Sha1::OUTPUT_LEN,
+            HashAlgorithm::Sha512_224 => Sha512_224::OUTPUT_LEN,
+        }
+    }
+
+    /// 分组长度（字节）
+    pub fn block_len(&self) -> usize {
+        match self {
+            HashAlgorithm::Sha224 => Sha224::BLOCK_LEN,
+            HashAlgorithm::Sha256 => Sha256::BLOCK_LEN,
+            HashAlgorithm::Sha384 => Sha384::BLOCK_LEN,
+            HashAlgorithm::Sha512 => Sha512::BLOCK_LEN,
+            HashAlgorithm::Sha512_256 => Sha512_256::BLOCK_LEN,
+            HashAlgorithm::Sha3_224 => Sha3_224::BLOCK_LEN,
+            HashAlgorithm::Sha3_256 => Sha3_256::BLOCK_LEN,
+            HashAlgorithm::Sha3_384 => Sha3_384::BLOCK_LEN,
+            HashAlgorithm::Sha3_512 => Sha3_512::BLOCK_LEN,
+            HashAlgorithm::Shake128 => Shake128::BLOCK_LEN,
+            HashAlgorithm::Shake256 => Shake256::BLOCK_LEN,
+            HashAlgorithm::Sha1 => Sha1::BLOCK_LEN,
+            HashAlgorithm::Sha512_224 => Sha512_224::BLOCK_LEN,
+        }
+    }
+
+    /// 创建该算法的流式哈希上下文
+    pub fn hasher(&self) -> Hasher {
+        Hasher::new(*self)
+    }
+
+    /// 一次性计算摘要
+    pub fn digest(&self, data: &[u8]) -> Vec<u8> {
+        let mut hasher = self.hasher();
+        hasher.update(data);
+        hasher.finalize()
+    }
+}
+
+impl fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HashAlgorithm::Sha224 => write!(f, "SHA-224"),
+            HashAlgorithm::Sha256 => write!(f, "SHA-256"),
+            HashAlgorithm::Sha384 => write!(f, "SHA-384"),
+            HashAlgorithm::Sha512 => write!(f, "SHA-512"),
+            HashAlgorithm::Sha512_256 => write!(f, "SHA-512/256"),
+            HashAlgorithm::Sha3_224 => write!(f, "SHA3-224"),
+            HashAlgorithm::Sha3_256 => write!(f, "SHA3-256"),
+            HashAlgorithm::Sha3_384 => write!(f, "SHA3-384"),
+            HashAlgorithm::Sha3_512 => write!(f, "SHA3-512"),
+            HashAlgorithm::Shake128 => write!(f, "SHAKE128"),
+            HashAlgorithm::Shake256 => write!(f, "SHAKE256"),
+            HashAlgorithm::Sha1 => write!(f, "SHA-1"),
+            HashAlgorithm::Sha512_224 => write!(f, "SHA-512/224"),
+        }
+    }
+}
+
+/// 运行时选择算法的流式哈希上下文
+#[derive(Clone)]
+pub enum Hasher {
+    Sha224(Sha224),
+    Sha256(Sha256),
+    Sha384(Sha384),
+    Sha512(Sha512),
+    Sha512_256(Sha512_256),
+    Sha3_224(Sha3_224),
+    Sha3_256(Sha3_256),
+    Sha3_384(Sha3_384),
+    Sha3_512(Sha3_512),
+    Shake128(Shake128),
+    Shake256(Shake256),
+    Sha1(Sha1),
+    Sha512_224(Sha512_224),
+}
+
+impl Hasher {
+    pub fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::Sha224 => Hasher::Sha224(Sha224::new()),
+            HashAlgorithm::Sha256 => Hasher::Sha256(Sha256::new()),
+            HashAlgorithm::Sha384 => Hasher::Sha384(Sha384::new()),
+            HashAlgorithm::Sha512 => Hasher::Sha512(Sha512::new()),
+            HashAlgorithm::Sha512_256 => Hasher::Sha512_256(Sha512_256::new()),
+            HashAlgorithm::Sha3_224 => Hasher::Sha3_224(Sha3_224::new()),
+            HashAlgorithm::Sha3_256 => Hasher::Sha3_256(Sha3_256::new()),
+            HashAlgorithm::Sha3_384 => Hasher::Sha3_384(Sha3_384::new()),
+            HashAlgorithm::Sha3_512 => Hasher::Sha3_512(Sha3_512::new()),
+            HashAlgorithm::Shake128 => Hasher::Shake128(Shake128::new()),
+            HashAlgorithm::Shake256 => Hasher::Shake256(Shake256::new()),
+            HashAlgorithm::Sha1 => Hasher::Sha1(Sha1::new()),
+            HashAlgorithm::Sha512_224 => Hasher::Sha512_224(Sha512_224::new()),
+        }
+    }
+
+    pub fn algorithm(&self) -> HashAlgorithm {
+        match self {
+            Hasher::Sha224(_) => HashAlgorithm::Sha224,
+            Hasher::Sha256(_) => HashAlgorithm::Sha256,
+            Hasher::Sha384(_) => HashAlgorithm::Sha384,
+            Hasher::Sha512(_) => HashAlgorithm::Sha512,
+            Hasher::Sha512_256(_) => HashAlgorithm::Sha512_256,
+            Hasher::Sha3_224(_) => HashAlgorithm::Sha3_224,
+            Hasher::Sha3_256(_) => HashAlgorithm::Sha3_256,
+            Hasher::Sha3_384(_) => HashAlgorithm::Sha3_384,
+            Hasher::Sha3_512(_) => HashAlgorithm::Sha3_512,
+            Hasher::Shake128(_) => HashAlgorithm::Shake128,
+            Hasher::Shake256(_) => HashAlgorithm::Shake256,
+            Hasher::Sha1(_) => HashAlgorithm::Sha1,
+            Hasher::Sha512_224(_) => HashAlgorithm::Sha512_224,
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Sha224(h) => h.update(data),
+            Hasher::Sha256(h) => h.update(data),
+            Hasher::Sha384(h) => h.update(data),
+            Hasher::Sha512(h) => h.update(data),
+            Hasher::Sha512_256(h) => h.update(data),
+            Hasher::Sha3_224(h) => h.update(data),
+            Hasher::Sha3_256(h) => h.update(data),
+            Hasher::Sha3_384(h) => h.update(data),
+            Hasher::Sha3_512(h) => h.update(data),
+            Hasher::Shake128(h) => h.update(data),
+            Hasher::Shake256(h) => h.update(data),
+            Hasher::Sha1(h) => h.update(data),
+            Hasher::Sha512_224(h) => h.update(data),
+        }
+    }
+
+    pub fn finalize(self) -> Vec<u8> {
+        match self {
+            Hasher::Sha224(h) => h.finalize(),
+            Hasher::Sha256(h) => h.finalize(),
+            Hasher::Sha384(h) => h.finalize(),
+            Hasher::Sha512(h) => h.finalize(),
+            Hasher::Sha512_256(h) => h.finalize(),
+            Hasher::Sha3_224(h) => h.finalize(),
+            Hasher::Sha3_256(h) => h.finalize(),
+            Hasher::Sha3_384(h) => h.finalize(),
+            Hasher::Sha3_512(h) => h.finalize(),
+            Hasher::Shake128(h) => h.finalize(),
+            Hasher::Shake256(h) => h.finalize(),
+            Hasher::Sha1(h) => h.finalize(),
+            Hasher::Sha512_224(h) => h.finalize(),
+        }
+    }
+
+    /// 以可扩展输出方式结束，只有 SHAKE 支持，其他算法返回 None
+    pub fn finalize_xof(self, out_len: usize) -> Option<Vec<u8>> {
+        match self {
+            Hasher::Shake128(h) => Some(h.finalize_xof(out_len)),
+            Hasher::Shake256(h) => Some(h.finalize_xof(out_len)),
+            _ => None,
+        }
+    }
+
+    /// 同 `finalize_xof`，但直接写入 `out`；非 SHAKE 算法返回 false 且不写入
+    pub fn finalize_xof_into(self, out: &mut [u8]) -> bool {
+        match self {
+            Hasher::Shake128(h) => h.finalize_xof_into(out),
+            Hasher::Shake256(h) => h.finalize_xof_into(out),
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// 计算 SHA-256 摘要
+pub fn sha256(data: &[u8]) -> Vec<u8> {
+    Sha256::digest(data)
+}
+
+// 把消息切成三段分别 update、以及逐字节 update，结果必须与一次性计算相同；
+// 切分点覆盖 SHA-1/SHA-2 的 64、128 字节分组和 SHA-3 各个 rate 的边界
+#[cfg(test)]
+pub(crate) fn assert_streaming_matches_one_shot<H: HashFunction>() {
+    let data: Vec<u8> = (0..300u32).map(|i| (i * 31 + 7) as u8).collect();
+    let expected = H::digest(&data);
+
+    let points = [0, 1, 55, 56, 63, 64, 65, 72, 104, 111, 112, 127, 128, 129, 135, 136, 137, 144, 168, 200, 300];
+    for &a in &points {
+        for &b in points.iter().filter(|&&b| b >= a) {
+            let mut hasher = H::new();
+            hasher.update(&data[..a]);
+            hasher.update(&data[a..b]);
+            hasher.update(&data[b..]);
+            assert_eq!(hasher.finalize(), expected, "split at {} and {}", a, b);
+        }
+    }
+
+    let mut hasher = H::new();
+    for byte in &data {
+        hasher.update(std::slice::from_ref(byte));
+    }
+    assert_eq!(hasher.finalize(), expected);
+}
@@ -0,0 +1,397 @@
+//! SHA-3 与 SHAKE 可扩展输出函数 (FIPS 202)，基于 Keccak-f[1600] 海绵结构
+
+use super::{ExtendableOutput, HashFunction};
+
+const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000000000000001, 0x0000000000008082, 0x800000000000808a, 0x8000000080008000,
+    0x000000000000808b, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+    0x000000000000008a, 0x0000000000000088, 0x0000000080008009, 0x000000008000000a,
+    0x000000008000808b, 0x800000000000008b, 0x8000000000008089, 0x8000000000008003,
+    0x8000000000008002, 0x8000000000000080, 0x000000000000800a, 0x800000008000000a,
+    0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008,
+];
+
+// ρ 步骤的循环移位量，按 π 步骤的访问顺序排列
+const RHO_OFFSETS: [u32; 24] = [
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
+];
+
+// π 步骤的 lane 访问顺序
+const PI_LANES: [usize; 24] = [
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1,
+];
+
+const STATE_BYTES: usize = 200;
+
+// SHA-3 与 SHAKE 的域分隔后缀（已包含 pad10*1 的第一个比特）
+const SHA3_SUFFIX: u8 = 0x06;
+const SHAKE_SUFFIX: u8 = 0x1f;
+
+/// Keccak-f[1600] 置换
+pub fn keccak_f1600(state: &mut [u64; 25]) {
+    for rc in ROUND_CONSTANTS {
+        // θ
+        let mut column = [0u64; 5];
+        for (x, c) in column.iter_mut().enumerate() {
+            *c = state[x] ^ state[x + 5] ^ state[x + 10] ^ state[x + 15] ^ state[x + 20];
+        }
+        for x in 0..5 {
+            let d = column[(x + 4) % 5] ^ column[(x + 1) % 5].rotate_left(1);
+            for y in 0..5 {
+                state[5 * y + x] ^= d;
+            }
+        }
+
+        // ρ 与 π
+        let mut current = state[1];
+        for (&lane, &offset) in PI_LANES.iter().zip(RHO_OFFSETS.iter()) {
+            let next = state[lane];
+            state[lane] = current.rotate_left(offset);
+            current = next;
+        }
+
+        // χ
+        for y in 0..5 {
+            let mut row = [0u64; 5];
+            row.copy_from_slice(&state[5 * y..5 * y + 5]);
+            for x in 0..5 {
+                state[5 * y + x] = row[x] ^ (!row[(x + 1) % 5] & row[(x + 2) % 5]);
+            }
+        }
+
+        // ι
+        state[0] ^= rc;
+    }
+}
+
+// 海绵结构，rate 为每次吸收/挤出的字节数
+#[derive(Clone)]
+struct KeccakSponge {
+    state: [u64; 25],
+    rate: usize,
+    buffer: [u8; STATE_BYTES],
+    buffer_len: usize,
+    suffix: u8,
+}
+
+impl KeccakSponge {
+    fn new(rate: usize, suffix: u8) -> Self {
+        KeccakSponge {
+            state: [0u64; 25],
+            rate,
+            buffer: [0u8; STATE_BYTES],
+            buffer_len: 0,
+            suffix,
+        }
+    }
+
+    fn absorb_buffer(&mut self) {
+        for (lane, chunk) in self.state.iter_mut().zip(self.buffer[..self.rate].chunks(8)) {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(chunk);
+            *lane ^= u64::from_le_bytes(word);
+        }
+        keccak_f1600(&mut self.state);
+        self.buffer = [0u8; STATE_BYTES];
+        self.buffer_len = 0;
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            let take = (self.rate - self.buffer_len).min(data.len());
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&data[..take]);
+            self.buffer_len += take;
+            data = &data[take..];
+            if self.buffer_len == self.rate {
+                self.absorb_buffer();
+            }
+        }
+    }
+
+    fn finalize(self, out_len: usize) -> Vec<u8> {
+        let mut out = vec![0u8; out_len];
+        self.squeeze_into(&mut out);
+        out
+    }
+
+    fn squeeze_into(mut self, out: &mut [u8]) {
+        self.buffer[self.buffer_len] ^= self.suffix;
+        self.buffer[self.rate - 1] ^= 0x80;
+        self.absorb_buffer();
+
+        let mut chunks = out.chunks_mut(self.rate).peekable();
+        while let Some(chunk) = chunks.next() {
+            let block = self.state[..self.rate / 8].iter().flat_map(|lane| lane.to_le_bytes());
+            for (dst, src) in chunk.iter_mut().zip(block) {
+                *dst = src;
+            }
+            if chunks.peek().is_some() {
+                keccak_f1600(&mut self.state);
+            }
+        }
+    }
+}
+
+macro_rules! sha3_variant {
+    ($name:ident, $doc:expr, $output_len:expr) => {
+        #[doc = $doc]
+        #[derive(Clone)]
+        pub struct $name(KeccakSponge);
+
+        impl HashFunction for $name {
+            const OUTPUT_LEN: usize = $output_len;
+            const BLOCK_LEN: usize = STATE_BYTES - 2 * $output_len;
+
+            fn new() -> Self {
+                $name(KeccakSponge::new(Self::BLOCK_LEN, SHA3_SUFFIX))
+            }
+
+            fn update(&mut self, data: &[u8]) {
+                self.0.update(data);
+            }
+
+            fn finalize(self) -> Vec<u8> {
+                self.0.finalize(Self::OUTPUT_LEN)
+            }
+        }
+    };
+}
+
+sha3_variant!(Sha3_224, "SHA3-224", 28);
+sha3_variant!(Sha3_256, "SHA3-256", 32);
+sha3_variant!(Sha3_384, "SHA3-384", 48);
+sha3_variant!(Sha3_512, "SHA3-512", 64);
+
+macro_rules! shake_variant {
+    ($name:ident, $doc:expr, $security_bytes:expr) => {
+        #[doc = $doc]
+        ///
+        /// 作为普通哈希使用时输出 2 倍安全强度的长度，任意长度输出请使用 `finalize_xof`
+        #[derive(Clone)]
+        pub struct $name(KeccakSponge);
+
+        impl HashFunction for $name {
+            const OUTPUT_LEN: usize = 2 * $security_bytes;
+            const BLOCK_LEN: usize = STATE_BYTES - 2 * $security_bytes;
+
+            fn new() -> Self {
+                $name(KeccakSponge::new(Self::BLOCK_LEN, SHAKE_SUFFIX))
+            }
+
+            fn update(&mut self, data: &[u8]) {
+                self.0.update(data);
+            }
+
+            fn finalize(self) -> Vec<u8> {
+                self.0.finalize(Self::OUTPUT_LEN)
+            }
+        }
+
+        impl ExtendableOutput for $name {
+            fn finalize_xof_into(self, out: &mut [u8]) {
+                self.0.squeeze_into(out);
+            }
+        }
+    };
+}
+
+shake_variant!(Shake128, "SHAKE128", 16);
+shake_variant!(Shake256, "SHAKE256", 32);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::assert_streaming_matches_one_shot;
+
+    fn hex_digest<H: HashFunction>(data: &[u8]) -> String {
+        hex::encode(H::digest(data))
+    }
+
+    // NIST 示例中的 1600 比特消息：200 个 0xa3
+    fn msg1600() -> Vec<u8> {
+        vec![0xa3; 200]
+    }
+
+    #[test]
+    fn fips202_sha3_224() {
+        assert_eq!(hex_digest::<Sha3_224>(b""), "6b4e03423667dbb73b6e15454f0eb1abd4597f9a1b078e3f5b5a6bc7");
+        assert_eq!(hex_digest::<Sha3_224>(b"abc"), "e642824c3f8cf24ad09234ee7d3c766fc9a3a5168d0c94ad73b46fdf");
+        assert_eq!(hex_digest::<Sha3_224>(&msg1600()), "9376816aba503f72f96ce7eb65ac095deee3be4bf9bbc2a1cb7e11e0");
+    }
+
+    #[test]
+    fn fips202_sha3_256() {
+        assert_eq!(
+            hex_digest::<Sha3_256>(b""),
+            "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a"
+        );
+        assert_eq!(
+            hex_digest::<Sha3_256>(b"abc"),
+            "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"
+        );
+        assert_eq!(
+            hex_digest::<Sha3_256>(&msg1600()),
+            "79f38adec5c20307a98ef76e8324afbfd46cfd81b22e3973c65fa1bd9de31787"
+        );
+    }
+
+    #[test]
+    fn fips202_sha3_384() {
+        assert_eq!(
+            hex_digest::<Sha3_384>(b""),
+            "0c63a75b845e4f7d01107d852e4c2485c51a50aaaa94fc61995e71bbee983a2ac3713831264adb47fb6bd1e058d5f004"
+        );
+        assert_eq!(
+            hex_digest::<Sha3_384>(b"abc"),
+            "ec01498288516fc926459f58e2c6ad8df9b473cb0fc08c2596da7cf0e49be4b298d88cea927ac7f539f1edf228376d25"
+        );
+        assert_eq!(
+            hex_digest::<Sha3_384>(&msg1600()),
+            "1881de2ca7e41ef95dc4732b8f5f002b189cc1e42b74168ed1732649ce1dbcdd76197a31fd55ee989f2d7050dd473e8f"
+        );
+    }
+
+    #[test]
+    fn fips202_sha3_512() {
+        assert_eq!(
+            hex_digest::<Sha3_512>(b""),
+            concat!(
+                "a69f73cca23a9ac5c8b567dc185a756e97c982164fe25859e0d1dcc1475c80a6",
+                "15b2123af1f5f94c11e3e9402c3ac558f500199d95b6d3e301758586281dcd26",
+            )
+        );
+        assert_eq!(
+            hex_digest::<Sha3_512>(b"abc"),
+            concat!(
+                "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e",
+                "10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0",
+            )
+        );
+        assert_eq!(
+            hex_digest::<Sha3_512>(&msg1600()),
+            concat!(
+                "e76dfad22084a8b1467fcf2ffa58361bec7628edf5f3fdc0e4805dc48caeeca8",
+                "1b7c13c30adf52a3659584739a2df46be589c51ca1a4a8416df6545a1ce8ba00",
+            )
+        );
+    }
+
+    #[test]
+    fn rate_boundaries() {
+        // rate - 1 字节时后缀和 0x80 落在同一字节，rate 字节时填充独占一个分组
+        let cases: [(usize, &str); 6] = [
+            (143, "73b1b22b54f515f626a6abdde6af25cd4801dc6e9dc7fa3f77e1c122"),
+            (144, "f9019111996dcf160e284e320fd6d8825cabcd41a5ffdc4c5e9d64b6"),
+            (145, "7f0521c84aeacc8a46aba17171acbdd22522509a71c663257fbdee0e"),
+            (135, "8094bb53c44cfb1e67b7c30447f9a1c33696d2463ecc1d9c92538913392843c9"),
+            (136, "3fc5559f14db8e453a0a3091edbd2bc25e11528d81c66fa570a4efdcc2695ee1"),
+            (137, "f8d6846cedd2ccfadf15c5879ef95af724d799eed7391fb1c91f95344e738614"),
+        ];
+        for (len, expected) in &cases[..3] {
+            assert_eq!(hex_digest::<Sha3_224>(&vec![b'a'; *len]), *expected, "{} bytes", len);
+        }
+        for (len, expected) in &cases[3..] {
+            assert_eq!(hex_digest::<Sha3_256>(&vec![b'a'; *len]), *expected, "{} bytes", len);
+        }
+
+        assert_eq!(
+            hex_digest::<Sha3_384>(&[b'a'; 104]),
+            "3a4f3b6284e571238884e95655e8c8a60e068e4059a9734abc08823a900d161592860243f00619ae699a29092ed91a16"
+        );
+        assert_eq!(
+            hex_digest::<Sha3_512>(&[b'a'; 72]),
+            concat!(
+                "a8ae722a78e10cbbc413886c02eb5b369a03f6560084aff566bd597bb7ad8c1c",
+                "cd86e81296852359bf2faddb5153c0a7445722987875e74287adac21adebe952",
+            )
+        );
+
+        let shake128 = [
+            (167, "4f5c6c53ae8190a8ff8a55b2125d28703052d10278570960c2066a905d916c34"),
+            (168, "c22e11586c22b713bde373fce93314d76829de2c21d940a28eb659b8dec953a2"),
+            (169, "09fc23f3acfd944380db0c7f5b1bde62d3a43c6e4c61ca9cb3dfee54904b36a8"),
+        ];
+        for (len, expected) in shake128 {
+            assert_eq!(hex_digest::<Shake128>(&vec![b'a'; len]), expected, "{} bytes", len);
+        }
+        assert_eq!(
+            hex_digest::<Shake256>(&[b'a'; 136]),
+            concat!(
+                "8fcc5a08f0a1f6827c9cf64ee8d16e0443106359ca6c8efd230759256f44996a",
+                "703c7fa566b8308f7050f4c717418c5ef75f512d1ba01f4f1ff5984e1bc89efd",
+            )
+        );
+    }
+
+    #[test]
+    fn fips202_shake() {
+        assert_eq!(
+            hex_digest::<Shake128>(b""),
+            "7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26"
+        );
+        assert_eq!(
+            hex_digest::<Shake256>(b""),
+            concat!(
+                "46b9dd2b0ba88d13233b3feb743eeb243fcd52ea62b81b82b50c27646ed5762f",
+                "d75dc4ddd8c0f200cb05019d67b592f6fc821c49479ab48640292eacb3b7c4be",
+            )
+        );
+
+        // NIST 示例 SHAKE128/256_Msg1600：输出 4096 比特，这里比对最后 32 字节
+        let mut hasher = Shake128::new();
+        hasher.update(&msg1600());
+        let out = hasher.finalize_xof(512);
+        assert_eq!(
+            hex::encode(&out[480..]),
+            "44c9fb359fd56ac0a9a75a743cff6862f17d7259ab075216c0699511643b6439"
+        );
+
+        let mut hasher = Shake256::new();
+        hasher.update(&msg1600());
+        let out = hasher.finalize_xof(512);
+        assert_eq!(
+            hex::encode(&out[480..]),
+            "6a1a9d7846436e4dca5728b6f760eef0ca92bf0be5615e96959d767197a0beeb"
+        );
+    }
+
+    #[test]
+    fn long_squeeze() {
+        // 10000 字节需要连续挤出几十个分组，且最后一块不是整分组
+        let mut hasher = Shake128::new();
+        hasher.update(b"abc");
+        let out = hasher.finalize_xof(10000);
+        assert_eq!(
+            hex::encode(&out[10000 - 32..]),
+            "dc1735d1582e155f374b31ae88d40e40cc521d659a0cda9bb8c5189d80f7155b"
+        );
+
+        let mut hasher = Shake256::new();
+        hasher.update(b"abc");
+        let out = hasher.finalize_xof(10000);
+        assert_eq!(
+            hex::encode(&out[10000 - 32..]),
+            "15ae42e2b4eb338c8d0514a96a5caaaf2c7f040c3b333329108edecfa217aa7e"
+        );
+    }
+
+    #[test]
+    fn shorter_output_is_prefix() {
+        let mut hasher = Shake128::new();
+        hasher.update(b"abc");
+        let full = hasher.clone().finalize_xof(400);
+        for len in [0, 1, 167, 168, 169, 336, 337] {
+            assert_eq!(hasher.clone().finalize_xof(len), full[..len], "{} bytes", len);
+        }
+    }
+
+    #[test]
+    fn streaming_matches_one_shot() {
+        assert_streaming_matches_one_shot::<Sha3_224>();
+        assert_streaming_matches_one_shot::<Sha3_256>();
+        assert_streaming_matches_one_shot::<Sha3_384>();
+        assert_streaming_matches_one_shot::<Sha3_512>();
+        assert_streaming_matches_one_shot::<Shake128>();
+        assert_streaming_matches_one_shot::<Shake256>();
+    }
+}
@@ -0,0 +1,431 @@
+//! Argon2id (RFC 9106)，版本 0x13，内部使用 BLAKE2b (RFC 7693)
+
+use super::KdfError;
+
+const VERSION: u32 = 0x13;
+const ARGON2ID: u32 = 2;
+const BLOCK_WORDS: usize = 128;
+const SYNC_POINTS: u32 = 4;
+
+/// m_cost 上限：4 × 1024 × 1024 个 1 KiB 块，即 4 GiB，防止恶意的代价参数（例如口令哈希字符串中的 m=）耗尽内存
+pub const ARGON2_MAX_M_COST: u32 = 4 * 1024 * 1024;
+
+/// Argon2id 代价参数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Argon2Params {
+    /// 内存大小（KiB），至少为 8 * parallelism，至多为 `ARGON2_MAX_M_COST`
+    pub m_cost: u32,
+    /// 迭代次数
+    pub t_cost: u32,
+    /// 并行通道数
+    pub parallelism: u32,
+}
+
+impl Argon2Params {
+    pub fn new(m_cost: u32, t_cost: u32, parallelism: u32) -> Result<Self, KdfError> {
+        if parallelism == 0 || parallelism > 0x00ff_ffff || t_cost == 0 {
+            return Err(KdfError::InvalidParameters);
+        }
+        if m_cost < 8 * parallelism || m_cost > ARGON2_MAX_M_COST {
+            return Err(KdfError::InvalidParameters);
+        }
+        Ok(Argon2Params { m_cost, t_cost, parallelism })
+    }
+}
+
+impl Default for Argon2Params {
+    /// OWASP 推荐的最低配置：19 MiB 内存，2 次迭代，1 个通道
+    fn default() -> Self {
+        Argon2Params { m_cost: 19 * 1024, t_cost: 2, parallelism: 1 }
+    }
+}
+
+type Block = [u64; BLOCK_WORDS];
+
+/// 使用 Argon2id 从口令派生 len 字节（len 至少为 4）
+pub fn argon2id(password: &[u8], salt: &[u8], params: &Argon2Params, len: usize) -> Result<Vec<u8>, KdfError> {
+    let params = Argon2Params::new(params.m_cost, params.t_cost, params.parallelism)?;
+    if len < 4 || len > u32::MAX as usize {
+        return Err(KdfError::InvalidLength);
+    }
+    if salt.len() < 8 {
+        return Err(KdfError::InvalidParameters);
+    }
+
+    let lanes = params.parallelism as usize;
+    let segment_len = (params.m_cost / (SYNC_POINTS * params.parallelism)) as usize;
+    let lane_len = segment_len * SYNC_POINTS as usize;
+    let memory_blocks = lane_len * lanes;
+
+    // H0
+    let mut h0_input = Vec::new();
+    for value in [params.parallelism, len as u32, params.m_cost, params.t_cost, VERSION, ARGON2ID] {
+        h0_input.extend_from_slice(&value.to_le_bytes());
+    }
+    for field in [password, salt, &[], &[]] {
+        h0_input.extend_from_slice(&(field.len() as u32).to_le_bytes());
+        h0_input.extend_from_slice(field);
+    }
+    let h0 = blake2b(&h0_input, 64);
+
+    // 每个通道的前两个块
+    let mut memory: Vec<Block> = vec![[0u64; BLOCK_WORDS]; memory_blocks];
+    for lane in 0..lanes {
+        for i in 0..2u32 {
+            let mut input = h0.clone();
+            input.extend_from_slice(&i.to_le_bytes());
+            input.extend_from_slice(&(lane as u32).to_le_bytes());
+            memory[lane * lane_len + i as usize] = bytes_to_block(&variable_hash(&input, 1024));
+        }
+    }
+
+    for pass in 0..params.t_cost {
+        for slice in 0..SYNC_POINTS {
+            for lane in 0..lanes {
+                fill_segment(
+                    &mut memory,
+                    &Position { pass, lane, slice },
+                    &Geometry { lanes, lane_len, segment_len, memory_blocks, passes: params.t_cost },
+                );
+            }
+        }
+    }
+
+    // 最后一列异或后做变长哈希
+    let mut last = memory[lane_len - 1];
+    for lane in 1..lanes {
+        xor_into(&mut last, &memory[lane * lane_len + lane_len - 1]);
+    }
+    Ok(variable_hash(&block_to_bytes(&last), len))
+}
+
+struct Position {
+    pass: u32,
+    lane: usize,
+    slice: u32,
+}
+
+struct Geometry {
+    lanes: usize,
+    lane_len: usize,
+    segment_len: usize,
+    memory_blocks: usize,
+    passes: u32,
+}
+
+fn fill_segment(memory: &mut [Block], pos: &Position, geo: &Geometry) {
+    // Argon2id：第一遍的前两个切片使用与数据无关的寻址
+    let data_independent = pos.pass == 0 && pos.slice < SYNC_POINTS / 2;
+
+    let zero_block: Block = [0u64; BLOCK_WORDS];
+    let mut input_block: Block = [0u64; BLOCK_WORDS];
+    let mut address_block: Block = [0u64; BLOCK_WORDS];
+    if data_independent {
+        input_block[0] = pos.pass as u64;
+        input_block[1] = pos.lane as u64;
+        input_block[2] = pos.slice as u64;
+        input_block[3] = geo.memory_blocks as u64;
+        input_block[4] = geo.passes as u64;
+        input_block[5] = ARGON2ID as u64;
+    }
+
+    let start_index = if pos.pass == 0 && pos.slice == 0 {
+        if data_independent {
+            next_addresses(&mut address_block, &mut input_block, &zero_block);
+        }
+        2
+    } else {
+        0
+    };
+
+    for index in start_index..geo.segment_len {
+        let offset = pos.slice as usize * geo.segment_len + index;
+        let current = pos.lane * geo.lane_len + offset;
+        let previous = if offset == 0 {
+            current + geo.lane_len - 1
+        } else {
+            current - 1
+        };
+
+        let pseudo_rand = if data_independent {
+            if index % BLOCK_WORDS == 0 {
+                next_addresses(&mut address_block, &mut input_block, &zero_block);
+            }
+            address_block[index % BLOCK_WORDS]
+        } else {
+            memory[previous][0]
+        };
+
+        let ref_lane = if pos.pass == 0 && pos.slice == 0 {
+            pos.lane
+        } else {
+            ((pseudo_rand >> 32) % geo.lanes as u64) as usize
+        };
+        let ref_index = reference_index(pos, geo, index, pseudo_rand as u32, ref_lane == pos.lane);
+        let reference = ref_lane * geo.lane_len + ref_index;
+
+        let mut new_block = compress(&memory[previous], &memory[reference]);
+        if pos.pass != 0 {
+            xor_into(&mut new_block, &memory[current]);
+        }
+        memory[current] = new_block;
+    }
+}
+
+// 计算参考块在所选通道内的位置（RFC 9106 3.4.2）
+fn reference_index(pos: &Position, geo: &Geometry, index: usize, j1: u32, same_lane: bool) -> usize {
+    let reference_area = if pos.pass == 0 {
+        if pos.slice == 0 || same_lane {
+            pos.slice as usize * geo.segment_len + index - 1
+        } else if index == 0 {
+            pos.slice as usize * geo.segment_len - 1
+        } else {
+            pos.slice as usize * geo.segment_len
+        }
+    } else if same_lane {
+        geo.lane_len - geo.segment_len + index - 1
+    } else if index == 0 {
+        geo.lane_len - geo.segment_len - 1
+    } else {
+        geo.lane_len - geo.segment_len
+    } as u64;
+
+    let x = (j1 as u64 * j1 as u64) >> 32;
+    let y = (reference_area * x) >> 32;
+    let relative = reference_area - 1 - y;
+
+    let start = if pos.pass != 0 && pos.slice != SYNC_POINTS - 1 {
+        (pos.slice as usize + 1) * geo.segment_len
+    } else {
+        0
+    };
+    (start + relative as usize) % geo.lane_len
+}
+
+fn next_addresses(address_block: &mut Block, input_block: &mut Block, zero_block: &Block) {
+    input_block[6] += 1;
+    let tmp = compress(zero_block, input_block);
+    *address_block = compress(zero_block, &tmp);
+}
+
+// 压缩函数 G(X, Y)
+fn compress(x: &Block, y: &Block) -> Block {
+    let mut r = *x;
+    xor_into(&mut r, y);
+    let mut q = r;
+
+    // 逐行应用置换 P
+    for row in 0..8 {
+        let mut v = [0u64; 16];
+        v.copy_from_slice(&q[row * 16..row * 16 + 16]);
+        permute(&mut v);
+        q[row * 16..row * 16 + 16].copy_from_slice(&v);
+    }
+    // 逐列应用置换 P
+    for column in 0..8 {
+        let mut v = [0u64; 16];
+        for i in 0..8 {
+            v[2 * i] = q[2 * column + 16 * i];
+            v[2 * i + 1] = q[2 * column + 16 * i + 1];
+        }
+        permute(&mut v);
+        for i in 0..8 {
+            q[2 * column + 16 * i] = v[2 * i];
+            q[2 * column + 16 * i + 1] = v[2 * i + 1];
+        }
+    }
+
+    xor_into(&mut q, &r);
+    q
+}
+
+fn permute(v: &mut [u64; 16]) {
+    blamka_g(v, 0, 4, 8, 12);
+    blamka_g(v, 1, 5, 9, 13);
+    blamka_g(v, 2, 6, 10, 14);
+    blamka_g(v, 3, 7, 11, 15);
+    blamka_g(v, 0, 5, 10, 15);
+    blamka_g(v, 1, 6, 11, 12);
+    blamka_g(v, 2, 7, 8, 13);
+    blamka_g(v, 3, 4, 9, 14);
+}
+
+// BlaMka 混合函数：在 BLAKE2b 的 G 上加入 2 * lo(a) * lo(b)
+fn blamka_g(v: &mut [u64; 16], a: usize, b: usize, c: usize, d: usize) {
+    let f = |x: u64, y: u64| {
+        x.wrapping_add(y)
+            .wrapping_add(2u64.wrapping_mul(x & 0xffff_ffff).wrapping_mul(y & 0xffff_ffff))
+    };
+    v[a] = f(v[a], v[b]);
+    v[d] = (v[d] ^ v[a]).rotate_right(32);
+    v[c] = f(v[c], v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(24);
+    v[a] = f(v[a], v[b]);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = f(v[c], v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(63);
+}
+
+fn xor_into(a: &mut Block, b: &Block) {
+    for (x, y) in a.iter_mut().zip(b.iter()) {
+        *x ^= y;
+    }
+}
+
+fn bytes_to_block(bytes: &[u8]) -> Block {
+    let mut block = [0u64; BLOCK_WORDS];
+    for (word, chunk) in block.iter_mut().zip(bytes.chunks(8)) {
+        let mut b = [0u8; 8];
+        b.copy_from_slice(chunk);
+        *word = u64::from_le_bytes(b);
+    }
+    block
+}
+
+fn block_to_bytes(block: &Block) -> Vec<u8> {
+    block.iter().flat_map(|w| w.to_le_bytes()).collect()
+}
+
+// 变长哈希 H'（RFC 9106 3.3）
+fn variable_hash(input: &[u8], out_len: usize) -> Vec<u8> {
+    let mut prefixed = (out_len as u32).to_le_bytes().to_vec();
+    prefixed.extend_from_slice(input);
+    if out_len <= 64 {
+        return blake2b(&prefixed, out_len);
+    }
+
+    let r = out_len.div_ceil(32) - 2;
+    let mut out = Vec::with_capacity(out_len);
+    let mut v = blake2b(&prefixed, 64);
+    out.extend_from_slice(&v[..32]);
+    for _ in 1..r {
+        v = blake2b(&v, 64);
+        out.extend_from_slice(&v[..32]);
+    }
+    out.extend_from_slice(&blake2b(&v, out_len - 32 * r));
+    out
+}
+
+const BLAKE2B_IV: [u64; 8] = [
+    0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+    0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179,
+];
+
+const BLAKE2B_SIGMA: [[usize; 16]; 12] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+];
+
+// 无密钥 BLAKE2b，输出 1..=64 字节
+fn blake2b(data: &[u8], out_len: usize) -> Vec<u8> {
+    let mut h = BLAKE2B_IV;
+    h[0] ^= 0x0101_0000 ^ out_len as u64;
+
+    let block_count = data.len().div_ceil(128).max(1);
+    for i in 0..block_count {
+        let start = i * 128;
+        let end = (start + 128).min(data.len());
+        let mut block = [0u8; 128];
+        block[..end - start].copy_from_slice(&data[start..end]);
+        let last = i == block_count - 1;
+        blake2b_compress(&mut h, &block, end as u128, last);
+    }
+
+    h.iter().flat_map(|w| w.to_le_bytes()).take(out_len).collect()
+}
+
+fn blake2b_compress(h: &mut [u64; 8], block: &[u8; 128], counter: u128, last: bool) {
+    let mut m = [0u64; 16];
+    for (word, chunk) in m.iter_mut().zip(block.chunks(8)) {
+        let mut b = [0u8; 8];
+        b.copy_from_slice(chunk);
+        *word = u64::from_le_bytes(b);
+    }
+
+    let mut v = [0u64; 16];
+    v[..8].copy_from_slice(h);
+    v[8..].copy_from_slice(&BLAKE2B_IV);
+    v[12] ^= counter as u64;
+    v[13] ^= (counter >> 64) as u64;
+    if last {
+        v[14] = !v[14];
+    }
+
+    for s in BLAKE2B_SIGMA {
+        blake2b_g(&mut v, 0, 4, 8, 12, m[s[0]], m[s[1]]);
+        blake2b_g(&mut v, 1, 5, 9, 13, m[s[2]], m[s[3]]);
+        blake2b_g(&mut v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+        blake2b_g(&mut v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+        blake2b_g(&mut v, 0, 5, 10, 15, m[s[8]], m[s[9]]);
+        blake2b_g(&mut v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+        blake2b_g(&mut v, 2, 7, 8, 13, m[s[12]], m[s[13]]);
+        blake2b_g(&mut v, 3, 4, 9, 14, m[s[14]], m[s[15]]);
+    }
+
+    for (i, word) in h.iter_mut().enumerate() {
+        *word ^= v[i] ^ v[i + 8];
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn blake2b_g(v: &mut [u64; 16], a: usize, b: usize, c: usize, d: usize, x: u64, y: u64) {
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+    v[d] = (v[d] ^ v[a]).rotate_right(32);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(24);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(63);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kdf::verify_password;
+
+    #[test]
+    fn memory_cost_is_bounded() {
+        assert!(Argon2Params::new(8, 1, 1).is_ok());
+        assert_eq!(Argon2Params::new(7, 1, 1), Err(KdfError::InvalidParameters));
+        assert_eq!(Argon2Params::new(31, 1, 4), Err(KdfError::InvalidParameters));
+        assert!(Argon2Params::new(ARGON2_MAX_M_COST, 1, 1).is_ok());
+        assert_eq!(Argon2Params::new(ARGON2_MAX_M_COST + 1, 1, 1), Err(KdfError::InvalidParameters));
+        assert_eq!(Argon2Params::new(u32::MAX, 1, 1), Err(KdfError::InvalidParameters));
+    }
+
+    #[test]
+    fn oversized_parameters_are_rejected_before_allocation() {
+        // 直接构造绕过 new 的参数同样在分配内存之前被拒绝
+        let params = Argon2Params { m_cost: u32::MAX, t_cost: 1, parallelism: 1 };
+        assert_eq!(argon2id(b"password", b"somesalt", &params, 32), Err(KdfError::InvalidParameters));
+
+        let encoded = format!("$argon2id$v=19$m={},t=1,p=1$c29tZXNhbHQ$AAAAAAAAAAAAAAAAAAAAAA", ARGON2_MAX_M_COST + 1);
+        assert_eq!(verify_password(b"password", &encoded), Err(KdfError::InvalidParameters));
+    }
+
+    #[test]
+    fn matches_openssl_argon2id() {
+        // openssl kdf -keylen 32 -kdfopt pass:password -kdfopt salt:somesalt
+        //             -kdfopt iter:<t> -kdfopt memcost:<m> -kdfopt lanes:<p> ARGON2ID
+        let cases = [
+            (256, 2, 2, "6d093c501fd5999645e0ea3bf620d7b8be7fd2db59c20d9fff9539da2bf57037"),
+            (32, 3, 4, "bb0cc80a3e671149526915418c6eefe761bb19d5d2d567a017703e0cea6ab05c"),
+        ];
+        for (m_cost, t_cost, parallelism, expected) in cases {
+            let params = Argon2Params::new(m_cost, t_cost, parallelism).unwrap();
+            let tag = argon2id(b"password", b"somesalt", &params, 32).unwrap();
+            assert_eq!(hex::encode(tag), expected, "m = {}, t = {}, p = {}", m_cost, t_cost, parallelism);
+        }
+    }
+}
@@ -0,0 +1,270 @@
+//! HKDF：基于 HMAC 的提取-扩展密钥派生函数 (RFC 5869)
+
+use super::KdfError;
+use crate::hash::{HashAlgorithm, Hmac};
+
+/// 会话密钥派生时使用的 info 标签
+pub const SESSION_KEY_LABEL: &[u8] = b"secure-communication session key";
+pub const SESSION_IV_LABEL: &[u8] = b"secure-communication session iv";
+pub const SESSION_MAC_LABEL: &[u8] = b"secure-communication session mac";
+
+/// 完成 extract 步骤后的 HKDF，持有伪随机密钥 PRK
+pub struct Hkdf {
+    algorithm: HashAlgorithm,
+    prk: Vec<u8>,
+}
+
+impl Hkdf {
+    /// HKDF-Extract：salt 为空时按 RFC 5869 使用全零串
+    pub fn extract(algorithm: HashAlgorithm, salt: &[u8], ikm: &[u8]) -> Result<Self, KdfError> {
+        let zero_salt = vec![0u8; algorithm.output_len()];
+        let salt = if salt.is_empty() { &zero_salt[..] } else { salt };
+        let prk = Hmac::mac(algorithm, salt, ikm).ok_or(KdfError::UnsupportedHash)?;
+        Ok(Hkdf { algorithm, prk })
+    }
+
+    /// 直接使用已有的 PRK（跳过 extract）
+    pub fn from_prk(algorithm: HashAlgorithm, prk: &[u8]) -> Result<Self, KdfError> {
+        if algorithm.is_xof() {
+            return Err(KdfError::UnsupportedHash);
+        }
+        if prk.len() < algorithm.output_len() {
+            return Err(KdfError::InvalidLength);
+        }
+        Ok(Hkdf { algorithm, prk: prk.to_vec() })
+    }
+
+    pub fn prk(&self) -> &[u8] {
+        &self.prk
+    }
+
+    /// HKDF-Expand：输出长度不能超过 255 倍摘要长度
+    pub fn expand(&self, info: &[u8], len: usize) -> Result<Vec<u8>, KdfError> {
+        let hash_len = self.algorithm.output_len();
+        if len > 255 * hash_len {
+            return Err(KdfError::InvalidLength);
+        }
+
+        let mut okm = Vec::with_capacity(len);
+        let mut previous: Vec<u8> = Vec::new();
+        let mut counter = 1u8;
+        while okm.len() < len {
+            let mut hmac = Hmac::new(self.algorithm, &self.prk).ok_or(KdfError::UnsupportedHash)?;
+            hmac.update(&previous);
+            hmac.update(info);
+            hmac.update(&[counter]);
+            previous = hmac.finalize();
+
+            let take = (len - okm.len()).min(hash_len);
+            okm.extend_from_slice(&previous[..take]);
+            counter = counter.wrapping_add(1);
+        }
+        Ok(okm)
+    }
+}
+
+/// extract + expand 一步完成
+pub fn hkdf(
+    algorithm: HashAlgorithm,
+    salt: &[u8],
+    ikm: &[u8],
+    info: &[u8],
+    len: usize,
+) -> Result<Vec<u8>, KdfError> {
+    Hkdf::extract(algorithm, salt, ikm)?.expand(info, len)
+}
+
+/// 从共享秘密派生的一组会话密钥，可直接用于本库的 AES 与 HMAC
+pub struct SessionKeys {
+    pub enc_key: [u8; 16],
+    /// 基础 IV，不能直接用于加密；第 n 条消息使用 `message_iv(n)`
+    pub iv: [u8; 16],
+    pub mac_key: [u8; 32],
+}
+
+impl SessionKeys {
+    /// 第 sequence 条消息的 IV，见 [`message_iv`]
+    pub fn message_iv(&self, sequence: u64) -> [u8; 16] {
+        message_iv(&self.iv, sequence)
+    }
+}
+
+/// 每条消息的 IV：把 64 位消息序号按大端异或到基础 IV 的后 8 字节 (与 TLS 1.3 的 nonce 构造相同)
+///
+/// 同一组会话密钥下序号不能重复，否则 CTR/GCM 模式会重用密钥流；通信双方各自维护递增的序号。
+pub fn message_iv(base_iv: &[u8; 16], sequence: u64) -> [u8; 16] {
+    let mut iv = *base_iv;
+    for (byte, s) in iv[8..].iter_mut().zip(sequence.to_be_bytes()) {
+        *byte ^= s;
+    }
+    iv
+}
+
+/// 使用 HKDF-SHA256 从共享秘密派生会话加密密钥、基础 IV 和 MAC 密钥
+pub fn derive_session_keys(shared_secret: &[u8], salt: &[u8]) -> SessionKeys {
+    let hkdf = Hkdf::extract(HashAlgorithm::Sha256, salt, shared_secret)
+        .expect("SHA-256 supports HMAC");
+
+    let mut keys = SessionKeys {
+        enc_key: [0u8; 16],
+        iv: [0u8; 16],
+        mac_key: [0u8; 32],
+    };
+    // 以下长度都远小于 255 * 32，expand 不会失败
+    keys.enc_key.copy_from_slice(&hkdf.expand(SESSION_KEY_LABEL, 16).unwrap());
+    keys.iv.copy_from_slice(&hkdf.expand(SESSION_IV_LABEL, 16).unwrap());
+    keys.mac_key.copy_from_slice(&hkdf.expand(SESSION_MAC_LABEL, 32).unwrap());
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn h(hex_str: &str) -> Vec<u8> {
+        hex::decode(hex_str).unwrap()
+    }
+
+    struct Vector {
+        algorithm: HashAlgorithm,
+        ikm: Vec<u8>,
+        salt: Vec<u8>,
+        info: Vec<u8>,
+        prk: &'static str,
+        okm: &'static str,
+    }
+
+    fn rfc5869_vectors() -> Vec<Vector> {
+        let long_ikm: Vec<u8> = (0x00..=0x4f).collect();
+        let long_salt: Vec<u8> = (0x60..=0xaf).collect();
+        let long_info: Vec<u8> = (0xb0..=0xff).collect();
+        let short_salt: Vec<u8> = (0x00..=0x0c).collect();
+        let short_info: Vec<u8> = (0xf0..=0xf9).collect();
+        vec![
+            // A.1
+            Vector {
+                algorithm: HashAlgorithm::Sha256,
+                ikm: vec![0x0b; 22],
+                salt: short_salt.clone(),
+                info: short_info.clone(),
+                prk: "077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5",
+                okm: "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865",
+            },
+            // A.2
+            Vector {
+                algorithm: HashAlgorithm::Sha256,
+                ikm: long_ikm.clone(),
+                salt: long_salt.clone(),
+                info: long_info.clone(),
+                prk: "06a6b88c5853361a06104c9ceb35b45cef760014904671014a193f40c15fc244",
+                okm: concat!(
+                    "b11e398dc80327a1c8e7f78c596a49344f012eda2d4efad8a050cc4c19afa97c",
+                    "59045a99cac7827271cb41c65e590e09da3275600c2f09b8367793a9aca3db71",
+                    "cc30c58179ec3e87c14c01d5c1f3434f1d87",
+                ),
+            },
+            // A.3
+            Vector {
+                algorithm: HashAlgorithm::Sha256,
+                ikm: vec![0x0b; 22],
+                salt: Vec::new(),
+                info: Vec::new(),
+                prk: "19ef24a32c717b167f33a91d6f648bdf96596776afdb6377ac434c1c293ccb04",
+                okm: "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8",
+            },
+            // A.4
+            Vector {
+                algorithm: HashAlgorithm::Sha1,
+                ikm: vec![0x0b; 11],
+                salt: short_salt,
+                info: short_info,
+                prk: "9b6c18c432a7bf8f0e71c8eb88f4b30baa2ba243",
+                okm: "085a01ea1b10f36933068b56efa5ad81a4f14b822f5b091568a9cdd4f155fda2c22e422478d305f3f896",
+            },
+            // A.5
+            Vector {
+                algorithm: HashAlgorithm::Sha1,
+                ikm: long_ikm,
+                salt: long_salt,
+                info: long_info,
+                prk: "8adae09a2a307059478d309b26c4115a224cfaf6",
+                okm: concat!(
+                    "0bd770a74d1160f7c9f12cd5912a06ebff6adcae899d92191fe4305673ba2ffe",
+                    "8fa3f1a4e5ad79f3f334b3b202b2173c486ea37ce3d397ed034c7f9dfeb15c5e",
+                    "927336d0441f4c4300e2cff0d0900b52d3b4",
+                ),
+            },
+            // A.6
+            Vector {
+                algorithm: HashAlgorithm::Sha1,
+                ikm: vec![0x0b; 22],
+                salt: Vec::new(),
+                info: Vec::new(),
+                prk: "da8c8a73c7fa77288ec6f5e7c297786aa0d32d01",
+                okm: "0ac1af7002b3d761d1e55298da9d0506b9ae52057220a306e07b6b87e8df21d0ea00033de03984d34918",
+            },
+            // A.7：未提供 salt，按默认的全零串处理
+            Vector {
+                algorithm: HashAlgorithm::Sha1,
+                ikm: vec![0x0c; 22],
+                salt: Vec::new(),
+                info: Vec::new(),
+                prk: "2adccada18779e7c2077ad2eb19d3f3e731385dd",
+                okm: "2c91117204d745f3500d636a62f64f0ab3bae548aa53d423b0d1f27ebba6f5e5673a081d70cce7acfc48",
+            },
+        ]
+    }
+
+    #[test]
+    fn rfc5869_test_cases() {
+        for (i, v) in rfc5869_vectors().into_iter().enumerate() {
+            let okm = h(v.okm);
+            let extracted = Hkdf::extract(v.algorithm, &v.salt, &v.ikm).unwrap();
+            assert_eq!(extracted.prk(), h(v.prk), "A.{}", i + 1);
+            assert_eq!(extracted.expand(&v.info, okm.len()).unwrap(), okm, "A.{}", i + 1);
+            assert_eq!(hkdf(v.algorithm, &v.salt, &v.ikm, &v.info, okm.len()).unwrap(), okm, "A.{}", i + 1);
+
+            let from_prk = Hkdf::from_prk(v.algorithm, &h(v.prk)).unwrap();
+            assert_eq!(from_prk.expand(&v.info, okm.len()).unwrap(), okm, "A.{}", i + 1);
+        }
+    }
+
+    #[test]
+    fn expand_length_limits() {
+        let extracted = Hkdf::extract(HashAlgorithm::Sha256, b"salt", b"ikm").unwrap();
+        assert_eq!(extracted.expand(b"", 255 * 32).unwrap().len(), 255 * 32);
+        assert_eq!(extracted.expand(b"", 255 * 32 + 1).err(), Some(KdfError::InvalidLength));
+        assert_eq!(Hkdf::from_prk(HashAlgorithm::Sha256, &[0u8; 31]).err(), Some(KdfError::InvalidLength));
+        assert_eq!(Hkdf::from_prk(HashAlgorithm::Shake128, &[0u8; 32]).err(), Some(KdfError::UnsupportedHash));
+    }
+
+    #[test]
+    fn session_message_ivs_do_not_repeat() {
+        let keys = derive_session_keys(b"shared secret", b"salt");
+        assert_eq!(keys.message_iv(0), keys.iv);
+
+        let ivs: std::collections::HashSet<[u8; 16]> = (0..1000).map(|n| keys.message_iv(n)).collect();
+        assert_eq!(ivs.len(), 1000);
+
+        // 序号只影响后 8 字节
+        let last = keys.message_iv(u64::MAX);
+        assert_eq!(last[..8], keys.iv[..8]);
+        assert!(last[8..].iter().zip(&keys.iv[8..]).all(|(a, b)| a ^ b == 0xff));
+        assert_eq!(message_iv(&[0u8; 16], 0x0102), [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x01, 0x02]);
+    }
+
+    #[test]
+    fn session_keys_are_separated_by_label() {
+        let keys = derive_session_keys(b"shared secret", b"salt");
+        let again = derive_session_keys(b"shared secret", b"salt");
+        assert_eq!((keys.enc_key, keys.iv, keys.mac_key), (again.enc_key, again.iv, again.mac_key));
+        assert_ne!(keys.enc_key, keys.iv);
+        assert_ne!(keys.enc_key[..], keys.mac_key[..16]);
+
+        let extracted = Hkdf::extract(HashAlgorithm::Sha256, b"salt", b"shared secret").unwrap();
+        assert_eq!(keys.iv.to_vec(), extracted.expand(SESSION_IV_LABEL, 16).unwrap());
+
+        let other = derive_session_keys(b"shared secret", b"other salt");
+        assert_ne!(other.enc_key, keys.enc_key);
+    }
+}
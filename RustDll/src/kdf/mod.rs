@@ -0,0 +1,44 @@
+//! 密钥派生函数模块
+//!
+//! HKDF (RFC 5869) 用于从共享秘密派生会话密钥、IV 和 MAC 密钥；
+//! PBKDF2、scrypt 和 Argon2id 用于从口令派生密钥，`password` 提供 PHC 格式的口令哈希与校验。
+
+pub mod argon2;
+pub mod hkdf;
+pub mod password;
+pub mod pbkdf2;
+pub mod scrypt;
+
+pub use argon2::{argon2id, Argon2Params, ARGON2_MAX_M_COST};
+pub use hkdf::{derive_session_keys, hkdf, message_iv, Hkdf, SessionKeys};
+pub use password::{hash_password, hash_password_with_salt, verify_password, PasswordHashParams};
+pub use pbkdf2::pbkdf2;
+pub use scrypt::{scrypt, ScryptParams};
+
+use std::fmt;
+
+/// 密钥派生错误类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KdfError {
+    /// 摘要算法不能用于该 KDF（例如 SHAKE 不能用于 HMAC）
+    UnsupportedHash,
+    /// 请求的输出长度超出范围
+    InvalidLength,
+    /// 代价参数或盐不满足算法要求
+    InvalidParameters,
+    /// 口令哈希字符串格式错误
+    InvalidEncoding,
+}
+
+impl fmt::Display for KdfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KdfError::UnsupportedHash => write!(f, "不支持的摘要算法"),
+            KdfError::InvalidLength => write!(f, "输出长度无效"),
+            KdfError::InvalidParameters => write!(f, "参数无效"),
+            KdfError::InvalidEncoding => write!(f, "口令哈希格式错误"),
+        }
+    }
+}
+
+impl std::error::Error for KdfError {}
@@ -0,0 +1,286 @@
+//! 口令哈希：以 PHC 字符串格式保存算法、参数、盐和哈希值
+//!
+//! - `$pbkdf2-sha256$i=600000$<salt>$<hash>`
+//! - `$scrypt$ln=15,r=8,p=1$<salt>$<hash>`
+//! - `$argon2id$v=19$m=19456,t=2,p=1$<salt>$<hash>`
+//!
+//! 盐和哈希使用不带填充的标准 Base64 编码。
+//!
+//! 校验时所有参数都来自存储的字符串，为了不让一条篡改过的记录拖垮登录流程，
+//! 迭代次数、内存、scrypt 代价和哈希长度都有上限，超出时返回 `KdfError::InvalidParameters`；
+//! 生成口令哈希时使用同样的上限，保证生成的字符串一定能被校验。
+
+use super::argon2::{argon2id, Argon2Params};
+use super::pbkdf2::pbkdf2;
+use super::scrypt::{scrypt, ScryptParams};
+use super::KdfError;
+use crate::encoding::base64::{self, Base64Variant};
+use crate::hash::{constant_time_eq, HashAlgorithm};
+use rand::rngs::OsRng;
+use rand::RngCore;
+
+/// 随机盐长度（字节）
+pub const SALT_LEN: usize = 16;
+/// 存储的哈希长度（字节）
+pub const HASH_LEN: usize = 32;
+/// PBKDF2-HMAC-SHA256 默认迭代次数（OWASP 2023 推荐值）
+pub const PBKDF2_DEFAULT_ITERATIONS: u32 = 600_000;
+/// PBKDF2 迭代次数上限，与加密 PKCS#8 私钥的上限相同
+pub const PHC_MAX_PBKDF2_ITERATIONS: u32 = crate::encoding::pkcs8::PBKDF2_MAX_ITERATIONS;
+/// scrypt 的 N * r * p 上限：N = 2^20、r = 8、p = 1 时内存为 1 GiB
+pub const PHC_MAX_SCRYPT_COST: u64 = 1 << 23;
+/// Argon2id 内存上限（KiB），2 GiB，覆盖 RFC 9106 推荐的 t = 1、m = 2 GiB
+pub const PHC_MAX_ARGON2_M_COST: u32 = 2 * 1024 * 1024;
+/// Argon2id 迭代次数上限
+pub const PHC_MAX_ARGON2_T_COST: u32 = 16;
+/// 哈希值长度上限（字节）
+pub const PHC_MAX_HASH_LEN: usize = 64;
+
+/// 口令哈希算法及其参数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordHashParams {
+    Pbkdf2Sha256 { iterations: u32 },
+    Scrypt(ScryptParams),
+    Argon2id(Argon2Params),
+}
+
+impl Default for PasswordHashParams {
+    fn default() -> Self {
+        PasswordHashParams::Argon2id(Argon2Params::default())
+    }
+}
+
+/// 使用随机盐计算口令哈希，返回 PHC 格式字符串
+pub fn hash_password(password: &[u8], params: &PasswordHashParams) -> Result<String, KdfError> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    hash_password_with_salt(password, &salt, params)
+}
+
+/// 使用给定的盐计算口令哈希，返回 PHC 格式字符串
+pub fn hash_password_with_salt(
+    password: &[u8],
+    salt: &[u8],
+    params: &PasswordHashParams,
+) -> Result<String, KdfError> {
+    check_limits(params)?;
+    let hash = derive(password, salt, params, HASH_LEN)?;
+    let salt = base64::encode(salt, Base64Variant::StandardNoPad);
+    let hash = base64::encode(&hash, Base64Variant::StandardNoPad);
+
+    Ok(match params {
+        PasswordHashParams::Pbkdf2Sha256 { iterations } => {
+            format!("$pbkdf2-sha256$i={}${}${}", iterations, salt, hash)
+        }
+        PasswordHashParams::Scrypt(p) => {
+            format!("$scrypt$ln={},r={},p={}${}${}", p.log_n, p.r, p.p, salt, hash)
+        }
+        PasswordHashParams::Argon2id(p) => format!(
+            "$argon2id$v=19$m={},t={},p={}${}${}",
+            p.m_cost, p.t_cost, p.parallelism, salt, hash
+        ),
+    })
+}
+
+/// 校验口令是否与 PHC 字符串匹配；字符串格式错误时返回 Err
+pub fn verify_password(password: &[u8], encoded: &str) -> Result<bool, KdfError> {
+    let (params, salt, expected) = parse_phc(encoded)?;
+    let actual = derive(password, &salt, &params, expected.len())?;
+    Ok(constant_time_eq(&actual, &expected))
+}
+
+// 检查代价参数是否在上限之内
+fn check_limits(params: &PasswordHashParams) -> Result<(), KdfError> {
+    let within = match params {
+        PasswordHashParams::Pbkdf2Sha256 { iterations } => *iterations <= PHC_MAX_PBKDF2_ITERATIONS,
+        PasswordHashParams::Scrypt(p) => {
+            (1u128 << p.log_n) * p.r as u128 * p.p as u128 <= PHC_MAX_SCRYPT_COST as u128
+        }
+        PasswordHashParams::Argon2id(p) => {
+            p.m_cost <= PHC_MAX_ARGON2_M_COST && p.t_cost <= PHC_MAX_ARGON2_T_COST
+        }
+    };
+    if within { Ok(()) } else { Err(KdfError::InvalidParameters) }
+}
+
+fn derive(password: &[u8], salt: &[u8], params: &PasswordHashParams, len: usize) -> Result<Vec<u8>, KdfError> {
+    match params {
+        PasswordHashParams::Pbkdf2Sha256 { iterations } => {
+            pbkdf2(HashAlgorithm::Sha256, password, salt, *iterations, len)
+        }
+        PasswordHashParams::Scrypt(p) => scrypt(password, salt, p, len),
+        PasswordHashParams::Argon2id(p) => argon2id(password, salt, p, len),
+    }
+}
+
+// 解析 PHC 字符串，得到参数、盐和哈希值
+fn parse_phc(encoded: &str) -> Result<(PasswordHashParams, Vec<u8>, Vec<u8>), KdfError> {
+    let fields: Vec<&str> = encoded.split('$').collect();
+    if fields.first() != Some(&"") {
+        return Err(KdfError::InvalidEncoding);
+    }
+
+    let (params, rest) = match fields.get(1).copied() {
+        Some("pbkdf2-sha256") if fields.len() == 5 => {
+            let iterations = parse_param(fields[2], "i")?;
+            (PasswordHashParams::Pbkdf2Sha256 { iterations }, &fields[3..])
+        }
+        Some("scrypt") if fields.len() == 5 => {
+            let values = parse_params(fields[2], &["ln", "r", "p"])?;
+            let log_n = u8::try_from(values[0]).map_err(|_| KdfError::InvalidParameters)?;
+            let p = ScryptParams::new(log_n, values[1], values[2])?;
+            (PasswordHashParams::Scrypt(p), &fields[3..])
+        }
+        Some("argon2id") if fields.len() == 6 => {
+            if parse_param(fields[2], "v")? != 19 {
+                return Err(KdfError::InvalidParameters);
+            }
+            let values = parse_params(fields[3], &["m", "t", "p"])?;
+            let p = Argon2Params::new(values[0], values[1], values[2])?;
+            (PasswordHashParams::Argon2id(p), &fields[4..])
+        }
+        _ => return Err(KdfError::InvalidEncoding),
+    };
+
+    let salt = base64::decode(rest[0], Base64Variant::StandardNoPad).ok_or(KdfError::InvalidEncoding)?;
+    let hash = base64::decode(rest[1], Base64Variant::StandardNoPad).ok_or(KdfError::InvalidEncoding)?;
+    if hash.is_empty() {
+        return Err(KdfError::InvalidEncoding);
+    }
+    if hash.len() > PHC_MAX_HASH_LEN {
+        return Err(KdfError::InvalidParameters);
+    }
+    check_limits(&params)?;
+    Ok((params, salt, hash))
+}
+
+// 解析单个 "name=value" 参数
+fn parse_param(field: &str, name: &str) -> Result<u32, KdfError> {
+    let value = field
+        .strip_prefix(name)
+        .and_then(|v| v.strip_prefix('='))
+        .ok_or(KdfError::InvalidEncoding)?;
+    value.parse().map_err(|_| KdfError::InvalidEncoding)
+}
+
+// 按顺序解析以逗号分隔的 "name=value" 参数列表
+fn parse_params(field: &str, names: &[&str]) -> Result<Vec<u32>, KdfError> {
+    let parts: Vec<&str> = field.split(',').collect();
+    if parts.len() != names.len() {
+        return Err(KdfError::InvalidEncoding);
+    }
+    parts
+        .iter()
+        .zip(names.iter())
+        .map(|(part, name)| parse_param(part, name))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PASSWORD: &[u8] = b"correct horse battery staple";
+
+    fn small_params() -> [PasswordHashParams; 3] {
+        [
+            PasswordHashParams::Pbkdf2Sha256 { iterations: 1000 },
+            PasswordHashParams::Scrypt(ScryptParams::new(10, 8, 1).unwrap()),
+            PasswordHashParams::Argon2id(Argon2Params::new(64, 2, 1).unwrap()),
+        ]
+    }
+
+    #[test]
+    fn hash_then_verify_round_trip() {
+        for params in small_params() {
+            let encoded = hash_password(PASSWORD, &params).unwrap();
+            assert_eq!(verify_password(PASSWORD, &encoded), Ok(true), "{}", encoded);
+            assert_eq!(verify_password(b"correct horse battery stapler", &encoded), Ok(false), "{}", encoded);
+            assert_eq!(verify_password(b"", &encoded), Ok(false), "{}", encoded);
+        }
+    }
+
+    #[test]
+    fn random_salt_differs_between_hashes() {
+        let params = PasswordHashParams::Pbkdf2Sha256 { iterations: 1000 };
+        let a = hash_password(PASSWORD, &params).unwrap();
+        let b = hash_password(PASSWORD, &params).unwrap();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn verifies_strings_from_other_implementations() {
+        // 分别由 Python hashlib.pbkdf2_hmac、hashlib.scrypt 和 openssl kdf ARGON2ID 生成，盐为 "0123456789abcdef"
+        let encoded = [
+            "$pbkdf2-sha256$i=1000$MDEyMzQ1Njc4OWFiY2RlZg$yqSq2SygY1sB4EcH9f2FG0JTMES+wqLsOT5YmiRBplI",
+            "$scrypt$ln=10,r=8,p=1$MDEyMzQ1Njc4OWFiY2RlZg$7AnzIswxwEinpwz+ntydndYfVHPvOACmX9Vvo8hO1qM",
+            "$argon2id$v=19$m=64,t=2,p=1$MDEyMzQ1Njc4OWFiY2RlZg$gAwJW5vx5itn2V9KHlEUjyUCrpDYgKyQf0gMaHGx1h4",
+        ];
+        for (params, expected) in small_params().iter().zip(encoded) {
+            assert_eq!(hash_password_with_salt(PASSWORD, b"0123456789abcdef", params).unwrap(), expected);
+            assert_eq!(verify_password(PASSWORD, expected), Ok(true));
+            assert_eq!(verify_password(b"wrong password", expected), Ok(false));
+        }
+    }
+
+    #[test]
+    fn costs_above_the_caps_are_rejected() {
+        let hash = "c29tZXNhbHQ$AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+        let encoded = [
+            format!("$pbkdf2-sha256$i={}${}", PHC_MAX_PBKDF2_ITERATIONS + 1, hash),
+            format!("$pbkdf2-sha256$i={}${}", u32::MAX, hash),
+            // N * r * p = 2^24
+            format!("$scrypt$ln=20,r=8,p=2${}", hash),
+            format!("$argon2id$v=19$m=19456,t={},p=1${}", PHC_MAX_ARGON2_T_COST + 1, hash),
+            format!("$argon2id$v=19$m=19456,t={},p=1${}", u32::MAX, hash),
+            format!("$argon2id$v=19$m={},t=1,p=1${}", PHC_MAX_ARGON2_M_COST + 1, hash),
+        ];
+        for encoded in &encoded {
+            assert_eq!(verify_password(PASSWORD, encoded), Err(KdfError::InvalidParameters), "{}", encoded);
+        }
+
+        let over_cap = [
+            PasswordHashParams::Pbkdf2Sha256 { iterations: PHC_MAX_PBKDF2_ITERATIONS + 1 },
+            PasswordHashParams::Scrypt(ScryptParams::new(20, 8, 2).unwrap()),
+            PasswordHashParams::Argon2id(Argon2Params::new(64, PHC_MAX_ARGON2_T_COST + 1, 1).unwrap()),
+            PasswordHashParams::Argon2id(Argon2Params::new(PHC_MAX_ARGON2_M_COST + 1, 1, 1).unwrap()),
+        ];
+        for params in &over_cap {
+            assert_eq!(hash_password(PASSWORD, params), Err(KdfError::InvalidParameters), "{:?}", params);
+        }
+    }
+
+    #[test]
+    fn hash_length_is_bounded() {
+        let salt = "MDEyMzQ1Njc4OWFiY2RlZg";
+        let long = base64::encode(&[0u8; PHC_MAX_HASH_LEN + 1], Base64Variant::StandardNoPad);
+        let encoded = format!("$pbkdf2-sha256$i=1${}${}", salt, long);
+        assert_eq!(verify_password(PASSWORD, &encoded), Err(KdfError::InvalidParameters));
+
+        // 上限以内的长度按存储的长度派生并比较
+        let hash = pbkdf2(HashAlgorithm::Sha256, PASSWORD, b"0123456789abcdef", 1, PHC_MAX_HASH_LEN).unwrap();
+        let hash = base64::encode(&hash, Base64Variant::StandardNoPad);
+        let encoded = format!("$pbkdf2-sha256$i=1${}${}", salt, hash);
+        assert_eq!(verify_password(PASSWORD, &encoded), Ok(true));
+    }
+
+    #[test]
+    fn malformed_strings_are_rejected() {
+        let cases = [
+            "",
+            "pbkdf2-sha256$i=1000$MDEyMzQ1Njc4OWFiY2RlZg$AAAA",
+            "$pbkdf2-sha512$i=1000$MDEyMzQ1Njc4OWFiY2RlZg$AAAA",
+            "$pbkdf2-sha256$n=1000$MDEyMzQ1Njc4OWFiY2RlZg$AAAA",
+            "$pbkdf2-sha256$i=1000$MDEyMzQ1Njc4OWFiY2RlZg$",
+            "$pbkdf2-sha256$i=1000$MDEyMzQ1Njc4OWFiY2RlZg$!!!!",
+            "$scrypt$ln=10,r=8$MDEyMzQ1Njc4OWFiY2RlZg$AAAA",
+            "$argon2id$m=64,t=2,p=1$MDEyMzQ1Njc4OWFiY2RlZg$AAAA",
+        ];
+        for encoded in cases {
+            assert_eq!(verify_password(PASSWORD, encoded), Err(KdfError::InvalidEncoding), "{}", encoded);
+        }
+        // 只接受 Argon2 1.3 版本
+        let encoded = "$argon2id$v=16$m=64,t=2,p=1$MDEyMzQ1Njc4OWFiY2RlZg$AAAA";
+        assert_eq!(verify_password(PASSWORD, encoded), Err(KdfError::InvalidParameters));
+    }
+}
@@ -0,0 +1,100 @@
+//! PBKDF2 (RFC 8018 5.2)，伪随机函数为 HMAC
+
+use super::KdfError;
+use crate::hash::{HashAlgorithm, Hmac};
+
+/// PBKDF2-HMAC，`iterations` 至少为 1
+pub fn pbkdf2(
+    algorithm: HashAlgorithm,
+    password: &[u8],
+    salt: &[u8],
+    iterations: u32,
+    len: usize,
+) -> Result<Vec<u8>, KdfError> {
+    if iterations == 0 {
+        return Err(KdfError::InvalidParameters);
+    }
+    if len == 0 || len as u64 > u32::MAX as u64 * algorithm.output_len() as u64 {
+        return Err(KdfError::InvalidLength);
+    }
+
+    // 密钥处理只做一次，之后每轮克隆上下文即可
+    let prf = Hmac::new(algorithm, password).ok_or(KdfError::UnsupportedHash)?;
+
+    let mut out = Vec::with_capacity(len);
+    let mut block_index = 1u32;
+    while out.len() < len {
+        let mut mac = prf.clone();
+        mac.update(salt);
+        mac.update(&block_index.to_be_bytes());
+        let mut u = mac.finalize();
+        let mut t = u.clone();
+
+        for _ in 1..iterations {
+            let mut mac = prf.clone();
+            mac.update(&u);
+            u = mac.finalize();
+            for (a, b) in t.iter_mut().zip(u.iter()) {
+                *a ^= b;
+            }
+        }
+
+        let take = (len - out.len()).min(t.len());
+        out.extend_from_slice(&t[..take]);
+        block_index += 1;
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rfc6070_hmac_sha1() {
+        // RFC 6070 第 2 节；c = 16777216 的用例太慢，不在这里运行
+        let cases: [(&[u8], &[u8], u32, &str); 5] = [
+            (b"password", b"salt", 1, "0c60c80f961f0e71f3a9b524af6012062fe037a6"),
+            (b"password", b"salt", 2, "ea6c014dc72d6f8ccd1ed92ace1d41f0d8de8957"),
+            (b"password", b"salt", 4096, "4b007901b765489abead49d926f721d065a429c1"),
+            (
+                b"passwordPASSWORDpassword",
+                b"saltSALTsaltSALTsaltSALTsaltSALTsalt",
+                4096,
+                "3d2eec4fe41c849b80c8d83662c0e44a8b291a964cf2f07038",
+            ),
+            (b"pass\0word", b"sa\0lt", 4096, "56fa6aa75548099dcc37d7f03425e0c3"),
+        ];
+        for (password, salt, iterations, expected) in cases {
+            let expected = hex::decode(expected).unwrap();
+            let dk = pbkdf2(HashAlgorithm::Sha1, password, salt, iterations, expected.len()).unwrap();
+            assert_eq!(dk, expected, "c = {}", iterations);
+        }
+    }
+
+    #[test]
+    fn rfc7914_hmac_sha256() {
+        // RFC 7914 第 11 节
+        let cases: [(&[u8], &[u8], u32, &str); 2] = [
+            (b"passwd", b"salt", 1, concat!(
+                "55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc",
+                "49ca9cccf179b645991664b39d77ef317c71b845b1e30bd509112041d3a19783",
+            )),
+            (b"Password", b"NaCl", 80000, concat!(
+                "4ddcd8f60b98be21830cee5ef22701f9641a4418d04c0414aeff08876b34ab56",
+                "a1d425a1225833549adb841b51c9b3176a272bdebba1d078478f62b397f33c8d",
+            )),
+        ];
+        for (password, salt, iterations, expected) in cases {
+            let dk = pbkdf2(HashAlgorithm::Sha256, password, salt, iterations, 64).unwrap();
+            assert_eq!(hex::encode(dk), expected, "c = {}", iterations);
+        }
+    }
+
+    #[test]
+    fn invalid_arguments_are_rejected() {
+        assert_eq!(pbkdf2(HashAlgorithm::Sha256, b"p", b"s", 0, 32), Err(KdfError::InvalidParameters));
+        assert_eq!(pbkdf2(HashAlgorithm::Sha256, b"p", b"s", 1, 0), Err(KdfError::InvalidLength));
+        assert_eq!(pbkdf2(HashAlgorithm::Shake128, b"p", b"s", 1, 32), Err(KdfError::UnsupportedHash));
+    }
+}
@@ -0,0 +1,172 @@
+//! scrypt (RFC 7914)
+
+use super::pbkdf2::pbkdf2;
+use super::KdfError;
+use crate::hash::HashAlgorithm;
+
+/// scrypt 代价参数：N = 2^log_n，块大小 r，并行度 p
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScryptParams {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+}
+
+impl ScryptParams {
+    /// 校验参数：1 <= log_n < 64 且 N < 2^(128r/8)，r * p < 2^30，内存占用不超过 4 GiB
+    pub fn new(log_n: u8, r: u32, p: u32) -> Result<Self, KdfError> {
+        if log_n == 0 || log_n >= 64 || r == 0 || p == 0 {
+            return Err(KdfError::InvalidParameters);
+        }
+        if (log_n as u64) >= 16 * r as u64 {
+            return Err(KdfError::InvalidParameters);
+        }
+        if (r as u64) * (p as u64) >= 1 << 30 {
+            return Err(KdfError::InvalidParameters);
+        }
+        let memory = 128u128 * r as u128 * (1u128 << log_n);
+        if memory > 1 << 32 {
+            return Err(KdfError::InvalidParameters);
+        }
+        Ok(ScryptParams { log_n, r, p })
+    }
+}
+
+impl Default for ScryptParams {
+    /// 交互式登录推荐值：N = 2^15，r = 8，p = 1（约 32 MiB 内存）
+    fn default() -> Self {
+        ScryptParams { log_n: 15, r: 8, p: 1 }
+    }
+}
+
+/// 使用 scrypt 从口令派生 len 字节
+pub fn scrypt(password: &[u8], salt: &[u8], params: &ScryptParams, len: usize) -> Result<Vec<u8>, KdfError> {
+    let params = ScryptParams::new(params.log_n, params.r, params.p)?;
+    if len == 0 {
+        return Err(KdfError::InvalidLength);
+    }
+
+    let block_len = 128 * params.r as usize;
+    let mut b = pbkdf2(HashAlgorithm::Sha256, password, salt, 1, block_len * params.p as usize)?;
+    for chunk in b.chunks_mut(block_len) {
+        ro_mix(chunk, params.r as usize, 1usize << params.log_n);
+    }
+    pbkdf2(HashAlgorithm::Sha256, password, &b, 1, len)
+}
+
+// scryptROMix：在 N 个块的表上做依赖数据的随机访问
+fn ro_mix(block: &mut [u8], r: usize, n: usize) {
+    let words = 32 * r;
+    let mut x: Vec<u32> = block
+        .chunks(4)
+        .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+        .collect();
+
+    let mut v = vec![0u32; words * n];
+    let mut scratch = vec![0u32; words];
+    for i in 0..n {
+        v[i * words..(i + 1) * words].copy_from_slice(&x);
+        block_mix(&mut x, &mut scratch, r);
+    }
+    for _ in 0..n {
+        // Integerify：最后一个 64 字节块的前 8 字节（小端）
+        let j_low = x[(2 * r - 1) * 16] as u64;
+        let j_high = x[(2 * r - 1) * 16 + 1] as u64;
+        let j = ((j_high << 32 | j_low) % n as u64) as usize;
+        for (a, b) in x.iter_mut().zip(&v[j * words..(j + 1) * words]) {
+            *a ^= b;
+        }
+        block_mix(&mut x, &mut scratch, r);
+    }
+
+    for (chunk, word) in block.chunks_mut(4).zip(x.iter()) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+}
+
+// scryptBlockMix，输出按偶数块在前、奇数块在后排列
+fn block_mix(b: &mut [u32], y: &mut [u32], r: usize) {
+    let mut x = [0u32; 16];
+    x.copy_from_slice(&b[(2 * r - 1) * 16..]);
+    for i in 0..2 * r {
+        for (a, c) in x.iter_mut().zip(&b[i * 16..(i + 1) * 16]) {
+            *a ^= c;
+        }
+        salsa20_8(&mut x);
+        let dest = if i % 2 == 0 { i / 2 } else { r + i / 2 };
+        y[dest * 16..(dest + 1) * 16].copy_from_slice(&x);
+    }
+    b.copy_from_slice(y);
+}
+
+// Salsa20/8 核心函数
+fn salsa20_8(b: &mut [u32; 16]) {
+    let mut x = *b;
+    for _ in 0..4 {
+        // 列轮
+        quarter_round(&mut x, 0, 4, 8, 12);
+        quarter_round(&mut x, 5, 9, 13, 1);
+        quarter_round(&mut x, 10, 14, 2, 6);
+        quarter_round(&mut x, 15, 3, 7, 11);
+        // 行轮
+        quarter_round(&mut x, 0, 1, 2, 3);
+        quarter_round(&mut x, 5, 6, 7, 4);
+        quarter_round(&mut x, 10, 11, 8, 9);
+        quarter_round(&mut x, 15, 12, 13, 14);
+    }
+    for (a, c) in b.iter_mut().zip(x.iter()) {
+        *a = a.wrapping_add(*c);
+    }
+}
+
+fn quarter_round(x: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    x[b] ^= x[a].wrapping_add(x[d]).rotate_left(7);
+    x[c] ^= x[b].wrapping_add(x[a]).rotate_left(9);
+    x[d] ^= x[c].wrapping_add(x[b]).rotate_left(13);
+    x[a] ^= x[d].wrapping_add(x[c]).rotate_left(18);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (口令, 盐, log_n, r, p, 期望输出)
+    type Vector = (&'static [u8], &'static [u8], u8, u32, u32, &'static str);
+
+    #[test]
+    fn rfc7914_vectors() {
+        // RFC 7914 第 12 节；N = 2^20 的用例需要 1 GiB 内存，不在这里运行
+        let cases: [Vector; 3] = [
+            (b"", b"", 4, 1, 1, concat!(
+                "77d6576238657b203b19ca42c18a0497f16b4844e3074ae8dfdffa3fede21442",
+                "fcd0069ded0948f8326a753a0fc81f17e8d3e0fb2e0d3628cf35e20c38d18906",
+            )),
+            (b"password", b"NaCl", 10, 8, 16, concat!(
+                "fdbabe1c9d3472007856e7190d01e9fe7c6ad7cbc8237830e77376634b373162",
+                "2eaf30d92e22a3886ff109279d9830dac727afb94a83ee6d8360cbdfa2cc0640",
+            )),
+            (b"pleaseletmein", b"SodiumChloride", 14, 8, 1, concat!(
+                "7023bdcb3afd7348461c06cd81fd38ebfda8fbba904f8e3ea9b543f6545da1f2",
+                "d5432955613f0fcf62d49705242a9af9e61e85dc0d651e40dfcf017b45575887",
+            )),
+        ];
+        for (password, salt, log_n, r, p, expected) in cases {
+            let params = ScryptParams::new(log_n, r, p).unwrap();
+            let dk = scrypt(password, salt, &params, 64).unwrap();
+            assert_eq!(hex::encode(dk), expected, "N = 2^{}", log_n);
+        }
+    }
+
+    #[test]
+    fn invalid_parameters_are_rejected() {
+        assert_eq!(ScryptParams::new(0, 8, 1), Err(KdfError::InvalidParameters));
+        assert_eq!(ScryptParams::new(14, 0, 1), Err(KdfError::InvalidParameters));
+        assert_eq!(ScryptParams::new(14, 8, 0), Err(KdfError::InvalidParameters));
+        // N 必须小于 2^(16r)
+        assert_eq!(ScryptParams::new(16, 1, 1), Err(KdfError::InvalidParameters));
+        assert_eq!(ScryptParams::new(14, 1 << 15, 1 << 15), Err(KdfError::InvalidParameters));
+        // 内存 128 * r * N 超过 4 GiB
+        assert_eq!(ScryptParams::new(23, 8, 1), Err(KdfError::InvalidParameters));
+        assert!(ScryptParams::new(22, 8, 1).is_ok());
+    }
+}
@@ -6,17 +6,83 @@ use aes::{Aes, AesMode, bytes_to_hex, generate_random_key, generate_random_iv};
 pub mod Rsa;
 use Rsa::rsa;
 use num_bigint::BigUint;
+use std::ffi::c_void;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
 
 // 导出ElGamal模块
 pub mod ElGamal;
 use ElGamal::elgamal;
 
+// 导出DRBG模块
+pub mod drbg;
+
+// 导出哈希模块
+pub mod hash;
+
+// 导出编码模块
+pub mod encoding;
+
+// 导出密钥派生模块
+pub mod kdf;
+
+// 导出数论模块
+pub mod numtheory;
+
 // 错误码定义
 pub const CRYPTO_SUCCESS: i32 = 0;
 pub const CRYPTO_ERROR_INVALID_PARAMETER: i32 = -1;
 pub const CRYPTO_ERROR_BUFFER_TOO_SMALL: i32 = -2;
 pub const CRYPTO_ERROR_INTERNAL: i32 = -3;
+pub const CRYPTO_ERROR_CANCELLED: i32 = -4;
+
+/// 密钥生成的进度回调：user_data 原样传回，tested 为累计检测过的候选个数
+pub type CryptoProgressCallback = Option<unsafe extern "C" fn(user_data: *mut c_void, tested: u64)>;
+
+// 哈希算法编号
+pub const CRYPTO_HASH_SHA224: i32 = 1;
+pub const CRYPTO_HASH_SHA256: i32 = 2;
+pub const CRYPTO_HASH_SHA384: i32 = 3;
+pub const CRYPTO_HASH_SHA512: i32 = 4;
+pub const CRYPTO_HASH_SHA512_256: i32 = 5;
+pub const CRYPTO_HASH_SHA3_224: i32 = 6;
+pub const CRYPTO_HASH_SHA3_256: i32 = 7;
+pub const CRYPTO_HASH_SHA3_384: i32 = 8;
+pub const CRYPTO_HASH_SHA3_512: i32 = 9;
+pub const CRYPTO_HASH_SHAKE128: i32 = 10;
+pub const CRYPTO_HASH_SHAKE256: i32 = 11;
+pub const CRYPTO_HASH_SHA1: i32 = 12;
+pub const CRYPTO_HASH_SHA512_224: i32 = 13;
+
+// RSA加密填充方式编号
+pub const CRYPTO_RSA_PADDING_PKCS1V15: i32 = 1;
+pub const CRYPTO_RSA_PADDING_OAEP: i32 = 2;
+
+// RSA签名方案编号
+pub const CRYPTO_RSA_SIGNATURE_PKCS1V15: i32 = 1;
+pub const CRYPTO_RSA_SIGNATURE_PSS: i32 = 2;
+
+// RSA 盲签名（RFC 9474）变体编号
+pub const CRYPTO_RSA_BSSA_SHA384_PSS_RANDOMIZED: i32 = 1;
+pub const CRYPTO_RSA_BSSA_SHA384_PSSZERO_RANDOMIZED: i32 = 2;
+pub const CRYPTO_RSA_BSSA_SHA384_PSS_DETERMINISTIC: i32 = 3;
+pub const CRYPTO_RSA_BSSA_SHA384_PSSZERO_DETERMINISTIC: i32 = 4;
+
+// 密钥编码格式编号：PKCS#1，或 PKCS#8 私钥 / SubjectPublicKeyInfo 公钥
+pub const CRYPTO_KEY_FORMAT_PKCS1: i32 = 1;
+pub const CRYPTO_KEY_FORMAT_PKCS8: i32 = 2;
+
+// 密钥输出方式编号
+pub const CRYPTO_KEY_ENCODING_DER: i32 = 1;
+pub const CRYPTO_KEY_ENCODING_PEM: i32 = 2;
+
+// 加密私钥（PKCS#8 PBES2）使用的对称算法编号；OpenSSL 不支持 PBES2 中的 AEAD，只有 CBC 能被 `openssl pkcs8` 读取
+pub const CRYPTO_PBES2_AES128_CBC: i32 = 1;
+pub const CRYPTO_PBES2_AES192_CBC: i32 = 2;
+pub const CRYPTO_PBES2_AES256_CBC: i32 = 3;
+pub const CRYPTO_PBES2_AES128_GCM: i32 = 4;
+pub const CRYPTO_PBES2_AES192_GCM: i32 = 5;
+pub const CRYPTO_PBES2_AES256_GCM: i32 = 6;
 
 // C兼容的结构体定义
 #[repr(C)]
@@ -101,6 +167,115 @@ fn write_string_to_buffer(s: &str, out_buffer: *mut u8, out_len: *mut usize) ->
     write_to_buffer(s.as_bytes(), out_buffer, out_len)
 }
 
+// 辅助函数：把C传入的指针和长度转换为切片，长度为0时允许空指针
+unsafe fn input_slice<'a>(data: *const u8, data_len: usize) -> Option<&'a [u8]> {
+    if data_len == 0 {
+        return Some(&[]);
+    }
+    if data.is_null() {
+        return None;
+    }
+    Some(unsafe { std::slice::from_raw_parts(data, data_len) })
+}
+
+// 辅助函数：把C传入的十进制字符串解析为大整数
+unsafe fn input_biguint(data: *const u8, data_len: usize) -> Option<BigUint> {
+    let slice = unsafe { input_slice(data, data_len) }?;
+    let text = std::str::from_utf8(slice).ok()?;
+    BigUint::from_str(text).ok()
+}
+
+// 辅助函数：解析十进制的 n、e、d 并构造私钥实例；私钥运算需要 e 做基数盲化和结果验算
+unsafe fn input_private_key(
+    n: *const u8, n_len: usize,
+    e: *const u8, e_len: usize,
+    d: *const u8, d_len: usize
+) -> Option<rsa::RSA> {
+    let n = unsafe { input_biguint(n, n_len) }?;
+    let e = unsafe { input_biguint(e, e_len) }?;
+    let d = unsafe { input_biguint(d, d_len) }?;
+    if n.bits() == 0 || e.bits() == 0 {
+        return None;
+    }
+    Some(rsa::RSA::from_parts(n, e, d))
+}
+
+// 辅助函数：输出导入的 RSA 密钥的十进制 n、e、d、p、q，私钥的输出可以直接交给 crypto_rsa_export_private_key
+//
+// 公钥没有 d，没有 CRT 参数的私钥没有 p、q，此时对应的长度置为 0；这三项的缓冲区和长度可以同时为空指针
+unsafe fn write_imported_rsa_key(
+    rsa: &rsa::RSA,
+    n_buffer: *mut u8, n_len: *mut usize,
+    e_buffer: *mut u8, e_len: *mut usize,
+    private_outputs: [(*mut u8, *mut usize); 3]
+) -> i32 {
+    let (n, e) = rsa.public_key();
+    for (value, buffer, len) in [(n, n_buffer, n_len), (e, e_buffer, e_len)] {
+        let result = write_string_to_buffer(&value, buffer, len);
+        if result != CRYPTO_SUCCESS {
+            return result;
+        }
+    }
+
+    let d = rsa.has_private_key().then(|| rsa.private_key().1);
+    let factors = rsa.crt_key().map(|key| (key.p().to_str_radix(10), key.q().to_str_radix(10)));
+    let (p, q) = factors.unzip();
+    for (value, (buffer, len)) in [d, p, q].into_iter().zip(private_outputs) {
+        if len.is_null() {
+            continue;
+        }
+        let result = match value {
+            Some(value) => write_string_to_buffer(&value, buffer, len),
+            None => {
+                unsafe { *len = 0 };
+                CRYPTO_SUCCESS
+            }
+        };
+        if result != CRYPTO_SUCCESS {
+            return result;
+        }
+    }
+    CRYPTO_SUCCESS
+}
+
+// 辅助函数：由调用方提供的种子实例化 HMAC-DRBG，种子不足 32 字节时返回 None
+//
+// personalization 区分不同用途，同一个种子用于 RSA 和 ElGamal 时得到互不相关的随机流
+unsafe fn seeded_drbg(seed: *const u8, seed_len: usize, personalization: &[u8]) -> Option<drbg::HmacDrbg> {
+    let seed = unsafe { input_slice(seed, seed_len) }?;
+    drbg::HmacDrbg::new(seed, &[], personalization).ok()
+}
+
+// 辅助函数：由 FFI 的线程数、进度回调和取消标志构造搜索选项并执行 generate
+//
+// 回调和取消标志都只在调用线程上访问；cancel 非空时必须对齐，且在生成期间保持有效
+unsafe fn with_search_options<T>(
+    threads: usize,
+    progress: CryptoProgressCallback,
+    user_data: *mut c_void,
+    cancel: *const i32,
+    generate: impl FnOnce(&mut numtheory::search::SearchOptions<'_>) -> T
+) -> T {
+    let poll_cancel = || !cancel.is_null() && unsafe { AtomicI32::from_ptr(cancel.cast_mut()) }.load(Ordering::Relaxed) != 0;
+    let cancelled = AtomicBool::new(poll_cancel());
+    let mut report = |tested: u64| {
+        if let Some(callback) = progress {
+            unsafe { callback(user_data, tested) };
+        }
+        if poll_cancel() {
+            cancelled.store(true, Ordering::Relaxed);
+        }
+    };
+
+    let mut options = numtheory::search::SearchOptions {
+        threads,
+        cancel: Some(&cancelled),
+        progress: Some(&mut report),
+        tested: 0,
+    };
+    generate(&mut options)
+}
+
 // 重新导出AES的各种模式函数
 // ECB模式（带填充）
 #[unsafe(no_mangle)]
@@ -490,9 +665,11 @@ pub unsafe extern "C" fn crypto_rsa_generate_keys(
        d_buffer.is_null() || d_len.is_null() {
         return CRYPTO_ERROR_INVALID_PARAMETER;
     }
-    
-    // 生成RSA密钥对
-    let rsa = rsa::RSA::new(bit_length);
+
+    // 生成RSA密钥对，bit_length 为模数 n 的位数
+    let Ok(rsa) = rsa::RSA::new(bit_length) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
     let (n, e) = rsa.public_key();
     let (_, d) = rsa.private_key();
     
@@ -512,6 +689,38 @@ pub unsafe extern "C" fn crypto_rsa_generate_keys(
     write_string_to_buffer(&d, d_buffer, d_len)
 }
 
+/// 由种子确定性地生成 RSA 密钥对（公钥指数 65537），同一个种子和位数总是得到同一个密钥，用于可复现的测试
+///
+/// 种子作为 HMAC-DRBG 的熵输入，至少 32 字节；输出格式同 `crypto_rsa_generate_keys`
+///
+/// # Safety
+/// `seed` 必须指向至少 `seed_len` 字节的可读内存；各输出缓冲区必须至少有对应 `*_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_rsa_generate_keys_from_seed(
+    bit_length: usize,
+    seed: *const u8, seed_len: usize,
+    n_buffer: *mut u8, n_len: *mut usize,
+    e_buffer: *mut u8, e_len: *mut usize,
+    d_buffer: *mut u8, d_len: *mut usize
+) -> i32 {
+    let Some(mut rng) = (unsafe { seeded_drbg(seed, seed_len, b"crypto_rsa_generate_keys_from_seed") }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let Ok(rsa) = rsa::RSA::generate_with_rng(bit_length, &mut rng) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let (n, e) = rsa.public_key();
+    let (_, d) = rsa.private_key();
+
+    for (value, buffer, len) in [(n, n_buffer, n_len), (e, e_buffer, e_len), (d, d_buffer, d_len)] {
+        let result = write_string_to_buffer(&value, buffer, len);
+        if result != CRYPTO_SUCCESS {
+            return result;
+        }
+    }
+    CRYPTO_SUCCESS
+}
+
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn crypto_rsa_encrypt(
     n: *const u8, n_len: usize,
@@ -545,357 +754,2562 @@ pub unsafe extern "C" fn crypto_rsa_encrypt(
     write_to_buffer(&encrypted, out_buffer, out_len)
 }
 
+/// 教科书 RSA 解密（无填充），私钥运算做基数盲化
+///
+/// # Safety
+/// `n`、`e`、`d`（十进制字符串）、`ciphertext` 必须指向对应长度的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn crypto_rsa_decrypt(
     n: *const u8, n_len: usize,
+    e: *const u8, e_len: usize,
     d: *const u8, d_len: usize,
     ciphertext: *const u8, ciphertext_len: usize,
     out_buffer: *mut u8, out_len: *mut usize
 ) -> i32 {
-    if n.is_null() || d.is_null() || ciphertext.is_null() || out_buffer.is_null() || out_len.is_null() {
+    let Some(rsa) = (unsafe { input_private_key(n, n_len, e, e_len, d, d_len) }) else {
         return CRYPTO_ERROR_INVALID_PARAMETER;
-    }
-    
-    // 转换为字符串
-    let n_slice = std::slice::from_raw_parts(n, n_len);
-    let d_slice = std::slice::from_raw_parts(d, d_len);
-    let ciphertext_slice = std::slice::from_raw_parts(ciphertext, ciphertext_len);
-    
-    let n_str = match std::str::from_utf8(n_slice) {
-        Ok(s) => s,
-        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
     };
-    
-    let d_str = match std::str::from_utf8(d_slice) {
-        Ok(s) => s,
-        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+
+    let ciphertext_slice = match unsafe { input_slice(ciphertext, ciphertext_len) } {
+        Some(c) => c,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
     };
-    
-    // 创建RSA实例并解密
-    let rsa = rsa::RSA::from_private_key(n_str, d_str);
-    
-    let decrypted = rsa.decrypt(ciphertext_slice);
-    write_to_buffer(&decrypted, out_buffer, out_len)
+
+    match rsa.try_decrypt(ciphertext_slice) {
+        Ok(decrypted) => write_to_buffer(&decrypted, out_buffer, out_len),
+        Err(_) => CRYPTO_ERROR_INVALID_PARAMETER,
+    }
 }
 
-// 导出ElGamal相关函数
+/// 生成带 CRT 参数的 RSA 密钥对，所有分量以十进制字符串输出
+///
+/// # Safety
+/// 每个输出缓冲区必须至少有对应 `*_len` 字节可写
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn crypto_elgamal_generate_keys(
+pub unsafe extern "C" fn crypto_rsa_generate_crt_keys(
     bit_length: usize,
+    n_buffer: *mut u8, n_len: *mut usize,
+    e_buffer: *mut u8, e_len: *mut usize,
+    d_buffer: *mut u8, d_len: *mut usize,
     p_buffer: *mut u8, p_len: *mut usize,
-    g_buffer: *mut u8, g_len: *mut usize,
-    y_buffer: *mut u8, y_len: *mut usize,
-    x_buffer: *mut u8, x_len: *mut usize
+    q_buffer: *mut u8, q_len: *mut usize,
+    dp_buffer: *mut u8, dp_len: *mut usize,
+    dq_buffer: *mut u8, dq_len: *mut usize,
+    qinv_buffer: *mut u8, qinv_len: *mut usize
 ) -> i32 {
-    if p_buffer.is_null() || p_len.is_null() ||
-       g_buffer.is_null() || g_len.is_null() ||
-       y_buffer.is_null() || y_len.is_null() ||
-       x_buffer.is_null() || x_len.is_null() {
+    let Ok(key) = Rsa::RsaPrivateKey::generate(bit_length) else {
         return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let outputs = [
+        (key.n(), n_buffer, n_len),
+        (key.e(), e_buffer, e_len),
+        (key.d(), d_buffer, d_len),
+        (key.p(), p_buffer, p_len),
+        (key.q(), q_buffer, q_len),
+        (key.dp(), dp_buffer, dp_len),
+        (key.dq(), dq_buffer, dq_len),
+        (key.qinv(), qinv_buffer, qinv_len),
+    ];
+
+    for (value, buffer, len) in outputs {
+        let result = write_string_to_buffer(&value.to_str_radix(10), buffer, len);
+        if result != CRYPTO_SUCCESS {
+            return result;
+        }
     }
-    
-    // 生成ElGamal密钥对
-    let keys = elgamal::ElGamalKeys::new(bit_length);
-    let (p, g, y) = keys.public_key();
-    let (_, _, x) = keys.private_key();
-    
-    // 写入p值
-    let result = write_string_to_buffer(&p, p_buffer, p_len);
-    if result != CRYPTO_SUCCESS {
-        return result;
-    }
-    
-    // 写入g值
-    let result = write_string_to_buffer(&g, g_buffer, g_len);
-    if result != CRYPTO_SUCCESS {
-        return result;
-    }
-    
-    // 写入y值
-    let result = write_string_to_buffer(&y, y_buffer, y_len);
-    if result != CRYPTO_SUCCESS {
-        return result;
-    }
-    
-    // 写入x值
-    write_string_to_buffer(&x, x_buffer, x_len)
+    CRYPTO_SUCCESS
 }
 
+/// 使用 CRT 参数解密（无填充），结果验算失败时返回 CRYPTO_ERROR_INTERNAL
+///
+/// # Safety
+/// 各十进制字符串参数和 `ciphertext` 必须指向对应长度的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn crypto_elgamal_encrypt(
+pub unsafe extern "C" fn crypto_rsa_decrypt_crt(
+    n: *const u8, n_len: usize,
+    e: *const u8, e_len: usize,
     p: *const u8, p_len: usize,
-    g: *const u8, g_len: usize,
-    y: *const u8, y_len: usize,
-    message: *const u8, message_len: usize,
-    c1_buffer: *mut u8, c1_len: *mut usize,
-    c2_buffer: *mut u8, c2_len: *mut usize
+    q: *const u8, q_len: usize,
+    dp: *const u8, dp_len: usize,
+    dq: *const u8, dq_len: usize,
+    qinv: *const u8, qinv_len: usize,
+    ciphertext: *const u8, ciphertext_len: usize,
+    out_buffer: *mut u8, out_len: *mut usize
 ) -> i32 {
-    if p.is_null() || g.is_null() || y.is_null() || message.is_null() ||
-       c1_buffer.is_null() || c1_len.is_null() ||
-       c2_buffer.is_null() || c2_len.is_null() {
-        return CRYPTO_ERROR_INVALID_PARAMETER;
-    }
-    
-    // 转换为字符串
-    let p_slice = std::slice::from_raw_parts(p, p_len);
-    let g_slice = std::slice::from_raw_parts(g, g_len);
-    let y_slice = std::slice::from_raw_parts(y, y_len);
-    let message_slice = std::slice::from_raw_parts(message, message_len);
-    
-    let p_str = match std::str::from_utf8(p_slice) {
-        Ok(s) => s,
-        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+    let components = unsafe {
+        [
+            input_biguint(n, n_len),
+            input_biguint(e, e_len),
+            input_biguint(p, p_len),
+            input_biguint(q, q_len),
+            input_biguint(dp, dp_len),
+            input_biguint(dq, dq_len),
+            input_biguint(qinv, qinv_len),
+        ]
     };
-    
-    let g_str = match std::str::from_utf8(g_slice) {
-        Ok(s) => s,
-        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+    let [Some(n), Some(e), Some(p), Some(q), Some(dp), Some(dq), Some(qinv)] = components else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
     };
-    
-    let y_str = match std::str::from_utf8(y_slice) {
-        Ok(s) => s,
-        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+
+    let key = match Rsa::RsaPrivateKey::from_crt_components(n, e, p, q, dp, dq, qinv) {
+        Some(key) => key,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
     };
-    
-    let message_str = match std::str::from_utf8(message_slice) {
-        Ok(s) => s,
-        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+
+    let ciphertext_slice = match unsafe { input_slice(ciphertext, ciphertext_len) } {
+        Some(c) => c,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
     };
-    
-    // 创建ElGamal实例并加密
-    let keys = elgamal::ElGamalKeys::from_public_params(p_str, g_str, y_str);
-    
-    // 将消息转换为BigUint
-    let message_hash = elgamal::sha256_to_biguint(message_str);
-    
-    // 加密消息
-    let ciphertext = keys.encrypt(&message_hash);
-    
-    // 写入c1值
-    let c1_str = ciphertext.c1.to_str_radix(10);
-    let result = write_string_to_buffer(&c1_str, c1_buffer, c1_len);
-    if result != CRYPTO_SUCCESS {
-        return result;
+
+    match key.decrypt(ciphertext_slice) {
+        Some(decrypted) => write_to_buffer(&decrypted, out_buffer, out_len),
+        None => CRYPTO_ERROR_INTERNAL,
     }
-    
-    // 写入c2值
-    let c2_str = ciphertext.c2.to_str_radix(10);
-    write_string_to_buffer(&c2_str, c2_buffer, c2_len)
 }
 
+/// 生成多素数 RSA 私钥（公钥指数 65537），以 PKCS#1 RSAPrivateKey DER 输出
+///
+/// prime_count 为 2 到 4；小于 1024 位只允许 2 个，小于 4096 位最多 3 个
+///
+/// # Safety
+/// `out_buffer` 必须至少有 `*out_len` 字节可写
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn crypto_elgamal_decrypt(
-    p: *const u8, p_len: usize,
-    g: *const u8, g_len: usize,
-    x: *const u8, x_len: usize,
-    c1: *const u8, c1_len: usize,
-    c2: *const u8, c2_len: usize,
+pub unsafe extern "C" fn crypto_rsa_generate_multi_prime_key(
+    bit_length: usize,
+    prime_count: usize,
     out_buffer: *mut u8, out_len: *mut usize
 ) -> i32 {
-    if p.is_null() || g.is_null() || x.is_null() || 
-       c1.is_null() || c2.is_null() ||
-       out_buffer.is_null() || out_len.is_null() {
-        return CRYPTO_ERROR_INVALID_PARAMETER;
+    let e = BigUint::from(Rsa::private_key::DEFAULT_PUBLIC_EXPONENT);
+    match Rsa::RsaPrivateKey::generate_multi_prime(bit_length, prime_count, &e, &mut rand::rngs::OsRng) {
+        Ok(key) => write_to_buffer(&key.to_pkcs1_der(), out_buffer, out_len),
+        Err(_) => CRYPTO_ERROR_INVALID_PARAMETER,
     }
-    
-    // 转换为字符串
-    let p_slice = std::slice::from_raw_parts(p, p_len);
-    let g_slice = std::slice::from_raw_parts(g, g_len);
-    let x_slice = std::slice::from_raw_parts(x, x_len);
-    let c1_slice = std::slice::from_raw_parts(c1, c1_len);
-    let c2_slice = std::slice::from_raw_parts(c2, c2_len);
-    
-    let p_str = match std::str::from_utf8(p_slice) {
-        Ok(s) => s,
-        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
-    };
-    
-    let g_str = match std::str::from_utf8(g_slice) {
-        Ok(s) => s,
-        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+}
+
+/// 使用 PKCS#1 DER 编码的私钥解密（无填充），支持多素数私钥；结果验算失败时返回 CRYPTO_ERROR_INTERNAL
+///
+/// # Safety
+/// `key_der`、`ciphertext` 必须指向对应长度的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_rsa_decrypt_der(
+    key_der: *const u8, key_der_len: usize,
+    ciphertext: *const u8, ciphertext_len: usize,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    let (key_slice, ciphertext_slice) = match unsafe { (input_slice(key_der, key_der_len), input_slice(ciphertext, ciphertext_len)) } {
+        (Some(k), Some(c)) => (k, c),
+        _ => return CRYPTO_ERROR_INVALID_PARAMETER,
     };
-    
-    let x_str = match std::str::from_utf8(x_slice) {
-        Ok(s) => s,
-        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+
+    let key = match Rsa::RsaPrivateKey::from_pkcs1_der(key_slice) {
+        Some(key) => key,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
     };
-    
-    let c1_str = match std::str::from_utf8(c1_slice) {
-        Ok(s) => s,
-        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+
+    match key.decrypt(ciphertext_slice) {
+        Some(decrypted) => write_to_buffer(&decrypted, out_buffer, out_len),
+        None => CRYPTO_ERROR_INTERNAL,
+    }
+}
+
+/// RSAES-OAEP 加密，密文长度等于模长
+///
+/// # Safety
+/// `n`、`e`（十进制字符串）、`label`、`message` 必须指向对应长度的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_rsa_oaep_encrypt(
+    n: *const u8, n_len: usize,
+    e: *const u8, e_len: usize,
+    hash_algorithm: i32,
+    label: *const u8, label_len: usize,
+    message: *const u8, message_len: usize,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    let algorithm = match hash::HashAlgorithm::from_id(hash_algorithm) {
+        Some(a) => a,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
     };
-    
-    let c2_str = match std::str::from_utf8(c2_slice) {
-        Ok(s) => s,
-        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+
+    let (Some(n), Some(e)) = (unsafe { input_biguint(n, n_len) }, unsafe { input_biguint(e, e_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
     };
-    
-    // 创建ElGamal实例
-    let keys = elgamal::ElGamalKeys::from_private_params(p_str, g_str, x_str);
-    
-    // 解析c1和c2
-    let c1_big = match BigUint::from_str(c1_str) {
-        Ok(n) => n,
-        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+
+    let (label_slice, message_slice) = match unsafe { (input_slice(label, label_len), input_slice(message, message_len)) } {
+        (Some(l), Some(m)) => (l, m),
+        _ => return CRYPTO_ERROR_INVALID_PARAMETER,
     };
-    
-    let c2_big = match BigUint::from_str(c2_str) {
-        Ok(n) => n,
+
+    let rsa = rsa::RSA::from_public_parts(n, e);
+    match rsa.encrypt_oaep(algorithm, message_slice, label_slice) {
+        Ok(encrypted) => write_to_buffer(&encrypted, out_buffer, out_len),
+        Err(_) => CRYPTO_ERROR_INVALID_PARAMETER,
+    }
+}
+
+/// RSAES-OAEP 解密；填充错误、标签不匹配等所有解密失败都返回 CRYPTO_ERROR_INVALID_PARAMETER
+///
+/// # Safety
+/// `n`、`e`、`d`（十进制字符串）、`label`、`ciphertext` 必须指向对应长度的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_rsa_oaep_decrypt(
+    n: *const u8, n_len: usize,
+    e: *const u8, e_len: usize,
+    d: *const u8, d_len: usize,
+    hash_algorithm: i32,
+    label: *const u8, label_len: usize,
+    ciphertext: *const u8, ciphertext_len: usize,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    let algorithm = match hash::HashAlgorithm::from_id(hash_algorithm) {
+        Some(a) => a,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    let Some(rsa) = (unsafe { input_private_key(n, n_len, e, e_len, d, d_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    let (label_slice, ciphertext_slice) = match unsafe { (input_slice(label, label_len), input_slice(ciphertext, ciphertext_len)) } {
+        (Some(l), Some(c)) => (l, c),
+        _ => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    match rsa.decrypt_oaep(algorithm, ciphertext_slice, label_slice) {
+        Ok(decrypted) => write_to_buffer(&decrypted, out_buffer, out_len),
+        Err(_) => CRYPTO_ERROR_INVALID_PARAMETER,
+    }
+}
+
+/// 按指定填充方式加密（CRYPTO_RSA_PADDING_*），OAEP 使用空标签，PKCS#1 v1.5 忽略 hash_algorithm
+///
+/// # Safety
+/// `n`、`e`（十进制字符串）、`message` 必须指向对应长度的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_rsa_encrypt_padded(
+    n: *const u8, n_len: usize,
+    e: *const u8, e_len: usize,
+    padding: i32, hash_algorithm: i32,
+    message: *const u8, message_len: usize,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    let padding = match Rsa::RsaPadding::from_id(padding, hash_algorithm) {
+        Some(p) => p,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    let (Some(n), Some(e)) = (unsafe { input_biguint(n, n_len) }, unsafe { input_biguint(e, e_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    let message_slice = match unsafe { input_slice(message, message_len) } {
+        Some(m) => m,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    let rsa = rsa::RSA::from_public_parts(n, e);
+    match rsa.encrypt_padded(padding, message_slice) {
+        Ok(encrypted) => write_to_buffer(&encrypted, out_buffer, out_len),
+        Err(_) => CRYPTO_ERROR_INVALID_PARAMETER,
+    }
+}
+
+/// 按指定填充方式解密
+///
+/// PKCS#1 v1.5 使用隐式拒绝：填充错误时仍返回 CRYPTO_SUCCESS 和一段伪随机消息，
+/// 调用方必须依靠后续的完整性校验发现错误
+///
+/// # Safety
+/// `n`、`e`、`d`（十进制字符串）、`ciphertext` 必须指向对应长度的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_rsa_decrypt_padded(
+    n: *const u8, n_len: usize,
+    e: *const u8, e_len: usize,
+    d: *const u8, d_len: usize,
+    padding: i32, hash_algorithm: i32,
+    ciphertext: *const u8, ciphertext_len: usize,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    let padding = match Rsa::RsaPadding::from_id(padding, hash_algorithm) {
+        Some(p) => p,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    let Some(rsa) = (unsafe { input_private_key(n, n_len, e, e_len, d, d_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    let ciphertext_slice = match unsafe { input_slice(ciphertext, ciphertext_len) } {
+        Some(c) => c,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    match rsa.decrypt_padded(padding, ciphertext_slice) {
+        Ok(decrypted) => write_to_buffer(&decrypted, out_buffer, out_len),
+        Err(_) => CRYPTO_ERROR_INVALID_PARAMETER,
+    }
+}
+
+/// RSA 签名（CRYPTO_RSA_SIGNATURE_*），salt_len 仅用于 PSS，签名长度等于模长
+///
+/// # Safety
+/// `n`、`e`、`d`（十进制字符串）、`message` 必须指向对应长度的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_rsa_sign(
+    n: *const u8, n_len: usize,
+    e: *const u8, e_len: usize,
+    d: *const u8, d_len: usize,
+    scheme: i32, hash_algorithm: i32, salt_len: usize,
+    message: *const u8, message_len: usize,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    let scheme = match Rsa::SignatureScheme::from_id(scheme, hash_algorithm, salt_len) {
+        Some(s) => s,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    let Some(rsa) = (unsafe { input_private_key(n, n_len, e, e_len, d, d_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    let message_slice = match unsafe { input_slice(message, message_len) } {
+        Some(m) => m,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    match rsa.sign(scheme, message_slice) {
+        Ok(signature) => write_to_buffer(&signature, out_buffer, out_len),
+        Err(_) => CRYPTO_ERROR_INVALID_PARAMETER,
+    }
+}
+
+/// RSA 验签，签名有效时返回 CRYPTO_SUCCESS
+///
+/// # Safety
+/// `n`、`e`（十进制字符串）、`message`、`signature` 必须指向对应长度的可读内存
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_rsa_verify(
+    n: *const u8, n_len: usize,
+    e: *const u8, e_len: usize,
+    scheme: i32, hash_algorithm: i32, salt_len: usize,
+    message: *const u8, message_len: usize,
+    signature: *const u8, signature_len: usize
+) -> i32 {
+    let scheme = match Rsa::SignatureScheme::from_id(scheme, hash_algorithm, salt_len) {
+        Some(s) => s,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    let (Some(n), Some(e)) = (unsafe { input_biguint(n, n_len) }, unsafe { input_biguint(e, e_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    let (message_slice, signature_slice) = match unsafe { (input_slice(message, message_len), input_slice(signature, signature_len)) } {
+        (Some(m), Some(s)) => (m, s),
+        _ => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    let rsa = rsa::RSA::from_public_parts(n, e);
+    if rsa.verify(scheme, message_slice, signature_slice) {
+        CRYPTO_SUCCESS
+    } else {
+        CRYPTO_ERROR_INVALID_PARAMETER
+    }
+}
+
+/// 使用指定公钥指数生成 RSA 密钥对（e 为奇数且 3 <= e < 2^256），n、d 以十进制字符串输出
+///
+/// # Safety
+/// `e` 必须指向 `e_len` 字节的可读内存；各输出缓冲区必须至少有对应 `*_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_rsa_generate_keys_with_exponent(
+    bit_length: usize,
+    e: *const u8, e_len: usize,
+    n_buffer: *mut u8, n_len: *mut usize,
+    d_buffer: *mut u8, d_len: *mut usize
+) -> i32 {
+    let Some(e) = (unsafe { input_biguint(e, e_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    let rsa = match rsa::RSA::generate_with_exponent(bit_length, &e, &mut rand::rngs::OsRng) {
+        Ok(rsa) => rsa,
         Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
     };
-    
-    let ciphertext = elgamal::ElGamalCiphertext {
-        c1: c1_big,
-        c2: c2_big,
+    let (n, d) = rsa.private_key();
+
+    // 写入n值
+    let result = write_string_to_buffer(&n, n_buffer, n_len);
+    if result != CRYPTO_SUCCESS {
+        return result;
+    }
+
+    // 写入d值
+    write_string_to_buffer(&d, d_buffer, d_len)
+}
+
+/// 校验导入的 RSA 密钥，d、p、q、dp、dq、qinv 可省略（长度传 0）；校验失败时 `*reason` 为具体原因编号
+///
+/// 给出 dp、dq、qinv 时三者必须和 p、q 一起提供，私钥由 `RsaPrivateKey::from_crt_components` 构造，
+/// 此时 d 可以省略；同时给出 d 时要求 d 与 dp、dq 一致。
+///
+/// # Safety
+/// 各十进制字符串参数必须指向对应长度的可读内存；`reason` 可以为空，否则必须可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_rsa_validate_key(
+    n: *const u8, n_len: usize,
+    e: *const u8, e_len: usize,
+    d: *const u8, d_len: usize,
+    p: *const u8, p_len: usize,
+    q: *const u8, q_len: usize,
+    dp: *const u8, dp_len: usize,
+    dq: *const u8, dq_len: usize,
+    qinv: *const u8, qinv_len: usize,
+    reason: *mut i32
+) -> i32 {
+    let parse_optional = |ptr: *const u8, len: usize| -> Result<Option<BigUint>, ()> {
+        if len == 0 {
+            return Ok(None);
+        }
+        unsafe { input_biguint(ptr, len) }.map(Some).ok_or(())
+    };
+
+    let (Some(n), Some(e)) = (unsafe { input_biguint(n, n_len) }, unsafe { input_biguint(e, e_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let (Ok(d), Ok(p), Ok(q)) = (parse_optional(d, d_len), parse_optional(p, p_len), parse_optional(q, q_len)) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let (Ok(dp), Ok(dq), Ok(qinv)) = (parse_optional(dp, dp_len), parse_optional(dq, dq_len), parse_optional(qinv, qinv_len)) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    let result = match (d, p, q, dp, dq, qinv) {
+        (d, Some(p), Some(q), Some(dp), Some(dq), Some(qinv)) => {
+            // 给出的 d 只需与 dp、dq 一致，不要求等于 e 模 λ(n) 的逆
+            let matches_d = |key: &Rsa::RsaPrivateKey, d: BigUint| {
+                &d % (key.p() - 1u32) == *key.dp() && &d % (key.q() - 1u32) == *key.dq()
+            };
+            match Rsa::RsaPrivateKey::from_crt_components(n, e, p, q, dp, dq, qinv) {
+                Some(key) if d.is_none_or(|d| matches_d(&key, d)) => key.validate(),
+                _ => Err(Rsa::KeyError::InconsistentCrt),
+            }
+        }
+        (Some(d), Some(p), Some(q), None, None, None) => match Rsa::RsaPrivateKey::from_components(n, e, d, p, q) {
+            Some(key) => key.validate(),
+            None => Err(Rsa::KeyError::InconsistentCrt),
+        },
+        (Some(d), None, None, None, None, None) => Rsa::validation::check_public_key(&n, &e)
+            .and_then(|_| Rsa::validation::pairwise_test(&n, &e, &d)),
+        (None, None, None, None, None, None) => Rsa::validation::check_public_key(&n, &e),
+        _ => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    let code = match result {
+        Ok(()) => 0,
+        Err(err) => err.code(),
+    };
+    if !reason.is_null() {
+        unsafe { *reason = code };
+    }
+
+    if code == 0 {
+        CRYPTO_SUCCESS
+    } else {
+        CRYPTO_ERROR_INVALID_PARAMETER
+    }
+}
+
+/// 混合加密：RSA-KEM 封装随机密钥，AES-128-GCM 加密任意长度的消息
+///
+/// 输出信封长度为 1 + 模长 + message_len + 16，格式见 `Rsa::envelope`
+///
+/// 公钥无效或模数不足 `MIN_ENVELOPE_MODULUS_BITS` 位时返回 CRYPTO_ERROR_INVALID_PARAMETER
+///
+/// # Safety
+/// `n`、`e`（十进制字符串）、`message` 必须指向对应长度的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_rsa_seal(
+    n: *const u8, n_len: usize,
+    e: *const u8, e_len: usize,
+    message: *const u8, message_len: usize,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    let (Some(n), Some(e)) = (unsafe { input_biguint(n, n_len) }, unsafe { input_biguint(e, e_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    let message_slice = match unsafe { input_slice(message, message_len) } {
+        Some(m) => m,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    let rsa = rsa::RSA::from_public_parts(n, e);
+    match rsa.seal(message_slice) {
+        Ok(envelope) => write_to_buffer(&envelope, out_buffer, out_len),
+        Err(_) => CRYPTO_ERROR_INVALID_PARAMETER,
+    }
+}
+
+/// 打开 `crypto_rsa_seal` 生成的信封；格式错误、密钥不匹配或数据被篡改都返回 CRYPTO_ERROR_INVALID_PARAMETER
+///
+/// # Safety
+/// `n`、`e`、`d`（十进制字符串）、`envelope` 必须指向对应长度的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_rsa_open(
+    n: *const u8, n_len: usize,
+    e: *const u8, e_len: usize,
+    d: *const u8, d_len: usize,
+    envelope: *const u8, envelope_len: usize,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    let Some(rsa) = (unsafe { input_private_key(n, n_len, e, e_len, d, d_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    let envelope_slice = match unsafe { input_slice(envelope, envelope_len) } {
+        Some(env) => env,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    match rsa.open(envelope_slice) {
+        Ok(message) => write_to_buffer(&message, out_buffer, out_len),
+        Err(_) => CRYPTO_ERROR_INVALID_PARAMETER,
+    }
+}
+
+/// 导出 RSA 私钥为 DER 或 PEM（format 为 CRYPTO_KEY_FORMAT_*，encoding 为 CRYPTO_KEY_ENCODING_*）
+///
+/// # Safety
+/// `n`、`e`、`d`、`p`、`q`（十进制字符串）必须指向对应长度的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_rsa_export_private_key(
+    n: *const u8, n_len: usize,
+    e: *const u8, e_len: usize,
+    d: *const u8, d_len: usize,
+    p: *const u8, p_len: usize,
+    q: *const u8, q_len: usize,
+    format: i32, encoding: i32,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    let Some(format) = Rsa::KeyFormat::from_id(format) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    let components = unsafe {
+        [
+            input_biguint(n, n_len),
+            input_biguint(e, e_len),
+            input_biguint(d, d_len),
+            input_biguint(p, p_len),
+            input_biguint(q, q_len),
+        ]
+    };
+    let [Some(n), Some(e), Some(d), Some(p), Some(q)] = components else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let key = match Rsa::RsaPrivateKey::from_components(n, e, d, p, q) {
+        Some(key) => key,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    let rsa = rsa::RSA::from_crt_key(key);
+    let output = match encoding {
+        CRYPTO_KEY_ENCODING_DER => rsa.to_der(format),
+        CRYPTO_KEY_ENCODING_PEM => rsa.to_pem(format).map(String::into_bytes),
+        _ => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    match output {
+        Ok(data) => write_to_buffer(&data, out_buffer, out_len),
+        Err(_) => CRYPTO_ERROR_INTERNAL,
+    }
+}
+
+/// 导出 RSA 公钥为 DER 或 PEM（format 为 CRYPTO_KEY_FORMAT_*，encoding 为 CRYPTO_KEY_ENCODING_*）
+///
+/// # Safety
+/// `n`、`e`（十进制字符串）必须指向对应长度的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_rsa_export_public_key(
+    n: *const u8, n_len: usize,
+    e: *const u8, e_len: usize,
+    format: i32, encoding: i32,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    let Some(format) = Rsa::KeyFormat::from_id(format) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    let (Some(n), Some(e)) = (unsafe { input_biguint(n, n_len) }, unsafe { input_biguint(e, e_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    let rsa = rsa::RSA::from_public_parts(n, e);
+    let output = match encoding {
+        CRYPTO_KEY_ENCODING_DER => rsa.to_public_der(format),
+        CRYPTO_KEY_ENCODING_PEM => rsa.to_public_pem(format).into_bytes(),
+        _ => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    write_to_buffer(&output, out_buffer, out_len)
+}
+
+/// 导入 DER 或 PEM 编码的 RSA 私钥或公钥（PKCS#1、PKCS#8、SubjectPublicKeyInfo），输出十进制的 n、e、d、p、q
+///
+/// 私钥的输出可以直接交给 `crypto_rsa_export_private_key` 重新导出；多素数私钥只输出前两个素因子，无法重新导出。
+/// 公钥没有 d、p、q，此时对应的长度置为 0；这三项的缓冲区和长度可以同时为空指针
+///
+/// # Safety
+/// `data` 必须指向 `data_len` 字节的可读内存；每个输出缓冲区必须至少有对应 `*_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_rsa_import_key(
+    data: *const u8, data_len: usize,
+    n_buffer: *mut u8, n_len: *mut usize,
+    e_buffer: *mut u8, e_len: *mut usize,
+    d_buffer: *mut u8, d_len: *mut usize,
+    p_buffer: *mut u8, p_len: *mut usize,
+    q_buffer: *mut u8, q_len: *mut usize
+) -> i32 {
+    let data_slice = match unsafe { input_slice(data, data_len) } {
+        Some(d) => d,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    let rsa = if encoding::pem::is_pem(data_slice) {
+        std::str::from_utf8(data_slice).ok().and_then(rsa::RSA::from_pem)
+    } else {
+        rsa::RSA::from_der(data_slice)
+    };
+    let Some(rsa) = rsa else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    unsafe {
+        write_imported_rsa_key(
+            &rsa,
+            n_buffer, n_len,
+            e_buffer, e_len,
+            [(d_buffer, d_len), (p_buffer, p_len), (q_buffer, q_len)],
+        )
+    }
+}
+
+/// 导出口令加密的 RSA 私钥（PKCS#8 EncryptedPrivateKeyInfo，PBES2），可用 `openssl pkcs8` 读取
+///
+/// cipher 为 CRYPTO_PBES2_*，只有 CBC 能被 OpenSSL 读取；iterations 为 PBKDF2-HMAC-SHA256 迭代次数
+/// （0 表示默认值，不能超过 10,000,000），encoding 为 CRYPTO_KEY_ENCODING_*
+///
+/// # Safety
+/// `n`、`e`、`d`、`p`、`q`（十进制字符串）和 `password` 必须指向对应长度的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_rsa_export_encrypted_private_key(
+    n: *const u8, n_len: usize,
+    e: *const u8, e_len: usize,
+    d: *const u8, d_len: usize,
+    p: *const u8, p_len: usize,
+    q: *const u8, q_len: usize,
+    password: *const u8, password_len: usize,
+    cipher: i32, iterations: u32, encoding: i32,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    let Some(params) = pbes2_params(cipher, iterations) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let Some(password) = (unsafe { input_slice(password, password_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    let components = unsafe {
+        [
+            input_biguint(n, n_len),
+            input_biguint(e, e_len),
+            input_biguint(d, d_len),
+            input_biguint(p, p_len),
+            input_biguint(q, q_len),
+        ]
+    };
+    let [Some(n), Some(e), Some(d), Some(p), Some(q)] = components else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let key = match Rsa::RsaPrivateKey::from_components(n, e, d, p, q) {
+        Some(key) => key,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    let rsa = rsa::RSA::from_crt_key(key);
+    let output = match encoding {
+        CRYPTO_KEY_ENCODING_DER => rsa.to_encrypted_der(password, &params),
+        CRYPTO_KEY_ENCODING_PEM => rsa.to_encrypted_pem(password, &params).map(String::into_bytes),
+        _ => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    match output {
+        Some(data) => write_to_buffer(&data, out_buffer, out_len),
+        None => CRYPTO_ERROR_INTERNAL,
+    }
+}
+
+/// 导入口令加密的 RSA 私钥（DER 或 PEM 的 EncryptedPrivateKeyInfo），输出十进制的 n、e、d、p、q
+///
+/// 输出可以直接交给 `crypto_rsa_export_encrypted_private_key` 或 `crypto_rsa_export_private_key` 重新导出；
+/// 多素数私钥只输出前两个素因子。PBKDF2 迭代次数超过 10,000,000 的文件会被拒绝
+///
+/// # Safety
+/// `data`、`password` 必须指向对应长度的可读内存；每个输出缓冲区必须至少有对应 `*_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_rsa_import_encrypted_private_key(
+    data: *const u8, data_len: usize,
+    password: *const u8, password_len: usize,
+    n_buffer: *mut u8, n_len: *mut usize,
+    e_buffer: *mut u8, e_len: *mut usize,
+    d_buffer: *mut u8, d_len: *mut usize,
+    p_buffer: *mut u8, p_len: *mut usize,
+    q_buffer: *mut u8, q_len: *mut usize
+) -> i32 {
+    let (Some(data_slice), Some(password)) = (unsafe { input_slice(data, data_len) }, unsafe { input_slice(password, password_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    let rsa = if encoding::pem::is_pem(data_slice) {
+        std::str::from_utf8(data_slice).ok().and_then(|text| rsa::RSA::from_encrypted_pem(text, password))
+    } else {
+        rsa::RSA::from_encrypted_der(data_slice, password)
+    };
+    let Some(rsa) = rsa else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    unsafe {
+        write_imported_rsa_key(
+            &rsa,
+            n_buffer, n_len,
+            e_buffer, e_len,
+            [(d_buffer, d_len), (p_buffer, p_len), (q_buffer, q_len)],
+        )
+    }
+}
+
+/// 导出 RSA 公钥 JWK（RFC 7517，UTF-8 的 JSON 文本，不以 '\0' 结尾）
+///
+/// # Safety
+/// `n`、`e`（十进制字符串）必须指向对应长度的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_rsa_export_public_jwk(
+    n: *const u8, n_len: usize,
+    e: *const u8, e_len: usize,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    let (Some(n), Some(e)) = (unsafe { input_biguint(n, n_len) }, unsafe { input_biguint(e, e_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let rsa = rsa::RSA::from_public_parts(n, e);
+    write_string_to_buffer(&rsa.to_public_jwk(), out_buffer, out_len)
+}
+
+/// 导出 RSA 私钥 JWK，包含 d 和全部 CRT 参数
+///
+/// # Safety
+/// `n`、`e`、`d`、`p`、`q`（十进制字符串）必须指向对应长度的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_rsa_export_private_jwk(
+    n: *const u8, n_len: usize,
+    e: *const u8, e_len: usize,
+    d: *const u8, d_len: usize,
+    p: *const u8, p_len: usize,
+    q: *const u8, q_len: usize,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    let components = unsafe {
+        [
+            input_biguint(n, n_len),
+            input_biguint(e, e_len),
+            input_biguint(d, d_len),
+            input_biguint(p, p_len),
+            input_biguint(q, q_len),
+        ]
+    };
+    let [Some(n), Some(e), Some(d), Some(p), Some(q)] = components else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let key = match Rsa::RsaPrivateKey::from_components(n, e, d, p, q) {
+        Some(key) => key,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    match rsa::RSA::from_crt_key(key).to_jwk() {
+        Ok(jwk) => write_string_to_buffer(&jwk, out_buffer, out_len),
+        Err(_) => CRYPTO_ERROR_INTERNAL,
+    }
+}
+
+/// 导入 RSA 公钥或私钥 JWK，输出十进制的 n、e、d、p、q
+///
+/// 带 CRT 成员的私钥输出可以直接交给 `crypto_rsa_export_private_jwk` 重新导出；多素数私钥只输出前两个素因子。
+/// 公钥没有 d、p、q，只含 n、e、d 的私钥没有 p、q，此时对应的长度置为 0；这三项的缓冲区和长度可以同时为空指针
+///
+/// # Safety
+/// `data` 必须指向 `data_len` 字节的可读内存；每个输出缓冲区必须至少有对应 `*_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_rsa_import_jwk(
+    data: *const u8, data_len: usize,
+    n_buffer: *mut u8, n_len: *mut usize,
+    e_buffer: *mut u8, e_len: *mut usize,
+    d_buffer: *mut u8, d_len: *mut usize,
+    p_buffer: *mut u8, p_len: *mut usize,
+    q_buffer: *mut u8, q_len: *mut usize
+) -> i32 {
+    let data_slice = match unsafe { input_slice(data, data_len) } {
+        Some(d) => d,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    let Some(rsa) = std::str::from_utf8(data_slice).ok().and_then(rsa::RSA::from_jwk) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    unsafe {
+        write_imported_rsa_key(
+            &rsa,
+            n_buffer, n_len,
+            e_buffer, e_len,
+            [(d_buffer, d_len), (p_buffer, p_len), (q_buffer, q_len)],
+        )
+    }
+}
+
+/// 盲签名第一步（客户端）：准备消息，随机化变体输出 32 字节随机前缀 || message，确定性变体原样输出
+///
+/// # Safety
+/// `message` 必须指向 `message_len` 字节的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_rsa_blind_prepare(
+    variant: i32,
+    message: *const u8, message_len: usize,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    let Some(variant) = Rsa::BlindVariant::from_id(variant) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let Some(message_slice) = (unsafe { input_slice(message, message_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    write_to_buffer(&Rsa::blind::prepare(variant, message_slice), out_buffer, out_len)
+}
+
+/// 盲签名第二步（客户端）：盲化准备好的消息，输出盲化消息和 inv（均为模长字节）
+///
+/// inv 是客户端的秘密状态，finalize 时需要，不能发送给签名方
+///
+/// # Safety
+/// `n`、`e`（十进制字符串）、`prepared_msg` 必须指向对应长度的可读内存；各输出缓冲区必须至少有对应 `*_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_rsa_blind(
+    n: *const u8, n_len: usize,
+    e: *const u8, e_len: usize,
+    variant: i32,
+    prepared_msg: *const u8, prepared_msg_len: usize,
+    blinded_buffer: *mut u8, blinded_len: *mut usize,
+    inv_buffer: *mut u8, inv_len: *mut usize
+) -> i32 {
+    let Some(variant) = Rsa::BlindVariant::from_id(variant) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let (Some(n), Some(e)) = (unsafe { input_biguint(n, n_len) }, unsafe { input_biguint(e, e_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let Some(prepared_slice) = (unsafe { input_slice(prepared_msg, prepared_msg_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    let rsa = rsa::RSA::from_public_parts(n, e);
+    let (blinded, inv) = match rsa.blind(variant, prepared_slice) {
+        Ok(result) => result,
+        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    let result = write_to_buffer(&blinded, blinded_buffer, blinded_len);
+    if result != CRYPTO_SUCCESS {
+        return result;
+    }
+    write_to_buffer(&inv, inv_buffer, inv_len)
+}
+
+/// 盲签名第三步（签名方）：对盲化消息签名，输出模长字节的盲签名
+///
+/// # Safety
+/// `n`、`e`、`d`（十进制字符串）、`blinded_msg` 必须指向对应长度的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_rsa_blind_sign(
+    n: *const u8, n_len: usize,
+    e: *const u8, e_len: usize,
+    d: *const u8, d_len: usize,
+    blinded_msg: *const u8, blinded_msg_len: usize,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    let components = unsafe { [input_biguint(n, n_len), input_biguint(e, e_len), input_biguint(d, d_len)] };
+    let [Some(n), Some(e), Some(d)] = components else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let Some(blinded_slice) = (unsafe { input_slice(blinded_msg, blinded_msg_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    let rsa = rsa::RSA::from_parts(n, e, d);
+    match rsa.blind_sign(blinded_slice) {
+        Ok(signature) => write_to_buffer(&signature, out_buffer, out_len),
+        Err(_) => CRYPTO_ERROR_INVALID_PARAMETER,
+    }
+}
+
+/// 盲签名第四步（客户端）：用 inv 去除盲化并验证，输出最终签名（标准 RSASSA-PSS-SHA384 签名）
+///
+/// # Safety
+/// `n`、`e`（十进制字符串）、`prepared_msg`、`blind_sig`、`inv` 必须指向对应长度的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_rsa_blind_finalize(
+    n: *const u8, n_len: usize,
+    e: *const u8, e_len: usize,
+    variant: i32,
+    prepared_msg: *const u8, prepared_msg_len: usize,
+    blind_sig: *const u8, blind_sig_len: usize,
+    inv: *const u8, inv_len: usize,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    let Some(variant) = Rsa::BlindVariant::from_id(variant) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let (Some(n), Some(e)) = (unsafe { input_biguint(n, n_len) }, unsafe { input_biguint(e, e_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let slices = unsafe {
+        (
+            input_slice(prepared_msg, prepared_msg_len),
+            input_slice(blind_sig, blind_sig_len),
+            input_slice(inv, inv_len),
+        )
+    };
+    let (Some(prepared_slice), Some(blind_sig_slice), Some(inv_slice)) = slices else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    let rsa = rsa::RSA::from_public_parts(n, e);
+    match rsa.finalize(variant, prepared_slice, blind_sig_slice, inv_slice) {
+        Ok(signature) => write_to_buffer(&signature, out_buffer, out_len),
+        Err(_) => CRYPTO_ERROR_INVALID_PARAMETER,
+    }
+}
+
+/// 验证盲签名协议产生的签名，签名有效时返回 CRYPTO_SUCCESS
+///
+/// # Safety
+/// `n`、`e`（十进制字符串）、`prepared_msg`、`signature` 必须指向对应长度的可读内存
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_rsa_blind_verify(
+    n: *const u8, n_len: usize,
+    e: *const u8, e_len: usize,
+    variant: i32,
+    prepared_msg: *const u8, prepared_msg_len: usize,
+    signature: *const u8, signature_len: usize
+) -> i32 {
+    let Some(variant) = Rsa::BlindVariant::from_id(variant) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let (Some(n), Some(e)) = (unsafe { input_biguint(n, n_len) }, unsafe { input_biguint(e, e_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let (Some(prepared_slice), Some(signature_slice)) = (unsafe { input_slice(prepared_msg, prepared_msg_len) }, unsafe { input_slice(signature, signature_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    let rsa = rsa::RSA::from_public_parts(n, e);
+    if rsa.blind_verify(variant, prepared_slice, signature_slice) {
+        CRYPTO_SUCCESS
+    } else {
+        CRYPTO_ERROR_INVALID_PARAMETER
+    }
+}
+
+/// 生成可证明素数的 RSA 私钥 (FIPS 186-5 A.1.2.2，公钥指数 65537)，以 PKCS#1 DER 输出，
+/// 同时输出种子和 p、q 的生成计数器供第三方核对
+///
+/// seed_len 为 0 时随机生成种子；否则使用给定的种子，同一种子总是得到同一个密钥
+///
+/// # Safety
+/// `seed` 必须指向 `seed_len` 字节的可读内存；各输出缓冲区必须至少有对应 `*_len` 字节可写，`p_counter`、`q_counter` 必须可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_rsa_generate_provable_key(
+    bit_length: usize,
+    seed: *const u8, seed_len: usize,
+    key_buffer: *mut u8, key_len: *mut usize,
+    seed_buffer: *mut u8, seed_out_len: *mut usize,
+    p_counter: *mut u32, q_counter: *mut u32
+) -> i32 {
+    if p_counter.is_null() || q_counter.is_null() {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    }
+    let Some(seed_slice) = (unsafe { input_slice(seed, seed_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    let e = BigUint::from(Rsa::private_key::DEFAULT_PUBLIC_EXPONENT);
+    let generated = if seed_slice.is_empty() {
+        Rsa::RsaPrivateKey::generate_provable_with_rng(bit_length, &e, &mut rand::rngs::OsRng)
+    } else {
+        Rsa::RsaPrivateKey::generate_provable(bit_length, &e, seed_slice)
+    };
+    let Ok((key, record)) = generated else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let (Ok(p_count), Ok(q_count)) = (u32::try_from(record.p_counter), u32::try_from(record.q_counter)) else {
+        return CRYPTO_ERROR_INTERNAL;
+    };
+
+    let result = write_to_buffer(&key.to_pkcs1_der(), key_buffer, key_len);
+    if result != CRYPTO_SUCCESS {
+        return result;
+    }
+    let result = write_to_buffer(&record.seed, seed_buffer, seed_out_len);
+    if result != CRYPTO_SUCCESS {
+        return result;
+    }
+    unsafe {
+        *p_counter = p_count;
+        *q_counter = q_count;
+    }
+    CRYPTO_SUCCESS
+}
+
+/// 用种子和计数器重新构造 PKCS#1 DER 私钥的素因子，一致时返回 CRYPTO_SUCCESS
+///
+/// # Safety
+/// `key_der`、`seed` 必须指向对应长度的可读内存
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_rsa_verify_provable_key(
+    key_der: *const u8, key_der_len: usize,
+    seed: *const u8, seed_len: usize,
+    p_counter: u32, q_counter: u32
+) -> i32 {
+    let (Some(key_slice), Some(seed_slice)) = (unsafe { input_slice(key_der, key_der_len) }, unsafe { input_slice(seed, seed_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let Some(key) = Rsa::RsaPrivateKey::from_pkcs1_der(key_slice) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    let record = Rsa::ProvableSeed {
+        seed: seed_slice.to_vec(),
+        p_counter: p_counter as usize,
+        q_counter: q_counter as usize,
+    };
+    if key.verify_provable(&record) {
+        CRYPTO_SUCCESS
+    } else {
+        CRYPTO_ERROR_INVALID_PARAMETER
+    }
+}
+
+/// 多线程生成 RSA 密钥对（公钥指数 65537），n、d 以十进制字符串输出
+///
+/// threads 为 0 时使用全部 CPU，超过 CPU 数时按 CPU 数截断；progress 可以为空，约每 100 毫秒在调用线程上调用一次；
+/// cancel 指向的值变为非 0 后尽快返回 CRYPTO_ERROR_CANCELLED
+///
+/// # Safety
+/// `cancel` 可以为空，否则必须对齐且在调用期间有效；各输出缓冲区必须至少有对应 `*_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_rsa_generate_keys_parallel(
+    bit_length: usize,
+    threads: usize,
+    progress: CryptoProgressCallback,
+    user_data: *mut c_void,
+    cancel: *const i32,
+    n_buffer: *mut u8, n_len: *mut usize,
+    d_buffer: *mut u8, d_len: *mut usize
+) -> i32 {
+    let e = BigUint::from(Rsa::private_key::DEFAULT_PUBLIC_EXPONENT);
+    let generated = unsafe {
+        with_search_options(threads, progress, user_data, cancel, |options| {
+            Rsa::RsaPrivateKey::generate_parallel(bit_length, &e, options, &mut rand::rngs::OsRng)
+        })
+    };
+    let key = match generated {
+        Ok(key) => key,
+        Err(Rsa::KeyError::Cancelled) => return CRYPTO_ERROR_CANCELLED,
+        Err(Rsa::KeyError::ThreadSpawnFailed) => return CRYPTO_ERROR_INTERNAL,
+        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    let result = write_string_to_buffer(&key.n().to_str_radix(10), n_buffer, n_len);
+    if result != CRYPTO_SUCCESS {
+        return result;
+    }
+    write_string_to_buffer(&key.d().to_str_radix(10), d_buffer, d_len)
+}
+
+// 辅助函数：由 FFI 的算法编号和迭代次数构造 PBES2 参数，迭代次数为 0 时使用默认值，超过上限时返回 None
+fn pbes2_params(cipher: i32, iterations: u32) -> Option<encoding::pkcs8::Pbes2Params> {
+    if iterations > encoding::pkcs8::PBKDF2_MAX_ITERATIONS {
+        return None;
+    }
+    let defaults = encoding::pkcs8::Pbes2Params::default();
+    Some(encoding::pkcs8::Pbes2Params {
+        cipher: encoding::pkcs8::Pbes2Cipher::from_id(cipher)?,
+        iterations: if iterations == 0 { defaults.iterations } else { iterations },
+        ..defaults
+    })
+}
+
+// 导出ElGamal相关函数
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_elgamal_generate_keys(
+    bit_length: usize,
+    p_buffer: *mut u8, p_len: *mut usize,
+    g_buffer: *mut u8, g_len: *mut usize,
+    y_buffer: *mut u8, y_len: *mut usize,
+    x_buffer: *mut u8, x_len: *mut usize
+) -> i32 {
+    if p_buffer.is_null() || p_len.is_null() ||
+       g_buffer.is_null() || g_len.is_null() ||
+       y_buffer.is_null() || y_len.is_null() ||
+       x_buffer.is_null() || x_len.is_null() {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    }
+    
+    // 生成ElGamal密钥对
+    let keys = elgamal::ElGamalKeys::new(bit_length);
+    let (p, g, y) = keys.public_key();
+    let (_, _, x) = keys.private_key();
+    
+    // 写入p值
+    let result = write_string_to_buffer(&p, p_buffer, p_len);
+    if result != CRYPTO_SUCCESS {
+        return result;
+    }
+    
+    // 写入g值
+    let result = write_string_to_buffer(&g, g_buffer, g_len);
+    if result != CRYPTO_SUCCESS {
+        return result;
+    }
+    
+    // 写入y值
+    let result = write_string_to_buffer(&y, y_buffer, y_len);
+    if result != CRYPTO_SUCCESS {
+        return result;
+    }
+    
+    // 写入x值
+    write_string_to_buffer(&x, x_buffer, x_len)
+}
+
+/// 由种子确定性地生成 ElGamal 密钥对，同一个种子和位数总是得到同一组 p、g、y、x，用于可复现的测试
+///
+/// 种子作为 HMAC-DRBG 的熵输入，至少 32 字节；输出格式同 `crypto_elgamal_generate_keys`
+///
+/// # Safety
+/// `seed` 必须指向至少 `seed_len` 字节的可读内存；各输出缓冲区必须至少有对应 `*_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_elgamal_generate_keys_from_seed(
+    bit_length: usize,
+    seed: *const u8, seed_len: usize,
+    p_buffer: *mut u8, p_len: *mut usize,
+    g_buffer: *mut u8, g_len: *mut usize,
+    y_buffer: *mut u8, y_len: *mut usize,
+    x_buffer: *mut u8, x_len: *mut usize
+) -> i32 {
+    if bit_length < 2 {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    }
+    let Some(mut rng) = (unsafe { seeded_drbg(seed, seed_len, b"crypto_elgamal_generate_keys_from_seed") }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    let keys = elgamal::ElGamalKeys::generate_with_rng(bit_length, &mut rng);
+    let (p, g, y) = keys.public_key();
+    let (_, _, x) = keys.private_key();
+
+    let outputs = [(p, p_buffer, p_len), (g, g_buffer, g_len), (y, y_buffer, y_len), (x, x_buffer, x_len)];
+    for (value, buffer, len) in outputs {
+        let result = write_string_to_buffer(&value, buffer, len);
+        if result != CRYPTO_SUCCESS {
+            return result;
+        }
+    }
+    CRYPTO_SUCCESS
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_elgamal_encrypt(
+    p: *const u8, p_len: usize,
+    g: *const u8, g_len: usize,
+    y: *const u8, y_len: usize,
+    message: *const u8, message_len: usize,
+    c1_buffer: *mut u8, c1_len: *mut usize,
+    c2_buffer: *mut u8, c2_len: *mut usize
+) -> i32 {
+    if p.is_null() || g.is_null() || y.is_null() || message.is_null() ||
+       c1_buffer.is_null() || c1_len.is_null() ||
+       c2_buffer.is_null() || c2_len.is_null() {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    }
+    
+    // 转换为字符串
+    let p_slice = std::slice::from_raw_parts(p, p_len);
+    let g_slice = std::slice::from_raw_parts(g, g_len);
+    let y_slice = std::slice::from_raw_parts(y, y_len);
+    let message_slice = std::slice::from_raw_parts(message, message_len);
+    
+    let p_str = match std::str::from_utf8(p_slice) {
+        Ok(s) => s,
+        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    
+    let g_str = match std::str::from_utf8(g_slice) {
+        Ok(s) => s,
+        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    
+    let y_str = match std::str::from_utf8(y_slice) {
+        Ok(s) => s,
+        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    
+    let message_str = match std::str::from_utf8(message_slice) {
+        Ok(s) => s,
+        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    
+    // 创建ElGamal实例并加密
+    let keys = elgamal::ElGamalKeys::from_public_params(p_str, g_str, y_str);
+    
+    // 将消息转换为BigUint
+    let message_hash = elgamal::sha256_to_biguint(message_str);
+    
+    // 加密消息
+    let ciphertext = keys.encrypt(&message_hash);
+    
+    // 写入c1值
+    let c1_str = ciphertext.c1.to_str_radix(10);
+    let result = write_string_to_buffer(&c1_str, c1_buffer, c1_len);
+    if result != CRYPTO_SUCCESS {
+        return result;
+    }
+    
+    // 写入c2值
+    let c2_str = ciphertext.c2.to_str_radix(10);
+    write_string_to_buffer(&c2_str, c2_buffer, c2_len)
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_elgamal_decrypt(
+    p: *const u8, p_len: usize,
+    g: *const u8, g_len: usize,
+    x: *const u8, x_len: usize,
+    c1: *const u8, c1_len: usize,
+    c2: *const u8, c2_len: usize,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    if p.is_null() || g.is_null() || x.is_null() || 
+       c1.is_null() || c2.is_null() ||
+       out_buffer.is_null() || out_len.is_null() {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    }
+    
+    // 转换为字符串
+    let p_slice = std::slice::from_raw_parts(p, p_len);
+    let g_slice = std::slice::from_raw_parts(g, g_len);
+    let x_slice = std::slice::from_raw_parts(x, x_len);
+    let c1_slice = std::slice::from_raw_parts(c1, c1_len);
+    let c2_slice = std::slice::from_raw_parts(c2, c2_len);
+    
+    let p_str = match std::str::from_utf8(p_slice) {
+        Ok(s) => s,
+        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    
+    let g_str = match std::str::from_utf8(g_slice) {
+        Ok(s) => s,
+        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    
+    let x_str = match std::str::from_utf8(x_slice) {
+        Ok(s) => s,
+        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    
+    let c1_str = match std::str::from_utf8(c1_slice) {
+        Ok(s) => s,
+        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    
+    let c2_str = match std::str::from_utf8(c2_slice) {
+        Ok(s) => s,
+        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    
+    // 创建ElGamal实例
+    let keys = elgamal::ElGamalKeys::from_private_params(p_str, g_str, x_str);
+    
+    // 解析c1和c2
+    let c1_big = match BigUint::from_str(c1_str) {
+        Ok(n) => n,
+        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    
+    let c2_big = match BigUint::from_str(c2_str) {
+        Ok(n) => n,
+        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    
+    let ciphertext = elgamal::ElGamalCiphertext {
+        c1: c1_big,
+        c2: c2_big,
+    };
+    
+    // 解密并返回结果
+    let decrypted = keys.decrypt(&ciphertext);
+    let decrypted_str = decrypted.to_str_radix(10);
+    
+    write_string_to_buffer(&decrypted_str, out_buffer, out_len)
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_elgamal_sign(
+    p: *const u8, p_len: usize,
+    g: *const u8, g_len: usize,
+    x: *const u8, x_len: usize,
+    message: *const u8, message_len: usize,
+    r_buffer: *mut u8, r_len: *mut usize,
+    s_buffer: *mut u8, s_len: *mut usize
+) -> i32 {
+    unsafe {
+        crypto_elgamal_sign_with_hash(
+            CRYPTO_HASH_SHA256,
+            p, p_len, g, g_len, x, x_len,
+            message, message_len,
+            r_buffer, r_len, s_buffer, s_len,
+        )
+    }
+}
+
+/// 使用指定摘要算法（CRYPTO_HASH_*，可选 SHA-3）的 ElGamal 签名
+///
+/// # Safety
+/// 所有输入指针必须指向对应长度的可读内存，输出缓冲区约定同 crypto_elgamal_sign
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_elgamal_sign_with_hash(
+    hash_algorithm: i32,
+    p: *const u8, p_len: usize,
+    g: *const u8, g_len: usize,
+    x: *const u8, x_len: usize,
+    message: *const u8, message_len: usize,
+    r_buffer: *mut u8, r_len: *mut usize,
+    s_buffer: *mut u8, s_len: *mut usize
+) -> i32 {
+    if p.is_null() || g.is_null() || x.is_null() || message.is_null() ||
+       r_buffer.is_null() || r_len.is_null() ||
+       s_buffer.is_null() || s_len.is_null() {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    }
+    
+    // 转换为字符串
+    let p_slice = std::slice::from_raw_parts(p, p_len);
+    let g_slice = std::slice::from_raw_parts(g, g_len);
+    let x_slice = std::slice::from_raw_parts(x, x_len);
+    let message_slice = std::slice::from_raw_parts(message, message_len);
+    
+    let p_str = match std::str::from_utf8(p_slice) {
+        Ok(s) => s,
+        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    
+    let g_str = match std::str::from_utf8(g_slice) {
+        Ok(s) => s,
+        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    
+    let x_str = match std::str::from_utf8(x_slice) {
+        Ok(s) => s,
+        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    
+    let message_str = match std::str::from_utf8(message_slice) {
+        Ok(s) => s,
+        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    
+    let algorithm = match hash::HashAlgorithm::from_id(hash_algorithm) {
+        Some(a) => a,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    
+    // 创建ElGamal实例
+    let keys = elgamal::ElGamalKeys::from_private_params(p_str, g_str, x_str);
+    
+    // 计算消息哈希
+    let message_hash = elgamal::hash_to_biguint(algorithm, message_str);
+    
+    // 签名消息
+    let signature = keys.sign(&message_hash);
+    
+    // 写入r值
+    let r_str = signature.r.to_str_radix(10);
+    let result = write_string_to_buffer(&r_str, r_buffer, r_len);
+    if result != CRYPTO_SUCCESS {
+        return result;
+    }
+    
+    // 写入s值
+    let s_str = signature.s.to_str_radix(10);
+    write_string_to_buffer(&s_str, s_buffer, s_len)
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_elgamal_verify(
+    p: *const u8, p_len: usize,
+    g: *const u8, g_len: usize,
+    y: *const u8, y_len: usize,
+    message: *const u8, message_len: usize,
+    r: *const u8, r_len: usize,
+    s: *const u8, s_len: usize
+) -> i32 {
+    unsafe {
+        crypto_elgamal_verify_with_hash(
+            CRYPTO_HASH_SHA256,
+            p, p_len, g, g_len, y, y_len,
+            message, message_len,
+            r, r_len, s, s_len,
+        )
+    }
+}
+
+/// 使用指定摘要算法（CRYPTO_HASH_*，可选 SHA-3）的 ElGamal 验签
+///
+/// # Safety
+/// 所有输入指针必须指向对应长度的可读内存
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_elgamal_verify_with_hash(
+    hash_algorithm: i32,
+    p: *const u8, p_len: usize,
+    g: *const u8, g_len: usize,
+    y: *const u8, y_len: usize,
+    message: *const u8, message_len: usize,
+    r: *const u8, r_len: usize,
+    s: *const u8, s_len: usize
+) -> i32 {
+    if p.is_null() || g.is_null() || y.is_null() || message.is_null() || 
+       r.is_null() || s.is_null() {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    }
+    
+    // 转换为字符串
+    let p_slice = std::slice::from_raw_parts(p, p_len);
+    let g_slice = std::slice::from_raw_parts(g, g_len);
+    let y_slice = std::slice::from_raw_parts(y, y_len);
+    let message_slice = std::slice::from_raw_parts(message, message_len);
+    let r_slice = std::slice::from_raw_parts(r, r_len);
+    let s_slice = std::slice::from_raw_parts(s, s_len);
+    
+    let p_str = match std::str::from_utf8(p_slice) {
+        Ok(s) => s,
+        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    
+    let g_str = match std::str::from_utf8(g_slice) {
+        Ok(s) => s,
+        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    
+    let y_str = match std::str::from_utf8(y_slice) {
+        Ok(s) => s,
+        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    
+    let message_str = match std::str::from_utf8(message_slice) {
+        Ok(s) => s,
+        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    
+    let r_str = match std::str::from_utf8(r_slice) {
+        Ok(s) => s,
+        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    
+    let s_str = match std::str::from_utf8(s_slice) {
+        Ok(s) => s,
+        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    
+    let algorithm = match hash::HashAlgorithm::from_id(hash_algorithm) {
+        Some(a) => a,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    
+    // 创建ElGamal实例
+    let keys = elgamal::ElGamalKeys::from_public_params(p_str, g_str, y_str);
+    
+    // 将消息转换为BigUint
+    let message_hash = elgamal::hash_to_biguint(algorithm, message_str);
+    
+    // 解析r和s
+    let r_big = match BigUint::from_str(r_str) {
+        Ok(n) => n,
+        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    
+    let s_big = match BigUint::from_str(s_str) {
+        Ok(n) => n,
+        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    
+    let signature = elgamal::ElGamalSignature {
+        r: r_big,
+        s: s_big,
+    };
+    
+    // 验证签名
+    if keys.verify(&message_hash, &signature) {
+        CRYPTO_SUCCESS
+    } else {
+        CRYPTO_ERROR_INVALID_PARAMETER
+    }
+}
+
+/// 导出口令加密的 ElGamal 私钥（PKCS#8 EncryptedPrivateKeyInfo，PBES2），参数含义同 `crypto_rsa_export_encrypted_private_key`
+///
+/// # Safety
+/// `p`、`g`、`x`（十进制字符串）和 `password` 必须指向对应长度的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_elgamal_export_encrypted_private_key(
+    p: *const u8, p_len: usize,
+    g: *const u8, g_len: usize,
+    x: *const u8, x_len: usize,
+    password: *const u8, password_len: usize,
+    cipher: i32, iterations: u32, encoding: i32,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    let Some(params) = pbes2_params(cipher, iterations) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let Some(password) = (unsafe { input_slice(password, password_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    let components = unsafe { [input_biguint(p, p_len), input_biguint(g, g_len), input_biguint(x, x_len)] };
+    let [Some(p), Some(g), Some(x)] = components else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let Some(keys) = elgamal::ElGamalKeys::from_private_components(p, g, x) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    let output = match encoding {
+        CRYPTO_KEY_ENCODING_DER => keys.to_encrypted_der(password, &params),
+        CRYPTO_KEY_ENCODING_PEM => keys.to_encrypted_pem(password, &params).map(String::into_bytes),
+        _ => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    match output {
+        Some(data) => write_to_buffer(&data, out_buffer, out_len),
+        None => CRYPTO_ERROR_INTERNAL,
+    }
+}
+
+/// 导入口令加密的 ElGamal 私钥（DER 或 PEM），输出十进制的 p、g、y、x
+///
+/// # Safety
+/// `data`、`password` 必须指向对应长度的可读内存；每个输出缓冲区必须至少有对应 `*_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_elgamal_import_encrypted_private_key(
+    data: *const u8, data_len: usize,
+    password: *const u8, password_len: usize,
+    p_buffer: *mut u8, p_len: *mut usize,
+    g_buffer: *mut u8, g_len: *mut usize,
+    y_buffer: *mut u8, y_len: *mut usize,
+    x_buffer: *mut u8, x_len: *mut usize
+) -> i32 {
+    let (Some(data_slice), Some(password)) = (unsafe { input_slice(data, data_len) }, unsafe { input_slice(password, password_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    let keys = if encoding::pem::is_pem(data_slice) {
+        std::str::from_utf8(data_slice).ok().and_then(|text| elgamal::ElGamalKeys::from_encrypted_pem(text, password))
+    } else {
+        elgamal::ElGamalKeys::from_encrypted_der(data_slice, password)
+    };
+    let Some(keys) = keys else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    let (p, g, y) = keys.public_key();
+    let (_, _, x) = keys.private_key();
+    for (value, buffer, len) in [(&p, p_buffer, p_len), (&g, g_buffer, g_len), (&y, y_buffer, y_len)] {
+        let result = write_string_to_buffer(value, buffer, len);
+        if result != CRYPTO_SUCCESS {
+            return result;
+        }
+    }
+    write_string_to_buffer(&x, x_buffer, x_len)
+}
+
+/// 导出 ElGamal 公钥 JWK（私有扩展 "kty":"ElGamal"，成员 p、g、y）
+///
+/// # Safety
+/// `p`、`g`、`y`（十进制字符串）必须指向对应长度的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_elgamal_export_public_jwk(
+    p: *const u8, p_len: usize,
+    g: *const u8, g_len: usize,
+    y: *const u8, y_len: usize,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    let components = unsafe { [input_biguint(p, p_len), input_biguint(g, g_len), input_biguint(y, y_len)] };
+    let [Some(p), Some(g), Some(y)] = components else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let Some(keys) = elgamal::ElGamalKeys::from_public_components(p, g, y) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    write_string_to_buffer(&keys.to_public_jwk(), out_buffer, out_len)
+}
+
+/// 导出 ElGamal 私钥 JWK（成员 p、g、y、x，y 由 g^x mod p 计算）
+///
+/// # Safety
+/// `p`、`g`、`x`（十进制字符串）必须指向对应长度的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_elgamal_export_private_jwk(
+    p: *const u8, p_len: usize,
+    g: *const u8, g_len: usize,
+    x: *const u8, x_len: usize,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    let components = unsafe { [input_biguint(p, p_len), input_biguint(g, g_len), input_biguint(x, x_len)] };
+    let [Some(p), Some(g), Some(x)] = components else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let jwk = elgamal::ElGamalKeys::from_private_components(p, g, x).and_then(|keys| keys.to_jwk());
+    let Some(jwk) = jwk else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    write_string_to_buffer(&jwk, out_buffer, out_len)
+}
+
+/// 导入 ElGamal 公钥或私钥 JWK，输出十进制的 p、g、y、x
+///
+/// 公钥没有 x，此时 `*x_len` 置为 0；`x_buffer` 和 `x_len` 可以同时为空指针
+///
+/// # Safety
+/// `data` 必须指向 `data_len` 字节的可读内存；每个输出缓冲区必须至少有对应 `*_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_elgamal_import_jwk(
+    data: *const u8, data_len: usize,
+    p_buffer: *mut u8, p_len: *mut usize,
+    g_buffer: *mut u8, g_len: *mut usize,
+    y_buffer: *mut u8, y_len: *mut usize,
+    x_buffer: *mut u8, x_len: *mut usize
+) -> i32 {
+    let data_slice = match unsafe { input_slice(data, data_len) } {
+        Some(d) => d,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    let Some(keys) = std::str::from_utf8(data_slice).ok().and_then(elgamal::ElGamalKeys::from_jwk) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    let (p, g, y) = keys.public_key();
+    for (value, buffer, len) in [(&p, p_buffer, p_len), (&g, g_buffer, g_len), (&y, y_buffer, y_len)] {
+        let result = write_string_to_buffer(value, buffer, len);
+        if result != CRYPTO_SUCCESS {
+            return result;
+        }
+    }
+
+    if x_len.is_null() {
+        return CRYPTO_SUCCESS;
+    }
+    if !keys.has_private_key() {
+        unsafe { *x_len = 0 };
+        return CRYPTO_SUCCESS;
+    }
+    let (_, _, x) = keys.private_key();
+    write_string_to_buffer(&x, x_buffer, x_len)
+}
+
+/// 由种子构造可证明的 ElGamal/DSA 域参数 (FIPS 186-4 A.1.2.1.2)：L 位的 p、N 位的 q 和阶为 q 的生成元 g（编号 1），
+/// p、q、g 以十进制字符串输出，同时输出种子和 p、q 的生成计数器供第三方核对
+///
+/// seed_len 为 0 时随机生成 N 位的种子；要求 2 <= N <= 摘要位数且 L >= 2N + 8
+///
+/// # Safety
+/// `seed` 必须指向 `seed_len` 字节的可读内存；各输出缓冲区必须至少有对应 `*_len` 字节可写，`p_counter`、`q_counter` 必须可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_elgamal_generate_provable_params(
+    l: usize,
+    n: usize,
+    hash_algorithm: i32,
+    seed: *const u8, seed_len: usize,
+    p_buffer: *mut u8, p_len: *mut usize,
+    q_buffer: *mut u8, q_len: *mut usize,
+    g_buffer: *mut u8, g_len: *mut usize,
+    seed_buffer: *mut u8, seed_out_len: *mut usize,
+    p_counter: *mut u32, q_counter: *mut u32
+) -> i32 {
+    if p_counter.is_null() || q_counter.is_null() {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    }
+    let Some(algorithm) = hash::HashAlgorithm::from_id(hash_algorithm) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let Some(seed_slice) = (unsafe { input_slice(seed, seed_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    let generated = if seed_slice.is_empty() {
+        ElGamal::params::DomainParameters::generate_provable_with_rng(l, n, algorithm, &mut rand::rngs::OsRng)
+    } else {
+        ElGamal::params::DomainParameters::generate_provable(l, n, algorithm, seed_slice, 1)
+    };
+    let Some((params, record)) = generated else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let (Ok(p_count), Ok(q_count)) = (u32::try_from(record.p_counter), u32::try_from(record.q_counter)) else {
+        return CRYPTO_ERROR_INTERNAL;
+    };
+
+    let outputs = [
+        (params.p(), p_buffer, p_len),
+        (params.q(), q_buffer, q_len),
+        (params.g(), g_buffer, g_len),
+    ];
+    for (value, buffer, len) in outputs {
+        let result = write_string_to_buffer(&value.to_str_radix(10), buffer, len);
+        if result != CRYPTO_SUCCESS {
+            return result;
+        }
+    }
+    let result = write_to_buffer(&record.first_seed, seed_buffer, seed_out_len);
+    if result != CRYPTO_SUCCESS {
+        return result;
+    }
+    unsafe {
+        *p_counter = p_count;
+        *q_counter = q_count;
+    }
+    CRYPTO_SUCCESS
+}
+
+/// 用种子和计数器重新构造域参数（生成元编号 1），与给定的 p、q、g 一致时返回 CRYPTO_SUCCESS
+///
+/// # Safety
+/// 各十进制字符串参数和 `seed` 必须指向对应长度的可读内存
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_elgamal_verify_provable_params(
+    hash_algorithm: i32,
+    p: *const u8, p_len: usize,
+    q: *const u8, q_len: usize,
+    g: *const u8, g_len: usize,
+    seed: *const u8, seed_len: usize,
+    p_counter: u32, q_counter: u32
+) -> i32 {
+    let Some(algorithm) = hash::HashAlgorithm::from_id(hash_algorithm) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let components = unsafe { [input_biguint(p, p_len), input_biguint(q, q_len), input_biguint(g, g_len)] };
+    let [Some(p), Some(q), Some(g)] = components else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let Some(seed_slice) = (unsafe { input_slice(seed, seed_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    let Some(params) = ElGamal::params::DomainParameters::from_components(p, q, g) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let record = ElGamal::params::DomainParameterSeed {
+        first_seed: seed_slice.to_vec(),
+        p_counter: p_counter as usize,
+        q_counter: q_counter as usize,
+        index: 1,
+    };
+    if params.verify_provable(algorithm, &record) {
+        CRYPTO_SUCCESS
+    } else {
+        CRYPTO_ERROR_INVALID_PARAMETER
+    }
+}
+
+/// 在域参数 (p, q, g) 下生成 ElGamal 密钥，私钥 x 取自 [1, q)，y、x 以十进制字符串输出
+///
+/// # Safety
+/// 各十进制字符串参数必须指向对应长度的可读内存；各输出缓冲区必须至少有对应 `*_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_elgamal_generate_keys_from_params(
+    p: *const u8, p_len: usize,
+    q: *const u8, q_len: usize,
+    g: *const u8, g_len: usize,
+    y_buffer: *mut u8, y_len: *mut usize,
+    x_buffer: *mut u8, x_len: *mut usize
+) -> i32 {
+    let components = unsafe { [input_biguint(p, p_len), input_biguint(q, q_len), input_biguint(g, g_len)] };
+    let [Some(p), Some(q), Some(g)] = components else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let Some(params) = ElGamal::params::DomainParameters::from_components(p, q, g) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let keys = elgamal::ElGamalKeys::from_domain_parameters(&params);
+    let (_, _, y) = keys.public_key();
+    let (_, _, x) = keys.private_key();
+
+    let result = write_string_to_buffer(&y, y_buffer, y_len);
+    if result != CRYPTO_SUCCESS {
+        return result;
+    }
+    write_string_to_buffer(&x, x_buffer, x_len)
+}
+
+/// 多线程搜索安全素数并生成 ElGamal 密钥对，参数含义同 `crypto_elgamal_generate_keys`
+///
+/// threads 为 0 时使用全部 CPU，超过 CPU 数时按 CPU 数截断；progress 可以为空，约每 100 毫秒在调用线程上调用一次；
+/// cancel 指向的值变为非 0 后尽快返回 CRYPTO_ERROR_CANCELLED
+///
+/// # Safety
+/// `cancel` 可以为空，否则必须对齐且在调用期间有效；各输出缓冲区必须至少有对应 `*_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_elgamal_generate_keys_parallel(
+    bit_length: usize,
+    threads: usize,
+    progress: CryptoProgressCallback,
+    user_data: *mut c_void,
+    cancel: *const i32,
+    p_buffer: *mut u8, p_len: *mut usize,
+    g_buffer: *mut u8, g_len: *mut usize,
+    y_buffer: *mut u8, y_len: *mut usize,
+    x_buffer: *mut u8, x_len: *mut usize
+) -> i32 {
+    if bit_length < 2 {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    }
+
+    let generated = unsafe {
+        with_search_options(threads, progress, user_data, cancel, |options| {
+            elgamal::ElGamalKeys::generate_parallel(bit_length, options, &mut rand::rngs::OsRng)
+        })
+    };
+    let keys = match generated {
+        Ok(keys) => keys,
+        Err(numtheory::search::SearchError::Cancelled) => return CRYPTO_ERROR_CANCELLED,
+        Err(numtheory::search::SearchError::ThreadSpawnFailed) => return CRYPTO_ERROR_INTERNAL,
+    };
+    let (p, g, y) = keys.public_key();
+    let (_, _, x) = keys.private_key();
+
+    let outputs = [(p, p_buffer, p_len), (g, g_buffer, g_len), (y, y_buffer, y_len), (x, x_buffer, x_len)];
+    for (value, buffer, len) in outputs {
+        let result = write_string_to_buffer(&value, buffer, len);
+        if result != CRYPTO_SUCCESS {
+            return result;
+        }
+    }
+    CRYPTO_SUCCESS
+}
+
+// 导出哈希相关函数
+/// 一次性计算 SHA-256 摘要，输出 32 字节原始摘要
+///
+/// # Safety
+/// `data` 必须指向 `data_len` 字节的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_sha256(
+    data: *const u8, data_len: usize,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    unsafe { crypto_hash(CRYPTO_HASH_SHA256, data, data_len, out_buffer, out_len) }
+}
+
+/// 一次性计算指定算法（CRYPTO_HASH_*）的摘要
+///
+/// # Safety
+/// `data` 必须指向 `data_len` 字节的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_hash(
+    algorithm: i32,
+    data: *const u8, data_len: usize,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    let algorithm = match hash::HashAlgorithm::from_id(algorithm) {
+        Some(a) => a,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    let data_slice = match unsafe { input_slice(data, data_len) } {
+        Some(d) => d,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    let digest = algorithm.digest(data_slice);
+    write_to_buffer(&digest, out_buffer, out_len)
+}
+
+/// 计算 SHAKE128 / SHAKE256 的可扩展输出，输出长度由 `*out_len` 指定
+///
+/// 结果直接挤出到 `out_buffer`，不会按调用方给出的长度在库内分配内存
+///
+/// # Safety
+/// `data` 必须指向 `data_len` 字节的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_hash_xof(
+    algorithm: i32,
+    data: *const u8, data_len: usize,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    if out_buffer.is_null() || out_len.is_null() {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    }
+
+    let algorithm = match hash::HashAlgorithm::from_id(algorithm) {
+        Some(a) if a.is_xof() => a,
+        _ => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    let data_slice = match unsafe { input_slice(data, data_len) } {
+        Some(d) => d,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    let mut hasher = algorithm.hasher();
+    hasher.update(data_slice);
+    let output = unsafe { std::slice::from_raw_parts_mut(out_buffer, *out_len) };
+    if !hasher.finalize_xof_into(output) {
+        return CRYPTO_ERROR_INTERNAL;
+    }
+    CRYPTO_SUCCESS
+}
+
+/// 创建流式哈希上下文，算法无效时返回空指针；用完必须调用 crypto_hash_free 释放
+#[unsafe(no_mangle)]
+pub extern "C" fn crypto_hash_new(algorithm: i32) -> *mut hash::Hasher {
+    match hash::HashAlgorithm::from_id(algorithm) {
+        Some(a) => Box::into_raw(Box::new(a.hasher())),
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// 向哈希上下文追加数据
+///
+/// # Safety
+/// `ctx` 必须是 crypto_hash_new 返回且尚未释放的指针；`data` 必须指向 `data_len` 字节的可读内存
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_hash_update(
+    ctx: *mut hash::Hasher,
+    data: *const u8, data_len: usize
+) -> i32 {
+    if ctx.is_null() {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    }
+
+    let data_slice = match unsafe { input_slice(data, data_len) } {
+        Some(d) => d,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    unsafe { (*ctx).update(data_slice) };
+    CRYPTO_SUCCESS
+}
+
+/// 输出当前已输入数据的摘要；上下文不会被释放，之后仍可继续 update
+///
+/// # Safety
+/// `ctx` 必须是 crypto_hash_new 返回且尚未释放的指针；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_hash_finalize(
+    ctx: *const hash::Hasher,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    if ctx.is_null() {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    }
+
+    let digest = unsafe { (*ctx).clone() }.finalize();
+    write_to_buffer(&digest, out_buffer, out_len)
+}
+
+/// 释放哈希上下文
+///
+/// # Safety
+/// `ctx` 必须是 crypto_hash_new 返回的指针或空指针，且只能释放一次
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_hash_free(ctx: *mut hash::Hasher) {
+    if !ctx.is_null() {
+        drop(unsafe { Box::from_raw(ctx) });
+    }
+}
+
+// 导出HMAC相关函数
+/// 计算 HMAC 标签，algorithm 为 CRYPTO_HASH_*（SHAKE 不支持）
+///
+/// # Safety
+/// `key`、`data` 必须指向对应长度的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_hmac(
+    algorithm: i32,
+    key: *const u8, key_len: usize,
+    data: *const u8, data_len: usize,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    let algorithm = match hash::HashAlgorithm::from_id(algorithm) {
+        Some(a) => a,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    let (key_slice, data_slice) = match unsafe { (input_slice(key, key_len), input_slice(data, data_len)) } {
+        (Some(k), Some(d)) => (k, d),
+        _ => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    match hash::Hmac::mac(algorithm, key_slice, data_slice) {
+        Some(tag) => write_to_buffer(&tag, out_buffer, out_len),
+        None => CRYPTO_ERROR_INVALID_PARAMETER,
+    }
+}
+
+/// 以常数时间验证完整长度的 HMAC 标签，有效返回 CRYPTO_SUCCESS
+///
+/// # Safety
+/// `key`、`data`、`tag` 必须指向对应长度的可读内存
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_hmac_verify(
+    algorithm: i32,
+    key: *const u8, key_len: usize,
+    data: *const u8, data_len: usize,
+    tag: *const u8, tag_len: usize
+) -> i32 {
+    if tag.is_null() {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    }
+
+    let algorithm = match hash::HashAlgorithm::from_id(algorithm) {
+        Some(a) => a,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    let (key_slice, data_slice) = match unsafe { (input_slice(key, key_len), input_slice(data, data_len)) } {
+        (Some(k), Some(d)) => (k, d),
+        _ => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    let tag_slice = unsafe { std::slice::from_raw_parts(tag, tag_len) };
+
+    let mut hmac = match hash::Hmac::new(algorithm, key_slice) {
+        Some(h) => h,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    hmac.update(data_slice);
+
+    // 与 crypto_elgamal_verify 一致，验证失败返回 CRYPTO_ERROR_INVALID_PARAMETER
+    if hmac.verify(tag_slice) {
+        CRYPTO_SUCCESS
+    } else {
+        CRYPTO_ERROR_INVALID_PARAMETER
+    }
+}
+
+// 导出HKDF相关函数
+/// HKDF extract + expand，输出长度由 `*out_len` 指定
+///
+/// # Safety
+/// `salt`、`ikm`、`info` 必须指向对应长度的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_hkdf(
+    algorithm: i32,
+    salt: *const u8, salt_len: usize,
+    ikm: *const u8, ikm_len: usize,
+    info: *const u8, info_len: usize,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    if out_buffer.is_null() || out_len.is_null() {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    }
+
+    let algorithm = match hash::HashAlgorithm::from_id(algorithm) {
+        Some(a) => a,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    let (salt_slice, ikm_slice, info_slice) = match unsafe {
+        (input_slice(salt, salt_len), input_slice(ikm, ikm_len), input_slice(info, info_len))
+    } {
+        (Some(s), Some(k), Some(i)) => (s, k, i),
+        _ => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    match kdf::hkdf(algorithm, salt_slice, ikm_slice, info_slice, unsafe { *out_len }) {
+        Ok(okm) => write_to_buffer(&okm, out_buffer, out_len),
+        Err(_) => CRYPTO_ERROR_INVALID_PARAMETER,
+    }
+}
+
+/// HKDF-Extract，输出 PRK（长度等于摘要长度）
+///
+/// # Safety
+/// `salt`、`ikm` 必须指向对应长度的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_hkdf_extract(
+    algorithm: i32,
+    salt: *const u8, salt_len: usize,
+    ikm: *const u8, ikm_len: usize,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    let algorithm = match hash::HashAlgorithm::from_id(algorithm) {
+        Some(a) => a,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    let (salt_slice, ikm_slice) = match unsafe { (input_slice(salt, salt_len), input_slice(ikm, ikm_len)) } {
+        (Some(s), Some(k)) => (s, k),
+        _ => return CRYPTO_ERROR_INVALID_PARAMETER,
     };
-    
-    // 解密并返回结果
-    let decrypted = keys.decrypt(&ciphertext);
-    let decrypted_str = decrypted.to_str_radix(10);
-    
-    write_string_to_buffer(&decrypted_str, out_buffer, out_len)
+
+    match kdf::Hkdf::extract(algorithm, salt_slice, ikm_slice) {
+        Ok(hkdf) => write_to_buffer(hkdf.prk(), out_buffer, out_len),
+        Err(_) => CRYPTO_ERROR_INVALID_PARAMETER,
+    }
 }
 
+/// HKDF-Expand，输出长度由 `*out_len` 指定
+///
+/// # Safety
+/// `prk`、`info` 必须指向对应长度的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn crypto_elgamal_sign(
-    p: *const u8, p_len: usize,
-    g: *const u8, g_len: usize,
-    x: *const u8, x_len: usize,
-    message: *const u8, message_len: usize,
-    r_buffer: *mut u8, r_len: *mut usize,
-    s_buffer: *mut u8, s_len: *mut usize
+pub unsafe extern "C" fn crypto_hkdf_expand(
+    algorithm: i32,
+    prk: *const u8, prk_len: usize,
+    info: *const u8, info_len: usize,
+    out_buffer: *mut u8, out_len: *mut usize
 ) -> i32 {
-    if p.is_null() || g.is_null() || x.is_null() || message.is_null() ||
-       r_buffer.is_null() || r_len.is_null() ||
-       s_buffer.is_null() || s_len.is_null() {
+    if out_buffer.is_null() || out_len.is_null() {
         return CRYPTO_ERROR_INVALID_PARAMETER;
     }
-    
-    // 转换为字符串
-    let p_slice = std::slice::from_raw_parts(p, p_len);
-    let g_slice = std::slice::from_raw_parts(g, g_len);
-    let x_slice = std::slice::from_raw_parts(x, x_len);
-    let message_slice = std::slice::from_raw_parts(message, message_len);
-    
-    let p_str = match std::str::from_utf8(p_slice) {
-        Ok(s) => s,
-        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+
+    let algorithm = match hash::HashAlgorithm::from_id(algorithm) {
+        Some(a) => a,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
     };
-    
-    let g_str = match std::str::from_utf8(g_slice) {
-        Ok(s) => s,
-        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+
+    let (prk_slice, info_slice) = match unsafe { (input_slice(prk, prk_len), input_slice(info, info_len)) } {
+        (Some(p), Some(i)) => (p, i),
+        _ => return CRYPTO_ERROR_INVALID_PARAMETER,
     };
-    
-    let x_str = match std::str::from_utf8(x_slice) {
-        Ok(s) => s,
+
+    let okm = match kdf::Hkdf::from_prk(algorithm, prk_slice).and_then(|h| h.expand(info_slice, unsafe { *out_len })) {
+        Ok(okm) => okm,
         Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
     };
-    
-    let message_str = match std::str::from_utf8(message_slice) {
-        Ok(s) => s,
-        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+    write_to_buffer(&okm, out_buffer, out_len)
+}
+
+/// 从共享秘密派生会话密钥：AES 密钥和基础 IV 以十六进制字符串输出，MAC 密钥以 32 字节原始数据输出（用于 crypto_hmac）
+///
+/// 基础 IV 不能直接用于加密，每条消息用 crypto_session_message_iv 按序号得到各自的 IV
+///
+/// # Safety
+/// `secret`、`salt` 必须指向对应长度的可读内存；各输出缓冲区必须至少有对应长度字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_derive_session_keys(
+    secret: *const u8, secret_len: usize,
+    salt: *const u8, salt_len: usize,
+    key_hex_buffer: *mut u8, key_hex_len: *mut usize,
+    iv_hex_buffer: *mut u8, iv_hex_len: *mut usize,
+    mac_key_buffer: *mut u8, mac_key_len: *mut usize
+) -> i32 {
+    if secret.is_null() || secret_len == 0 {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    }
+
+    let (secret_slice, salt_slice) = match unsafe { (input_slice(secret, secret_len), input_slice(salt, salt_len)) } {
+        (Some(k), Some(s)) => (k, s),
+        _ => return CRYPTO_ERROR_INVALID_PARAMETER,
     };
-    
-    // 创建ElGamal实例
-    let keys = elgamal::ElGamalKeys::from_private_params(p_str, g_str, x_str);
-    
-    // 计算消息哈希
-    let message_hash = elgamal::sha256_to_biguint(message_str);
-    
-    // 签名消息
-    let signature = keys.sign(&message_hash);
-    
-    // 写入r值
-    let r_str = signature.r.to_str_radix(10);
-    let result = write_string_to_buffer(&r_str, r_buffer, r_len);
+
+    let keys = kdf::derive_session_keys(secret_slice, salt_slice);
+
+    // 写入AES密钥
+    let result = write_string_to_buffer(&bytes_to_hex(&keys.enc_key), key_hex_buffer, key_hex_len);
     if result != CRYPTO_SUCCESS {
         return result;
     }
-    
-    // 写入s值
-    let s_str = signature.s.to_str_radix(10);
-    write_string_to_buffer(&s_str, s_buffer, s_len)
+
+    // 写入IV
+    let result = write_string_to_buffer(&bytes_to_hex(&keys.iv), iv_hex_buffer, iv_hex_len);
+    if result != CRYPTO_SUCCESS {
+        return result;
+    }
+
+    // 写入MAC密钥
+    write_to_buffer(&keys.mac_key, mac_key_buffer, mac_key_len)
 }
 
+/// 由 crypto_derive_session_keys 输出的基础 IV 和消息序号计算这条消息的 IV（十六进制）
+///
+/// 序号按大端异或到 IV 的后 8 字节；同一组会话密钥下每条消息必须使用不同的序号。
+/// 基础 IV 必须是 32 个十六进制字符（可带结尾的 NUL）
+///
+/// # Safety
+/// `iv_hex` 必须指向 `iv_hex_len` 字节的可读内存；`out_hex_buffer` 必须至少有 `*out_hex_len` 字节可写
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn crypto_elgamal_verify(
-    p: *const u8, p_len: usize,
-    g: *const u8, g_len: usize,
-    y: *const u8, y_len: usize,
-    message: *const u8, message_len: usize,
-    r: *const u8, r_len: usize,
-    s: *const u8, s_len: usize
+pub unsafe extern "C" fn crypto_session_message_iv(
+    iv_hex: *const u8, iv_hex_len: usize,
+    sequence: u64,
+    out_hex_buffer: *mut u8, out_hex_len: *mut usize
 ) -> i32 {
-    if p.is_null() || g.is_null() || y.is_null() || message.is_null() || 
-       r.is_null() || s.is_null() {
+    // 基础 IV 必须是完整的 32 个十六进制字符，不做 hex_to_bytes 那样的补零
+    let Some(iv_hex) = (unsafe { input_slice(iv_hex, iv_hex_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let iv_hex = iv_hex.strip_suffix(&[0]).unwrap_or(iv_hex);
+    let Some(base_iv) = hex::decode(iv_hex).ok().and_then(|iv| <[u8; 16]>::try_from(iv).ok()) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+    let iv = kdf::message_iv(&base_iv, sequence);
+    write_string_to_buffer(&bytes_to_hex(&iv), out_hex_buffer, out_hex_len)
+}
+
+// 导出口令派生相关函数
+/// PBKDF2-HMAC，输出长度由 `*out_len` 指定
+///
+/// # Safety
+/// `password`、`salt` 必须指向对应长度的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_pbkdf2(
+    algorithm: i32,
+    password: *const u8, password_len: usize,
+    salt: *const u8, salt_len: usize,
+    iterations: u32,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    if out_buffer.is_null() || out_len.is_null() {
         return CRYPTO_ERROR_INVALID_PARAMETER;
     }
-    
-    // 转换为字符串
-    let p_slice = std::slice::from_raw_parts(p, p_len);
-    let g_slice = std::slice::from_raw_parts(g, g_len);
-    let y_slice = std::slice::from_raw_parts(y, y_len);
-    let message_slice = std::slice::from_raw_parts(message, message_len);
-    let r_slice = std::slice::from_raw_parts(r, r_len);
-    let s_slice = std::slice::from_raw_parts(s, s_len);
-    
-    let p_str = match std::str::from_utf8(p_slice) {
-        Ok(s) => s,
-        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+
+    let algorithm = match hash::HashAlgorithm::from_id(algorithm) {
+        Some(a) => a,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
     };
-    
-    let g_str = match std::str::from_utf8(g_slice) {
-        Ok(s) => s,
-        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+
+    let (password_slice, salt_slice) = match unsafe { (input_slice(password, password_len), input_slice(salt, salt_len)) } {
+        (Some(p), Some(s)) => (p, s),
+        _ => return CRYPTO_ERROR_INVALID_PARAMETER,
     };
-    
-    let y_str = match std::str::from_utf8(y_slice) {
-        Ok(s) => s,
-        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+
+    match kdf::pbkdf2(algorithm, password_slice, salt_slice, iterations, unsafe { *out_len }) {
+        Ok(key) => write_to_buffer(&key, out_buffer, out_len),
+        Err(_) => CRYPTO_ERROR_INVALID_PARAMETER,
+    }
+}
+
+/// scrypt，N = 2^log_n，输出长度由 `*out_len` 指定
+///
+/// # Safety
+/// `password`、`salt` 必须指向对应长度的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_scrypt(
+    password: *const u8, password_len: usize,
+    salt: *const u8, salt_len: usize,
+    log_n: u32, r: u32, p: u32,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    if out_buffer.is_null() || out_len.is_null() {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    }
+
+    let params = match scrypt_params(log_n, r, p) {
+        Some(params) => params,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
     };
-    
-    let message_str = match std::str::from_utf8(message_slice) {
-        Ok(s) => s,
-        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+
+    let (password_slice, salt_slice) = match unsafe { (input_slice(password, password_len), input_slice(salt, salt_len)) } {
+        (Some(p), Some(s)) => (p, s),
+        _ => return CRYPTO_ERROR_INVALID_PARAMETER,
     };
-    
-    let r_str = match std::str::from_utf8(r_slice) {
-        Ok(s) => s,
+
+    match kdf::scrypt(password_slice, salt_slice, &params, unsafe { *out_len }) {
+        Ok(key) => write_to_buffer(&key, out_buffer, out_len),
+        Err(_) => CRYPTO_ERROR_INVALID_PARAMETER,
+    }
+}
+
+/// Argon2id（m_cost 以 KiB 为单位，至多 4 GiB；盐至少 8 字节），输出长度由 `*out_len` 指定
+///
+/// # Safety
+/// `password`、`salt` 必须指向对应长度的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_argon2id(
+    password: *const u8, password_len: usize,
+    salt: *const u8, salt_len: usize,
+    m_cost: u32, t_cost: u32, parallelism: u32,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    if out_buffer.is_null() || out_len.is_null() {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    }
+
+    let params = match kdf::Argon2Params::new(m_cost, t_cost, parallelism) {
+        Ok(params) => params,
         Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
     };
-    
-    let s_str = match std::str::from_utf8(s_slice) {
-        Ok(s) => s,
-        Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
+
+    let (password_slice, salt_slice) = match unsafe { (input_slice(password, password_len), input_slice(salt, salt_len)) } {
+        (Some(p), Some(s)) => (p, s),
+        _ => return CRYPTO_ERROR_INVALID_PARAMETER,
     };
-    
-    // 创建ElGamal实例
-    let keys = elgamal::ElGamalKeys::from_public_params(p_str, g_str, y_str);
-    
-    // 将消息转换为BigUint
-    let message_hash = elgamal::sha256_to_biguint(message_str);
-    
-    // 解析r和s
-    let r_big = match BigUint::from_str(r_str) {
-        Ok(n) => n,
+
+    match kdf::argon2id(password_slice, salt_slice, &params, unsafe { *out_len }) {
+        Ok(key) => write_to_buffer(&key, out_buffer, out_len),
+        Err(_) => CRYPTO_ERROR_INVALID_PARAMETER,
+    }
+}
+
+/// 使用 PBKDF2-HMAC-SHA256 和随机盐计算口令哈希，输出 PHC 格式字符串
+///
+/// # Safety
+/// `password` 必须指向 `password_len` 字节的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_pbkdf2_hash_password(
+    password: *const u8, password_len: usize,
+    iterations: u32,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    let params = kdf::PasswordHashParams::Pbkdf2Sha256 { iterations };
+    unsafe { hash_password_to_buffer(password, password_len, &params, out_buffer, out_len) }
+}
+
+/// 使用 scrypt 和随机盐计算口令哈希，输出 PHC 格式字符串
+///
+/// # Safety
+/// `password` 必须指向 `password_len` 字节的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_scrypt_hash_password(
+    password: *const u8, password_len: usize,
+    log_n: u32, r: u32, p: u32,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    let params = match scrypt_params(log_n, r, p) {
+        Some(params) => kdf::PasswordHashParams::Scrypt(params),
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+    unsafe { hash_password_to_buffer(password, password_len, &params, out_buffer, out_len) }
+}
+
+/// 使用 Argon2id 和随机盐计算口令哈希，输出 PHC 格式字符串
+///
+/// # Safety
+/// `password` 必须指向 `password_len` 字节的可读内存；`out_buffer` 必须至少有 `*out_len` 字节可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_argon2id_hash_password(
+    password: *const u8, password_len: usize,
+    m_cost: u32, t_cost: u32, parallelism: u32,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    let params = match kdf::Argon2Params::new(m_cost, t_cost, parallelism) {
+        Ok(params) => kdf::PasswordHashParams::Argon2id(params),
         Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
     };
-    
-    let s_big = match BigUint::from_str(s_str) {
-        Ok(n) => n,
+    unsafe { hash_password_to_buffer(password, password_len, &params, out_buffer, out_len) }
+}
+
+/// 校验口令与 PHC 字符串（由 crypto_*_hash_password 生成）是否匹配，匹配时返回 CRYPTO_SUCCESS
+///
+/// 字符串中的代价参数和哈希长度超过 `kdf::password` 中的上限时直接拒绝，不做计算
+///
+/// # Safety
+/// `password`、`encoded` 必须指向对应长度的可读内存
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_verify_password(
+    password: *const u8, password_len: usize,
+    encoded: *const u8, encoded_len: usize
+) -> i32 {
+    let (password_slice, encoded_slice) = match unsafe { (input_slice(password, password_len), input_slice(encoded, encoded_len)) } {
+        (Some(p), Some(e)) => (p, e),
+        _ => return CRYPTO_ERROR_INVALID_PARAMETER,
+    };
+
+    let encoded_str = match std::str::from_utf8(encoded_slice) {
+        Ok(s) => s,
         Err(_) => return CRYPTO_ERROR_INVALID_PARAMETER,
     };
-    
-    let signature = elgamal::ElGamalSignature {
-        r: r_big,
-        s: s_big,
+
+    match kdf::verify_password(password_slice, encoded_str) {
+        Ok(true) => CRYPTO_SUCCESS,
+        _ => CRYPTO_ERROR_INVALID_PARAMETER,
+    }
+}
+
+// 辅助函数：校验并构造 scrypt 参数
+fn scrypt_params(log_n: u32, r: u32, p: u32) -> Option<kdf::ScryptParams> {
+    let log_n = u8::try_from(log_n).ok()?;
+    kdf::ScryptParams::new(log_n, r, p).ok()
+}
+
+// 辅助函数：计算口令哈希并把 PHC 字符串写入输出缓冲区
+unsafe fn hash_password_to_buffer(
+    password: *const u8, password_len: usize,
+    params: &kdf::PasswordHashParams,
+    out_buffer: *mut u8, out_len: *mut usize
+) -> i32 {
+    let password_slice = match unsafe { input_slice(password, password_len) } {
+        Some(p) => p,
+        None => return CRYPTO_ERROR_INVALID_PARAMETER,
     };
-    
-    // 验证签名
-    if keys.verify(&message_hash, &signature) {
-        CRYPTO_SUCCESS
-    } else {
-        CRYPTO_ERROR_INVALID_PARAMETER
+
+    match kdf::hash_password(password_slice, params) {
+        Ok(encoded) => write_string_to_buffer(&encoded, out_buffer, out_len),
+        Err(_) => CRYPTO_ERROR_INVALID_PARAMETER,
+    }
+}
+
+// 导出数论相关函数
+
+/// Baillie-PSW 素性检测，n 为十进制字符串；`*is_prime` 输出 1 表示（概率）素数，0 表示合数
+///
+/// # Safety
+/// `n` 必须指向 `n_len` 字节的可读内存，`is_prime` 必须可写
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn crypto_is_probable_prime(
+    n: *const u8, n_len: usize,
+    is_prime: *mut i32
+) -> i32 {
+    if is_prime.is_null() {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    }
+    let Some(n) = (unsafe { input_biguint(n, n_len) }) else {
+        return CRYPTO_ERROR_INVALID_PARAMETER;
+    };
+
+    unsafe { *is_prime = numtheory::is_probable_prime(&n) as i32 };
+    CRYPTO_SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_xof_writes_into_caller_buffer() {
+        // 请求长度只决定写入多少字节，库内不按该长度分配
+        let mut out = vec![0u8; 10000];
+        let mut out_len = out.len();
+        let rc = unsafe {
+            crypto_hash_xof(CRYPTO_HASH_SHAKE128, b"abc".as_ptr(), 3, out.as_mut_ptr(), &mut out_len)
+        };
+        assert_eq!(rc, CRYPTO_SUCCESS);
+        assert_eq!(out_len, 10000);
+        assert_eq!(
+            hex::encode(&out[10000 - 32..]),
+            "dc1735d1582e155f374b31ae88d40e40cc521d659a0cda9bb8c5189d80f7155b"
+        );
+
+        let mut out_len = 4;
+        let rc = unsafe {
+            crypto_hash_xof(CRYPTO_HASH_SHA256, b"abc".as_ptr(), 3, out.as_mut_ptr(), &mut out_len)
+        };
+        assert_eq!(rc, CRYPTO_ERROR_INVALID_PARAMETER);
+    }
+
+    // 调用 crypto_rsa_generate_keys_from_seed，成功时返回十进制的 (n, e, d)
+    fn rsa_keys_from_seed(bit_length: usize, seed: &[u8]) -> Result<[String; 3], i32> {
+        let mut buffers = [[0u8; 1024]; 3];
+        let mut lens = [1024usize; 3];
+        let [n, e, d] = &mut buffers;
+        let [n_len, e_len, d_len] = &mut lens;
+        let rc = unsafe {
+            crypto_rsa_generate_keys_from_seed(
+                bit_length,
+                seed.as_ptr(), seed.len(),
+                n.as_mut_ptr(), n_len,
+                e.as_mut_ptr(), e_len,
+                d.as_mut_ptr(), d_len,
+            )
+        };
+        if rc != CRYPTO_SUCCESS {
+            return Err(rc);
+        }
+        Ok(std::array::from_fn(|i| String::from_utf8(buffers[i][..lens[i]].to_vec()).unwrap()))
+    }
+
+    // 调用 crypto_elgamal_generate_keys_from_seed，成功时返回十进制的 (p, g, y, x)
+    fn elgamal_keys_from_seed(bit_length: usize, seed: &[u8]) -> Result<[String; 4], i32> {
+        let mut buffers = [[0u8; 512]; 4];
+        let mut lens = [512usize; 4];
+        let [p, g, y, x] = &mut buffers;
+        let [p_len, g_len, y_len, x_len] = &mut lens;
+        let rc = unsafe {
+            crypto_elgamal_generate_keys_from_seed(
+                bit_length,
+                seed.as_ptr(), seed.len(),
+                p.as_mut_ptr(), p_len,
+                g.as_mut_ptr(), g_len,
+                y.as_mut_ptr(), y_len,
+                x.as_mut_ptr(), x_len,
+            )
+        };
+        if rc != CRYPTO_SUCCESS {
+            return Err(rc);
+        }
+        Ok(std::array::from_fn(|i| String::from_utf8(buffers[i][..lens[i]].to_vec()).unwrap()))
+    }
+
+    #[test]
+    fn rsa_keys_from_seed_are_reproducible() {
+        let seed = [0x42u8; 32];
+        let key = rsa_keys_from_seed(1024, &seed).unwrap();
+        assert_eq!(rsa_keys_from_seed(1024, &seed).unwrap(), key);
+        assert_eq!(key[1], "65537");
+        assert_eq!(BigUint::from_str(&key[0]).unwrap().bits(), 1024);
+
+        let mut other = seed;
+        other[31] ^= 0x01;
+        let other_key = rsa_keys_from_seed(1024, &other).unwrap();
+        assert_ne!(other_key[0], key[0]);
+        assert_ne!(other_key[2], key[2]);
+
+        assert_eq!(rsa_keys_from_seed(1024, &seed[..31]), Err(CRYPTO_ERROR_INVALID_PARAMETER));
+        assert_eq!(rsa_keys_from_seed(1024, &[]), Err(CRYPTO_ERROR_INVALID_PARAMETER));
+    }
+
+    #[test]
+    fn elgamal_keys_from_seed_are_reproducible() {
+        let seed = [0x42u8; 32];
+        let keys = elgamal_keys_from_seed(128, &seed).unwrap();
+        assert_eq!(elgamal_keys_from_seed(128, &seed).unwrap(), keys);
+
+        // 固定一组小参数，生成算法或 DRBG 的任何改动都会让它变化；p = 2q + 1 为 129 位安全素数
+        assert_eq!(
+            keys,
+            [
+                "529795847674984430266561833571739039639",
+                "252316009544074594611820156894122231321",
+                "78539177018226760364709675127998040171",
+                "48419369420169750947968939841097726419",
+            ]
+            .map(String::from)
+        );
+        let [p, g, y, x] = keys.clone().map(|v| BigUint::from_str(&v).unwrap());
+        assert_eq!(g.modpow(&x, &p), y);
+
+        let mut other = seed;
+        other[0] ^= 0x01;
+        let other_keys = elgamal_keys_from_seed(128, &other).unwrap();
+        assert_ne!(other_keys[0], keys[0]);
+        assert_ne!(other_keys[3], keys[3]);
+
+        assert_eq!(elgamal_keys_from_seed(128, &seed[..31]), Err(CRYPTO_ERROR_INVALID_PARAMETER));
+        assert_eq!(elgamal_keys_from_seed(1, &seed), Err(CRYPTO_ERROR_INVALID_PARAMETER));
+    }
+
+    #[test]
+    fn session_message_iv_through_ffi() {
+        let base = b"000102030405060708090a0b0c0d0e0f";
+        let mut out = [0u8; 33];
+        let mut out_len = out.len();
+        let rc = unsafe { crypto_session_message_iv(base.as_ptr(), base.len(), 0x0102, out.as_mut_ptr(), &mut out_len) };
+        assert_eq!(rc, CRYPTO_SUCCESS);
+        assert_eq!(&out[..out_len], b"000102030405060708090a0b0c0d0f0d");
+
+        let mut out_len = out.len();
+        let rc = unsafe { crypto_session_message_iv(base.as_ptr(), 30, 1, out.as_mut_ptr(), &mut out_len) };
+        assert_eq!(rc, CRYPTO_ERROR_INVALID_PARAMETER);
     }
 }
@@ -0,0 +1,287 @@
+//! 数论工具：扩展欧几里得、模逆、Jacobi 符号、中国剩余定理、模平方根、素性检测与可证明素数构造
+//!
+//! RSA 与 ElGamal 的密钥生成和参数校验都使用这里的实现。
+
+mod primality;
+pub mod provable;
+pub mod search;
+pub(crate) mod sieve;
+
+pub use primality::{
+    is_prime, is_probable_prime, is_strong_lucas_probable_prime, is_strong_probable_prime, miller_rabin,
+};
+
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+
+/// 扩展欧几里得算法，返回 (g, x, y)，满足 a·x + b·y = g = gcd(a, b) 且 g >= 0
+pub fn egcd(a: &BigInt, b: &BigInt) -> (BigInt, BigInt, BigInt) {
+    let (mut old_r, mut r) = (a.clone(), b.clone());
+    let (mut old_s, mut s) = (BigInt::one(), BigInt::zero());
+    let (mut old_t, mut t) = (BigInt::zero(), BigInt::one());
+
+    while !r.is_zero() {
+        let quotient = &old_r / &r;
+
+        let new_r = &old_r - &quotient * &r;
+        old_r = std::mem::replace(&mut r, new_r);
+
+        let new_s = &old_s - &quotient * &s;
+        old_s = std::mem::replace(&mut s, new_s);
+
+        let new_t = &old_t - &quotient * &t;
+        old_t = std::mem::replace(&mut t, new_t);
+    }
+
+    if old_r.sign() == Sign::Minus {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+/// 求 a 模 m 的逆元，gcd(a, m) ≠ 1 或 m 为 0 时返回 None
+pub fn mod_inverse(a: &BigUint, m: &BigUint) -> Option<BigUint> {
+    if m.is_zero() {
+        return None;
+    }
+    let m_int = BigInt::from(m.clone());
+    let (g, x, _) = egcd(&BigInt::from(a.clone()), &m_int);
+    if !g.is_one() {
+        return None;
+    }
+    x.mod_floor(&m_int).to_biguint()
+}
+
+/// Jacobi 符号 (a / n)，取值为 -1、0、1；n 必须是正奇数，否则返回 None
+pub fn jacobi(a: &BigInt, n: &BigUint) -> Option<i32> {
+    if n.is_even() {
+        return None;
+    }
+    let mut n = n.clone();
+    let mut a = a.mod_floor(&BigInt::from(n.clone())).to_biguint()?;
+    let mut result = 1;
+
+    while !a.is_zero() {
+        let twos = a.trailing_zeros().unwrap_or(0);
+        a >>= twos;
+        // (2 / n) = -1 当且仅当 n ≡ 3, 5 (mod 8)
+        let n_mod_8 = n.iter_u32_digits().next().unwrap_or(0) & 7;
+        if twos % 2 == 1 && (n_mod_8 == 3 || n_mod_8 == 5) {
+            result = -result;
+        }
+        // 二次互反律
+        std::mem::swap(&mut a, &mut n);
+        let a_mod_4 = a.iter_u32_digits().next().unwrap_or(0) & 3;
+        let n_mod_4 = n.iter_u32_digits().next().unwrap_or(0) & 3;
+        if a_mod_4 == 3 && n_mod_4 == 3 {
+            result = -result;
+        }
+        a %= &n;
+    }
+
+    Some(if n.is_one() { result } else { 0 })
+}
+
+/// 中国剩余定理：求解同余方程组 x ≡ a_i (mod m_i)，返回 (x, lcm(m_i))，0 <= x < lcm
+///
+/// 模数不要求两两互素；方程组无解或某个模数为 0 时返回 None，空方程组返回 (0, 1)。
+pub fn crt(congruences: &[(BigUint, BigUint)]) -> Option<(BigUint, BigUint)> {
+    let mut x = BigInt::zero();
+    let mut modulus = BigInt::one();
+
+    for (a, m) in congruences {
+        if m.is_zero() {
+            return None;
+        }
+        let m = BigInt::from(m.clone());
+        let a = BigInt::from(a.clone());
+
+        // x + modulus·t ≡ a (mod m)  =>  (modulus / g)·t ≡ (a - x) / g (mod m / g)
+        let (g, inv, _) = egcd(&modulus, &m);
+        let diff = &a - &x;
+        if !diff.is_multiple_of(&g) {
+            return None;
+        }
+        let m_g = &m / &g;
+        let t = ((diff / &g) * inv).mod_floor(&m_g);
+        x += &modulus * t;
+        modulus *= m_g;
+        x = x.mod_floor(&modulus);
+    }
+
+    Some((x.to_biguint()?, modulus.to_biguint()?))
+}
+
+/// 模素数平方根 (Tonelli-Shanks)：求 r 使 r^2 ≡ a (mod p)，返回两个根中较小的一个
+///
+/// p 必须是奇素数；a 不是二次剩余（或 p 不是素数导致算法失败）时返回 None。
+pub fn sqrt_mod(a: &BigUint, p: &BigUint) -> Option<BigUint> {
+    let one = BigUint::one();
+    if p.is_even() || p <= &one {
+        return None;
+    }
+    let a = a % p;
+    if a.is_zero() {
+        return Some(a);
+    }
+
+    let p1 = p - &one;
+    // Euler 判别法
+    if a.modpow(&(&p1 >> 1), p) != one {
+        return None;
+    }
+
+    let root = if p.iter_u32_digits().next().unwrap_or(0) & 3 == 3 {
+        a.modpow(&((p + &one) >> 2), p)
+    } else {
+        // p - 1 = q·2^s，q 为奇数
+        let s = p1.trailing_zeros()?;
+        let q = &p1 >> s;
+
+        // 任取一个二次非剩余 z
+        let mut z = BigUint::from(2u32);
+        while jacobi(&BigInt::from(z.clone()), p)? != -1 {
+            z += 1u32;
+            if &z >= p {
+                return None;
+            }
+        }
+
+        let mut m = s;
+        let mut c = z.modpow(&q, p);
+        let mut t = a.modpow(&q, p);
+        let mut r = a.modpow(&((&q + &one) >> 1), p);
+        while !t.is_one() {
+            // 最小的 i 使 t^(2^i) = 1
+            let mut i = 0;
+            let mut t2i = t.clone();
+            while !t2i.is_one() {
+                t2i = &t2i * &t2i % p;
+                i += 1;
+                if i >= m {
+                    return None;
+                }
+            }
+            let b = c.modpow(&(BigUint::one() << (m - i - 1)), p);
+            m = i;
+            c = &b * &b % p;
+            t = t * &c % p;
+            r = r * &b % p;
+        }
+        r
+    };
+
+    if &root * &root % p != a {
+        return None;
+    }
+    let other = p - &root;
+    Some(if other < root { other } else { root })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(v: u64) -> BigUint {
+        BigUint::from(v)
+    }
+
+    fn i(v: i64) -> BigInt {
+        BigInt::from(v)
+    }
+
+    #[test]
+    fn egcd_satisfies_bezout() {
+        let cases = [(240, 46), (46, 240), (-240, 46), (240, -46), (0, 7), (7, 0), (17, 1), (1 << 40, 3 << 20)];
+        for (a, b) in cases {
+            let (g, x, y) = egcd(&i(a), &i(b));
+            assert_eq!(g, i(a).gcd(&i(b)), "({}, {})", a, b);
+            assert_eq!(i(a) * x + i(b) * y, g, "({}, {})", a, b);
+        }
+        assert_eq!(egcd(&i(0), &i(0)).0, i(0));
+    }
+
+    #[test]
+    fn mod_inverse_cases() {
+        assert_eq!(mod_inverse(&n(3), &n(11)), Some(n(4)));
+        assert_eq!(mod_inverse(&n(65537), &n(3120)), Some(n(2753)));
+        assert_eq!(mod_inverse(&n(6), &n(9)), None);
+        assert_eq!(mod_inverse(&n(5), &n(0)), None);
+    }
+
+    #[test]
+    fn jacobi_table() {
+        // (a / 15) 与 (a / 21)，a = 0..n
+        let table_15 = [0, 1, 1, 0, 1, 0, 0, -1, 1, 0, 0, -1, 0, -1, -1];
+        let table_21 = [0, 1, -1, 0, 1, 1, 0, 0, -1, 0, -1, -1, 0, -1, 0, 0, 1, 1, 0, -1, 1];
+        for (a, &expected) in table_15.iter().enumerate() {
+            assert_eq!(jacobi(&i(a as i64), &n(15)), Some(expected), "({} / 15)", a);
+        }
+        for (a, &expected) in table_21.iter().enumerate() {
+            assert_eq!(jacobi(&i(a as i64), &n(21)), Some(expected), "({} / 21)", a);
+        }
+
+        // 素数模下与 Euler 判别法一致，负数按模 n 的剩余处理
+        for p in [3u64, 7, 13, 41, 101] {
+            for a in -(p as i64)..2 * p as i64 {
+                let r = i(a).mod_floor(&i(p as i64)).to_biguint().unwrap();
+                let euler = r.modpow(&n((p - 1) / 2), &n(p));
+                let expected = if r.is_zero() { 0 } else if euler.is_one() { 1 } else { -1 };
+                assert_eq!(jacobi(&i(a), &n(p)), Some(expected), "({} / {})", a, p);
+            }
+        }
+
+        assert_eq!(jacobi(&i(1001), &n(9907)), Some(-1));
+        assert_eq!(jacobi(&i(19), &n(45)), Some(1));
+        assert_eq!(jacobi(&i(5), &n(1)), Some(1));
+        assert_eq!(jacobi(&i(3), &n(8)), None);
+    }
+
+    #[test]
+    fn crt_cases() {
+        assert_eq!(crt(&[(n(2), n(3)), (n(3), n(5)), (n(2), n(7))]), Some((n(23), n(105))));
+        // 模数不互素但方程组相容时得到模 lcm 的唯一解
+        assert_eq!(crt(&[(n(1), n(4)), (n(3), n(6))]), Some((n(9), n(12))));
+        // 模数不互素且方程组矛盾
+        assert_eq!(crt(&[(n(0), n(4)), (n(1), n(6))]), None);
+        assert_eq!(crt(&[(n(1), n(10)), (n(2), n(15))]), None);
+        assert_eq!(crt(&[(n(1), n(0))]), None);
+        assert_eq!(crt(&[]), Some((n(0), n(1))));
+    }
+
+    #[test]
+    fn sqrt_mod_small_primes() {
+        // 41 ≡ 1 (mod 8) 走 Tonelli-Shanks，43 ≡ 3 (mod 4) 走直接幂运算
+        for p in [41u64, 43] {
+            for a in 0..p {
+                let is_residue = (0..p).any(|x| x * x % p == a);
+                match sqrt_mod(&n(a), &n(p)) {
+                    Some(r) => {
+                        assert!(is_residue, "{} mod {}", a, p);
+                        assert_eq!(&r * &r % n(p), n(a));
+                        assert!(r <= n(p) - &r || r.is_zero());
+                    }
+                    None => assert!(!is_residue, "{} mod {}", a, p),
+                }
+            }
+        }
+        assert_eq!(sqrt_mod(&n(2), &n(4)), None);
+        assert_eq!(sqrt_mod(&n(1), &n(1)), None);
+    }
+
+    #[test]
+    fn sqrt_mod_large_primes() {
+        // 2^255 - 19 ≡ 5 (mod 8)；P-224 的 p - 1 含 2^96 因子，Tonelli-Shanks 循环最长
+        let p25519 = (BigUint::one() << 255) - 19u32;
+        let p224 = (BigUint::one() << 224) - (BigUint::one() << 96) + 1u32;
+        for p in [p25519, p224] {
+            let x = BigUint::parse_bytes(b"123456789abcdef0123456789abcdef0123456789abcdef", 16).unwrap();
+            let r = sqrt_mod(&(&x * &x % &p), &p).unwrap();
+            assert_eq!(r, std::cmp::min(x.clone(), &p - &x));
+        }
+        // 对 p ≡ 5 (mod 8)，2 是二次非剩余
+        assert_eq!(sqrt_mod(&n(2), &((BigUint::one() << 255) - 19u32)), None);
+    }
+}
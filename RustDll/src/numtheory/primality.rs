@@ -0,0 +1,222 @@
+//! 素性检测：Miller-Rabin、强 Lucas 检测与 Baillie-PSW
+
+use super::jacobi;
+use super::sieve::{residue, small_primes};
+use num_bigint::{BigInt, BigUint, RandBigInt};
+use num_integer::Integer;
+use num_traits::{One, ToPrimitive, Zero};
+use rand::RngCore;
+
+// Baillie-PSW 前做试除的小素数个数（到 1000 为止的奇素数）
+const TRIAL_DIVISION_PRIMES: usize = 167;
+
+/// 以 base 为底的强概率素数检测（Miller-Rabin 的单轮），n 必须是大于 3 的奇数
+pub fn is_strong_probable_prime(n: &BigUint, base: &BigUint) -> bool {
+    let one = BigUint::one();
+    let n1 = n - &one;
+    let s = n1.trailing_zeros().unwrap_or(0);
+    let d = &n1 >> s;
+
+    let mut x = base.modpow(&d, n);
+    if x.is_one() || x == n1 {
+        return true;
+    }
+    for _ in 1..s {
+        x = &x * &x % n;
+        if x == n1 {
+            return true;
+        }
+        if x.is_one() {
+            return false;
+        }
+    }
+    false
+}
+
+/// Miller-Rabin 检测，rounds 轮随机底数
+pub fn miller_rabin<R: RngCore + ?Sized>(n: &BigUint, rounds: usize, rng: &mut R) -> bool {
+    if let Some(small) = small_case(n) {
+        return small;
+    }
+    let two = BigUint::from(2u8);
+    let n2 = n - 1u32;
+    (0..rounds).all(|_| is_strong_probable_prime(n, &rng.gen_biguint_range(&two, &n2)))
+}
+
+/// 强 Lucas 概率素数检测 (FIPS 186-5 B.3.3)，参数按 Selfridge 方法 A 选取：
+/// D 为 5, -7, 9, -11, ... 中第一个使 Jacobi(D, n) = -1 的值，P = 1，Q = (1 - D) / 4
+pub fn is_strong_lucas_probable_prime(n: &BigUint) -> bool {
+    if let Some(small) = small_case(n) {
+        return small;
+    }
+    // 完全平方数找不到合适的 D
+    let root = n.sqrt();
+    if &root * &root == *n {
+        return false;
+    }
+
+    let n_int = BigInt::from(n.clone());
+    let mut d = BigInt::from(5);
+    loop {
+        match jacobi(&d, n) {
+            Some(-1) => break,
+            Some(0) if d.magnitude() != n => return false,
+            _ => {}
+        }
+        d = if d.sign() == num_bigint::Sign::Minus { -d + 2 } else { -(d + 2i32) };
+    }
+
+    // 所有运算在模 n 的剩余类中进行
+    let d_mod = d.mod_floor(&n_int).to_biguint().unwrap_or_default();
+    let q_mod = ((BigInt::one() - &d) / 4i32).mod_floor(&n_int).to_biguint().unwrap_or_default();
+    let half = |x: BigUint| if x.is_odd() { (x + n) >> 1 } else { x >> 1 };
+
+    // n + 1 = k·2^s，k 为奇数
+    let n_plus_1 = n + 1u32;
+    let s = n_plus_1.trailing_zeros().unwrap_or(0);
+    let k = &n_plus_1 >> s;
+
+    // 从 U_1 = 1, V_1 = P = 1 出发按 k 的二进制位倍增
+    let mut u = BigUint::one();
+    let mut v = BigUint::one();
+    let mut qk = q_mod.clone();
+    for i in (0..k.bits() - 1).rev() {
+        u = &u * &v % n;
+        v = (&v * &v + n * 2u32 - (&qk << 1) % n) % n;
+        qk = &qk * &qk % n;
+        if k.bit(i) {
+            let u_next = half((&u + &v) % n);
+            v = half((&d_mod * &u + &v) % n);
+            u = u_next;
+            qk = &qk * &q_mod % n;
+        }
+    }
+
+    if u.is_zero() || v.is_zero() {
+        return true;
+    }
+    for _ in 1..s {
+        v = (&v * &v + n * 2u32 - (&qk << 1) % n) % n;
+        if v.is_zero() {
+            return true;
+        }
+        qk = &qk * &qk % n;
+    }
+    false
+}
+
+/// Baillie-PSW 检测：小素数试除 + 底为 2 的强概率素数检测 + 强 Lucas 检测
+///
+/// 目前没有已知的 Baillie-PSW 伪素数，低于 2^64 的整数结果是确定的。
+pub fn is_probable_prime(n: &BigUint) -> bool {
+    if let Some(small) = small_case(n) {
+        return small;
+    }
+    let digits = n.to_u64_digits();
+    if small_primes()[..TRIAL_DIVISION_PRIMES].iter().any(|&p| residue(&digits, p) == 0) {
+        return false;
+    }
+    is_strong_probable_prime(n, &BigUint::from(2u8)) && is_strong_lucas_probable_prime(n)
+}
+
+/// 密钥生成和校验使用的素性检测：Baillie-PSW 之后再做 rounds 轮随机底数 Miller-Rabin
+pub fn is_prime<R: RngCore + ?Sized>(n: &BigUint, rounds: usize, rng: &mut R) -> bool {
+    is_probable_prime(n) && miller_rabin(n, rounds, rng)
+}
+
+// 小于 2^16 的数直接查表，偶数直接判定；其余返回 None 交给后续检测
+fn small_case(n: &BigUint) -> Option<bool> {
+    if let Some(small) = n.to_u32().filter(|&v| v < 1 << 16) {
+        return Some(small == 2 || small_primes().binary_search(&small).is_ok());
+    }
+    if n.is_even() {
+        return Some(false);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::OsRng;
+
+    fn n(v: u64) -> BigUint {
+        BigUint::from(v)
+    }
+
+    // 埃氏筛，作为小范围内的参照
+    fn sieve(limit: usize) -> Vec<bool> {
+        let mut is_prime = vec![true; limit];
+        is_prime[0] = false;
+        is_prime[1] = false;
+        let mut i = 2;
+        while i * i < limit {
+            if is_prime[i] {
+                (i * i..limit).step_by(i).for_each(|j| is_prime[j] = false);
+            }
+            i += 1;
+        }
+        is_prime
+    }
+
+    #[test]
+    fn agrees_with_sieve() {
+        // 上限超过 2^16，查表之外的数也要走试除 + 强概率素数 + 强 Lucas 的完整流程
+        let reference = sieve(150_000);
+        for (v, &expected) in reference.iter().enumerate() {
+            assert_eq!(is_probable_prime(&n(v as u64)), expected, "{}", v);
+        }
+    }
+
+    #[test]
+    fn rejects_strong_pseudoprimes_to_base_2() {
+        // OEIS A001262；3825123056546413051 对前 9 个素数底都是强伪素数，且没有小于 1000 的因子
+        let two = n(2);
+        for v in [2047, 3215031751, 27278026129, 65700513721, 3825123056546413051] {
+            assert!(is_strong_probable_prime(&n(v), &two), "{}", v);
+            assert!(!is_probable_prime(&n(v)), "{}", v);
+            assert!(!is_prime(&n(v), 20, &mut OsRng), "{}", v);
+        }
+        for base in [3u32, 5, 7, 11, 13, 17, 19, 23] {
+            assert!(is_strong_probable_prime(&n(3825123056546413051), &BigUint::from(base)));
+        }
+    }
+
+    #[test]
+    fn rejects_strong_lucas_pseudoprimes() {
+        // OEIS A217255，小于 2^16 的部分走查表
+        for v in [5459, 5777, 10877, 16109, 18971] {
+            assert!(!is_probable_prime(&n(v)), "{}", v);
+        }
+        // 这些数超出查表范围，强 Lucas 检测单独会误判，需要底为 2 的检测把它们排除
+        for v in [75077, 97439, 100127, 1711469, 2263127, 2518889] {
+            assert!(is_strong_lucas_probable_prime(&n(v)), "{}", v);
+            assert!(!is_strong_probable_prime(&n(v), &n(2)), "{}", v);
+            assert!(!is_probable_prime(&n(v)), "{}", v);
+        }
+    }
+
+    #[test]
+    fn rejects_carmichael_numbers() {
+        // 后四个是 (6k+1)(12k+1)(18k+1) 形式，因子都大于 1000，试除不起作用；
+        // 252601、314821、27278026129、65700513721 同时还是底 2 的强伪素数
+        for v in [561, 1105, 1729, 2465, 2821, 6601, 8911, 252601, 314821, 9624742921, 11346205609, 27278026129, 65700513721]
+        {
+            assert!(!is_probable_prime(&n(v)), "{}", v);
+            assert!(!miller_rabin(&n(v), 20, &mut OsRng), "{}", v);
+        }
+    }
+
+    #[test]
+    fn accepts_large_primes() {
+        let mersenne_127 = (BigUint::one() << 127) - 1u32;
+        let p25519 = (BigUint::one() << 255) - 19u32;
+        for p in [mersenne_127, p25519, n(18446744073709551557)] {
+            assert!(is_strong_lucas_probable_prime(&p));
+            assert!(is_probable_prime(&p));
+            assert!(is_prime(&p, 20, &mut OsRng));
+        }
+        // 完全平方数不是强 Lucas 概率素数
+        assert!(!is_strong_lucas_probable_prime(&(n(65537) * n(65537))));
+    }
+}
@@ -0,0 +1,236 @@
+//! 可证明素数构造：Shawe-Taylor 随机素数 (FIPS 186-5 B.9) 与基于辅助素数的构造 (FIPS 186-5 B.10)
+//!
+//! 所有随机性都来自对种子的哈希，输出的种子和计数器足以让第三方重新构造并核对素数。
+//! 种子按大端整数做加法，结果保持原长度（模 2^seedlen）。
+
+use super::sieve::small_primes;
+use crate::hash::HashAlgorithm;
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::{One, ToPrimitive, Zero};
+
+// 小于该位数的素数直接试除，不再递归
+const TRIAL_DIVISION_BITS: usize = 33;
+
+/// 可证明素数及其生成状态
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProvablePrime {
+    /// 构造得到的素数
+    pub prime: BigUint,
+    /// 构造结束时的种子，作为下一次构造的输入
+    pub seed: Vec<u8>,
+    /// 生成计数器（尝试过的候选个数）
+    pub counter: usize,
+}
+
+/// Shawe-Taylor 随机素数 (FIPS 186-5 B.9 ST_Random_Prime)，生成恰好 length 位的可证明素数
+///
+/// length 小于 2、种子为空或计数器超过上限时返回 None。
+pub fn st_random_prime(hash: HashAlgorithm, length: usize, input_seed: &[u8]) -> Option<ProvablePrime> {
+    if length < 2 || input_seed.is_empty() || hash.is_xof() {
+        return None;
+    }
+    if length >= TRIAL_DIVISION_BITS {
+        return st_random_prime_recursive(hash, length, input_seed);
+    }
+
+    // 步骤 3-13：小素数直接由种子哈希得到候选，试除判定
+    let mut prime_seed = input_seed.to_vec();
+    let mut counter = 0;
+    let top = BigUint::one() << (length - 1);
+    loop {
+        let h0 = hash.digest(&prime_seed);
+        let h1 = hash.digest(&seed_plus(&prime_seed, 1));
+        let xored: Vec<u8> = h0.iter().zip(&h1).map(|(a, b)| a ^ b).collect();
+        let mut c = &top + BigUint::from_bytes_be(&xored) % &top;
+        c.set_bit(0, true);
+
+        counter += 1;
+        prime_seed = seed_plus(&prime_seed, 2);
+
+        if c.to_u64().is_some_and(is_small_prime) {
+            return Some(ProvablePrime { prime: c, seed: prime_seed, counter });
+        }
+        if counter > 4 * length {
+            return None;
+        }
+    }
+}
+
+// 步骤 14-33：先递归构造约一半长度的素数 c0，再在 2tc0 + 1 中寻找可由 Pocklington 定理证明的素数
+fn st_random_prime_recursive(hash: HashAlgorithm, length: usize, input_seed: &[u8]) -> Option<ProvablePrime> {
+    let ProvablePrime { prime: c0, seed: mut prime_seed, mut counter } =
+        st_random_prime(hash, length.div_ceil(2) + 1, input_seed)?;
+
+    let outlen = hash.output_len() * 8;
+    let iterations = length.div_ceil(outlen) - 1;
+    let old_counter = counter;
+
+    let top = BigUint::one() << (length - 1);
+    let x = &top + hash_integer(hash, &mut prime_seed, iterations) % &top;
+
+    let two_c0 = &c0 << 1;
+    let mut t = x.div_ceil(&two_c0);
+    loop {
+        if &two_c0 * &t + 1u32 > &top << 1 {
+            t = top.div_ceil(&two_c0);
+        }
+        let c = &two_c0 * &t + 1u32;
+        counter += 1;
+
+        let a = hash_integer(hash, &mut prime_seed, iterations);
+        let a = (a % (&c - 3u32)) + 2u32;
+        let z = a.modpow(&(&t << 1), &c);
+        if (&z - 1u32).gcd(&c).is_one() && z.modpow(&c0, &c).is_one() {
+            return Some(ProvablePrime { prime: c, seed: prime_seed, counter });
+        }
+        if counter >= 4 * length + old_counter {
+            return None;
+        }
+        t += 1u32;
+    }
+}
+
+/// 构造 length 位、满足 gcd(p - 1, e) = 1 的可证明素数 (FIPS 186-5 B.10，N1 = N2 = 1)
+///
+/// 结果满足 p >= ⌊√2·2^(length-1)⌋，两个这样的素数相乘恰好得到 2·length 位的模数。
+/// 计数器超过 5·length 时返回 None。
+pub fn provable_prime_construction(
+    hash: HashAlgorithm,
+    length: usize,
+    first_seed: &[u8],
+    e: &BigUint,
+) -> Option<ProvablePrime> {
+    // 步骤 6：p0 的长度约为 length 的一半
+    let ProvablePrime { prime: p0, seed: mut pseed, .. } = st_random_prime(hash, length.div_ceil(2) + 1, first_seed)?;
+
+    let outlen = hash.output_len() * 8;
+    let iterations = length.div_ceil(outlen) - 1;
+    let mut counter = 0;
+
+    let upper = BigUint::one() << length;
+    let lower = (BigUint::one() << (2 * length - 1)).sqrt();
+    let x: BigUint = &lower + hash_integer(hash, &mut pseed, iterations) % (&upper - &lower);
+
+    // N1 = N2 = 1 时 p1 = p2 = 1、y = 1，候选为 p = 2(t - 1)·p0 + 1
+    let two_p0: BigUint = &p0 << 1;
+    let mut t = (x + &two_p0).div_ceil(&two_p0);
+    loop {
+        if &two_p0 * (&t - 1u32) + 1u32 > upper {
+            t = (&lower + &two_p0).div_ceil(&two_p0);
+        }
+        let p = &two_p0 * (&t - 1u32) + 1u32;
+        counter += 1;
+
+        if (&p - 1u32).gcd(e).is_one() {
+            let a = hash_integer(hash, &mut pseed, iterations);
+            let a = (a % (&p - 3u32)) + 2u32;
+            let z = a.modpow(&((&t - 1u32) << 1), &p);
+            if (&z - 1u32).gcd(&p).is_one() && z.modpow(&p0, &p).is_one() {
+                return Some(ProvablePrime { prime: p, seed: pseed, counter });
+            }
+        }
+        if counter >= 5 * length {
+            return None;
+        }
+        t += 1u32;
+    }
+}
+
+/// 由种子得到整数：x = Σ Hash(seed + i)·2^(i·outlen)，i = 0..=iterations，之后 seed 前进 iterations + 1
+pub(crate) fn hash_integer(hash: HashAlgorithm, seed: &mut Vec<u8>, iterations: usize) -> BigUint {
+    let outlen = hash.output_len() * 8;
+    let mut x = BigUint::zero();
+    for i in 0..=iterations {
+        x += BigUint::from_bytes_be(&hash.digest(&seed_plus(seed, i))) << (i * outlen);
+    }
+    *seed = seed_plus(seed, iterations + 1);
+    x
+}
+
+/// seed + i，把种子当作大端整数相加，结果模 2^seedlen 并保持原长度
+pub(crate) fn seed_plus(seed: &[u8], i: usize) -> Vec<u8> {
+    let sum = (BigUint::from_bytes_be(seed) + i).to_bytes_be();
+    let mut out = vec![0u8; seed.len().saturating_sub(sum.len())];
+    out.extend_from_slice(&sum[sum.len().saturating_sub(seed.len())..]);
+    out
+}
+
+// 小于 2^32 的奇数做试除，小素数表覆盖到 2^16
+fn is_small_prime(c: u64) -> bool {
+    if c < 3 || c.is_multiple_of(2) {
+        return c == 2;
+    }
+    small_primes()
+        .iter()
+        .map(|&p| p as u64)
+        .take_while(|&p| p * p <= c)
+        .all(|p| !c.is_multiple_of(p))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::numtheory::is_probable_prime;
+
+    fn seed() -> Vec<u8> {
+        (1..=32).collect()
+    }
+
+    #[test]
+    fn st_random_prime_is_deterministic() {
+        // 种子 01 02 .. 20，SHA-256；期望值由按 FIPS 186-5 B.9 独立实现的脚本得到
+        let cases = [
+            (20, "c9547", 23),
+            (64, "976d15227f877efd", 23),
+            (256, "e3cec5f4f36b8bb872e17d3cffe08488d7ec8def86732eb2066f39b0be42950b", 103),
+        ];
+        for (length, prime, counter) in cases {
+            let result = st_random_prime(HashAlgorithm::Sha256, length, &seed()).unwrap();
+            assert_eq!(result.prime, BigUint::parse_bytes(prime.as_bytes(), 16).unwrap(), "{} bits", length);
+            assert_eq!(result.counter, counter, "{} bits", length);
+            assert_eq!(result.prime.bits() as usize, length);
+            assert!(is_probable_prime(&result.prime));
+            assert_eq!(st_random_prime(HashAlgorithm::Sha256, length, &seed()), Some(result));
+        }
+
+        let mut other = seed();
+        other[31] ^= 1;
+        assert_ne!(
+            st_random_prime(HashAlgorithm::Sha256, 256, &other).unwrap().prime,
+            st_random_prime(HashAlgorithm::Sha256, 256, &seed()).unwrap().prime
+        );
+    }
+
+    #[test]
+    fn st_random_prime_lengths() {
+        for length in [2, 3, 32, 33, 100, 521] {
+            let result = st_random_prime(HashAlgorithm::Sha512, length, &seed()).unwrap();
+            assert_eq!(result.prime.bits() as usize, length);
+            assert!(is_probable_prime(&result.prime), "{} bits", length);
+            assert_eq!(result.seed.len(), 32);
+        }
+        assert_eq!(st_random_prime(HashAlgorithm::Sha256, 1, &seed()), None);
+        assert_eq!(st_random_prime(HashAlgorithm::Sha256, 64, &[]), None);
+        assert_eq!(st_random_prime(HashAlgorithm::Shake128, 64, &seed()), None);
+    }
+
+    #[test]
+    fn provable_prime_construction_bounds() {
+        let e = BigUint::from(65537u32);
+        let first = provable_prime_construction(HashAlgorithm::Sha256, 1024, &seed(), &e).unwrap();
+        let lower = (BigUint::one() << 2047u32).sqrt();
+        assert!(first.prime >= lower);
+        assert_eq!(first.prime.bits(), 1024);
+        assert!((&first.prime - 1u32).gcd(&e).is_one());
+        assert!(is_probable_prime(&first.prime));
+        assert_eq!(provable_prime_construction(HashAlgorithm::Sha256, 1024, &seed(), &e), Some(first));
+    }
+
+    #[test]
+    fn seed_plus_wraps_and_keeps_length() {
+        assert_eq!(seed_plus(&[0x00, 0xff], 1), [0x01, 0x00]);
+        assert_eq!(seed_plus(&[0xff, 0xff], 2), [0x00, 0x01]);
+        assert_eq!(seed_plus(&[0x00, 0x00, 0x01], 0), [0x00, 0x00, 0x01]);
+    }
+}
@@ -0,0 +1,227 @@
+//! 多线程、可取消的素数搜索
+//!
+//! 每个工作线程使用由调用方随机数生成器播种的独立 HMAC-DRBG，各自从不同的随机起点搜索，
+//! 最先找到的结果胜出，其余线程随即停止。进度回调和取消检查都在调用线程上进行，
+//! 回调里可以直接更新界面。
+
+use crate::drbg::HmacDrbg;
+use rand::{CryptoRng, RngCore};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+// 调用线程检查取消标志、报告进度的间隔
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 工作线程数的上限，调用方传入更大的值时按此截断
+pub const MAX_THREADS: usize = 64;
+
+// 每个工作线程 DRBG 的熵输入和 nonce 长度
+const WORKER_ENTROPY_LEN: usize = 32;
+const WORKER_NONCE_LEN: usize = 16;
+
+/// 搜索错误
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchError {
+    /// 调用方取消了搜索
+    Cancelled,
+    /// 一个工作线程都无法创建
+    ThreadSpawnFailed,
+}
+
+impl fmt::Display for SearchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SearchError::Cancelled => write!(f, "操作已取消"),
+            SearchError::ThreadSpawnFailed => write!(f, "无法创建工作线程"),
+        }
+    }
+}
+
+impl std::error::Error for SearchError {}
+
+/// 搜索选项
+#[derive(Default)]
+pub struct SearchOptions<'a> {
+    /// 工作线程数，0 表示使用全部可用的 CPU；不超过可用 CPU 数和 `MAX_THREADS`
+    pub threads: usize,
+    /// 置位后尽快停止搜索并返回 `SearchError::Cancelled`
+    pub cancel: Option<&'a AtomicBool>,
+    /// 进度回调，约每 100 毫秒在调用线程上调用一次，参数为累计检测过的候选个数
+    pub progress: Option<&'a mut dyn FnMut(u64)>,
+    /// 累计检测过的候选个数，同一组选项用于多次搜索（例如 RSA 的 p 和 q）时持续累加
+    pub tested: u64,
+}
+
+impl SearchOptions<'_> {
+    fn is_cancelled(&self) -> bool {
+        self.cancel.is_some_and(|flag| flag.load(Ordering::Relaxed))
+    }
+
+    fn report(&mut self, tested: u64) {
+        if let Some(progress) = self.progress.as_mut() {
+            progress(tested);
+        }
+    }
+}
+
+/// 工作线程看到的搜索状态
+pub struct SearchState {
+    stop: AtomicBool,
+    tested: AtomicU64,
+}
+
+impl SearchState {
+    /// 不会被停止的状态，用于单线程搜索
+    pub(crate) fn new() -> Self {
+        SearchState { stop: AtomicBool::new(false), tested: AtomicU64::new(0) }
+    }
+
+    /// 是否应当放弃当前搜索（其他线程已找到结果或调用方已取消）
+    pub fn is_stopped(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+
+    /// 记录检测了一个候选
+    pub fn record_candidate(&self) {
+        self.tested.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// 在多个线程上运行 search，返回最先得到的结果
+///
+/// search 在 `SearchState::is_stopped` 为真时应尽快返回 None。
+///
+/// 部分线程创建失败时用已创建的线程继续搜索，一个都没有创建成功时返回 `SearchError::ThreadSpawnFailed`。
+pub(crate) fn parallel_search<T, R, F>(options: &mut SearchOptions<'_>, rng: &mut R, search: F) -> Result<T, SearchError>
+where
+    T: Send,
+    R: RngCore + CryptoRng + ?Sized,
+    F: Fn(&mut HmacDrbg, &SearchState) -> Option<T> + Sync,
+{
+    if options.is_cancelled() {
+        return Err(SearchError::Cancelled);
+    }
+    let available = thread::available_parallelism().map_or(1, |n| n.get()).min(MAX_THREADS);
+    let threads = match options.threads {
+        0 => available,
+        n => n.min(available),
+    };
+    let workers: Vec<HmacDrbg> = (0..threads).map(|i| worker_rng(rng, i)).collect();
+
+    let state = SearchState::new();
+    let base = options.tested;
+    let (sender, receiver) = mpsc::channel();
+    let result = thread::scope(|scope| {
+        let mut spawned = 0;
+        for mut worker in workers {
+            let sender = sender.clone();
+            let (state, search) = (&state, &search);
+            let handle = thread::Builder::new().spawn_scoped(scope, move || {
+                if let Some(found) = search(&mut worker, state) {
+                    let _ = sender.send(found);
+                }
+            });
+            if handle.is_err() {
+                break;
+            }
+            spawned += 1;
+        }
+        drop(sender);
+        if spawned == 0 {
+            return Err(SearchError::ThreadSpawnFailed);
+        }
+
+        let result = loop {
+            match receiver.recv_timeout(POLL_INTERVAL) {
+                Ok(found) => break Ok(found),
+                Err(RecvTimeoutError::Timeout) => {
+                    options.report(base + state.tested.load(Ordering::Relaxed));
+                    if options.is_cancelled() {
+                        break Err(SearchError::Cancelled);
+                    }
+                }
+                // 所有线程都放弃了搜索，只可能是被停止
+                Err(RecvTimeoutError::Disconnected) => break Err(SearchError::Cancelled),
+            }
+        };
+        state.stop.store(true, Ordering::Relaxed);
+        result
+    });
+
+    options.tested = base + state.tested.load(Ordering::Relaxed);
+    options.report(options.tested);
+    result
+}
+
+// 由调用方的随机数生成器为第 index 个工作线程播种
+fn worker_rng<R: RngCore + CryptoRng + ?Sized>(rng: &mut R, index: usize) -> HmacDrbg {
+    let mut entropy = [0u8; WORKER_ENTROPY_LEN];
+    let mut nonce = [0u8; WORKER_NONCE_LEN];
+    rng.fill_bytes(&mut entropy);
+    rng.fill_bytes(&mut nonce);
+    HmacDrbg::new(&entropy, &nonce, &(index as u64).to_be_bytes()).expect("entropy length is sufficient")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ElGamal::elgamal::ElGamalKeys;
+    use crate::Rsa::{KeyError, RsaPrivateKey};
+    use num_bigint::BigUint;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn preset_cancel_flag_returns_cancelled() {
+        let cancel = AtomicBool::new(true);
+        let mut options = SearchOptions { threads: 2, cancel: Some(&cancel), ..Default::default() };
+        let e = BigUint::from(65537u32);
+        assert_eq!(
+            RsaPrivateKey::generate_parallel(1024, &e, &mut options, &mut OsRng).err(),
+            Some(KeyError::Cancelled)
+        );
+        assert_eq!(
+            ElGamalKeys::generate_parallel(256, &mut options, &mut OsRng).err(),
+            Some(SearchError::Cancelled)
+        );
+    }
+
+    #[test]
+    fn cancel_from_progress_stops_search() {
+        let cancel = AtomicBool::new(false);
+        let mut calls = 0;
+        let mut progress = |_| {
+            calls += 1;
+            cancel.store(true, Ordering::Relaxed);
+        };
+        let tested = {
+            let mut options =
+                SearchOptions { threads: 2, cancel: Some(&cancel), progress: Some(&mut progress), tested: 0 };
+            // 永远找不到结果的搜索，只能靠取消结束
+            let result: Result<(), _> = parallel_search(&mut options, &mut OsRng, |_, state| {
+                while !state.is_stopped() {
+                    state.record_candidate();
+                    thread::yield_now();
+                }
+                None
+            });
+            assert_eq!(result, Err(SearchError::Cancelled));
+            options.tested
+        };
+        assert!(tested > 0);
+        assert!(calls >= 1);
+    }
+
+    #[test]
+    fn thread_count_is_clamped() {
+        let mut options = SearchOptions { threads: usize::MAX, ..Default::default() };
+        let found = parallel_search(&mut options, &mut OsRng, |worker, state| {
+            state.record_candidate();
+            Some(worker.next_u32())
+        });
+        assert!(found.is_ok());
+        assert!(options.tested as usize <= MAX_THREADS);
+    }
+}
@@ -0,0 +1,187 @@
+//! 素数候选的增量筛
+//!
+//! 从随机奇数 base 出发，依次考察 base、base + 2、base + 4 ...，
+//! 先用小素数表筛掉含小因子的候选，只对幸存者做 Miller-Rabin。
+//! base 对每个小素数的余数只计算一次，之后按窗口整体标记，代价远小于逐个做模幂。
+
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+use std::sync::OnceLock;
+
+// 小素数表的上界（不含 2）
+const SMALL_PRIME_BOUND: u32 = 1 << 16;
+
+// 每个窗口考察的奇数个数
+const WINDOW: usize = 4096;
+
+/// 3 到 2^16 之间的奇素数，首次使用时用埃氏筛生成
+pub(crate) fn small_primes() -> &'static [u32] {
+    static PRIMES: OnceLock<Vec<u32>> = OnceLock::new();
+    PRIMES.get_or_init(|| {
+        let bound = SMALL_PRIME_BOUND as usize;
+        let mut composite = vec![false; bound];
+        let mut primes = Vec::new();
+        for i in 3..bound {
+            if composite[i] || i % 2 == 0 {
+                continue;
+            }
+            primes.push(i as u32);
+            for j in (i * i..bound).step_by(2 * i) {
+                composite[j] = true;
+            }
+        }
+        primes
+    })
+}
+
+/// 大整数模小素数，digits 为 `to_u64_digits` 的结果（低位在前），按 64 位字从高到低折叠
+pub(crate) fn residue(digits: &[u64], p: u32) -> u32 {
+    let p = p as u128;
+    digits.iter().rev().fold(0u128, |r, &d| ((r << 64) | d as u128) % p) as u32
+}
+
+/// 从奇数 base 开始递增的候选序列，只产出没有小素因子的数
+///
+/// safe 为 true 时同时要求 2c + 1 没有小素因子（用于搜索安全素数 p = 2c + 1 中的 c）。
+/// 只使用小于 base 的小素数，因此 base 很小时也不会误筛掉素数本身。序列是无限的，
+/// 由调用方在候选超出所需位数时停止。
+pub(crate) struct Candidates {
+    base: BigUint,
+    safe: bool,
+    primes: &'static [u32],
+    residues: Vec<u32>,
+    // 当前窗口起点相对 base 的偏移（偶数）
+    offset: u64,
+    composite: Vec<bool>,
+    index: usize,
+}
+
+impl Candidates {
+    pub(crate) fn new(base: BigUint, safe: bool) -> Self {
+        debug_assert!(base.bit(0), "sieve base must be odd");
+        let all = small_primes();
+        let count = match base.to_u32() {
+            Some(b) => all.partition_point(|&p| p < b),
+            None => all.len(),
+        };
+        let primes = &all[..count];
+        let digits = base.to_u64_digits();
+        let residues = primes.iter().map(|&p| residue(&digits, p)).collect();
+
+        let mut candidates = Candidates {
+            base,
+            safe,
+            primes,
+            residues,
+            offset: 0,
+            composite: vec![false; WINDOW],
+            index: 0,
+        };
+        candidates.sieve_window();
+        candidates
+    }
+
+    // 标记当前窗口中 c = base + offset + 2j 含小因子（或 2c + 1 含小因子）的位置 j
+    fn sieve_window(&mut self) {
+        self.composite.iter_mut().for_each(|c| *c = false);
+        for (&p, &r) in self.primes.iter().zip(&self.residues) {
+            let p64 = p as u64;
+            // 2 模 p 的逆
+            let inv2 = p64.div_ceil(2);
+            let start = (r as u64 + self.offset % p64) % p64;
+
+            // c ≡ 0 (mod p)  <=>  j ≡ -start / 2
+            mark(&mut self.composite, (p64 - start) % p64 * inv2 % p64, p);
+            if self.safe {
+                // 2c + 1 ≡ 0 (mod p)  <=>  c ≡ (p - 1) / 2  <=>  j ≡ ((p - 1) / 2 - start) / 2
+                mark(&mut self.composite, ((p64 - 1) / 2 + p64 - start) % p64 * inv2 % p64, p);
+            }
+        }
+    }
+}
+
+// 从位置 first 开始每隔 p 个标记一次
+fn mark(composite: &mut [bool], first: u64, p: u32) {
+    for j in (first as usize..composite.len()).step_by(p as usize) {
+        composite[j] = true;
+    }
+}
+
+impl Iterator for Candidates {
+    type Item = BigUint;
+
+    fn next(&mut self) -> Option<BigUint> {
+        loop {
+            while self.index < WINDOW {
+                let j = self.index;
+                self.index += 1;
+                if !self.composite[j] {
+                    return Some(&self.base + (self.offset + 2 * j as u64));
+                }
+            }
+            self.offset += 2 * WINDOW as u64;
+            self.index = 0;
+            self.sieve_window();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 参照实现：逐个试除，c 没有小于 bound 的奇素因子（safe 时 2c + 1 也没有）
+    fn survives(c: u128, safe: bool, primes: &[u32]) -> bool {
+        primes.iter().all(|&p| {
+            let p = p as u128;
+            !(c.is_multiple_of(p) || safe && (2 * c + 1).is_multiple_of(p))
+        })
+    }
+
+    // 取出所有不超过 limit 的候选，与逐个试除的结果比较；limit 跨过两个窗口边界
+    fn check(base: u128, safe: bool) {
+        let primes = Candidates::new(BigUint::from(base), safe).primes;
+        let limit = base + 2 * (2 * WINDOW as u128 + 500);
+        let expected: Vec<u128> = (base..=limit).step_by(2).filter(|&c| survives(c, safe, primes)).collect();
+        let actual: Vec<u128> = Candidates::new(BigUint::from(base), safe)
+            .map(|c| c.to_u128().unwrap())
+            .take_while(|&c| c <= limit)
+            .collect();
+        assert_eq!(actual, expected, "base {} safe {}", base, safe);
+    }
+
+    #[test]
+    fn small_base_keeps_primes() {
+        // base 很小时只用小于 base 的素数筛，区间内的素数本身都应保留
+        for safe in [false, true] {
+            check(1001, safe);
+            let primes = Candidates::new(BigUint::from(1001u32), safe).primes;
+            assert_eq!(primes.last(), Some(&997));
+        }
+        let found: Vec<BigUint> = Candidates::new(BigUint::from(1001u32), false).take(5).collect();
+        assert_eq!(found, [1009u32, 1013, 1019, 1021, 1031].map(BigUint::from));
+        // base = 3 时没有可用的小素数，序列就是全部奇数
+        let odd: Vec<BigUint> = Candidates::new(BigUint::from(3u32), false).take(4).collect();
+        assert_eq!(odd, [3u32, 5, 7, 9].map(BigUint::from));
+    }
+
+    #[test]
+    fn large_base_uses_all_small_primes() {
+        let base = (1u128 << 100) + 1;
+        for safe in [false, true] {
+            check(base, safe);
+        }
+        assert_eq!(Candidates::new(BigUint::from(base), false).primes.len(), small_primes().len());
+    }
+
+    #[test]
+    fn residue_matches_biguint() {
+        let n = BigUint::parse_bytes(b"123456789abcdef0fedcba98765432100123456789abcdef", 16).unwrap();
+        let digits = n.to_u64_digits();
+        for &p in small_primes().iter().step_by(97) {
+            assert_eq!(residue(&digits, p), (&n % p).to_u32().unwrap());
+        }
+        assert_eq!(small_primes()[..5], [3, 5, 7, 11, 13]);
+        assert_eq!(small_primes().last(), Some(&65521));
+    }
+}
@@ -10,6 +10,7 @@
  *
  * 依赖库:
  * - NTL（Number Theory Library），用于大数操作。
+ * - RustDll（crypto.h），仅使用其中的 CRYPTO_HASH_* 算法编号。
  */
 
 #ifndef CRYPTOMESSAGESYSTEM_CERTIFICATE_H
@@ -22,11 +23,15 @@ class person;
 #include <crypto/sha256.h>
 #include <crypto/ElGamal.h>
 #include <NTL/ZZ.h>
+#include <crypto.h>
 
 class certificate {
-    // 证书的 Hash 结果，通常用于保证证书内容的完整性
+    // 证书的 Hash 结果（十六进制），通常用于保证证书内容的完整性
     std::string HashRes;
 
+    // 计算 HashRes 使用的摘要算法，CRYPTO_HASH_* 编号
+    int32_t HashAlg = CRYPTO_HASH_SHA256;
+
     // 持证人姓名
     std::string name;
 
@@ -46,8 +51,11 @@ public:
     /**
      * @brief 使用 person 对象初始化证书。
      * @param p 包含证书持有者信息的 person 对象。
+     * @param hashAlgorithm 证书摘要算法，CRYPTO_HASH_* 编号，默认 SHA-256。
+     *        目前只支持 CRYPTO_HASH_SHA256；SHA-3 等算法需要包含 crypto_hash 的 Crypto.dll。
+     * @throw std::invalid_argument 算法编号不受支持，或者为不抗碰撞的 CRYPTO_HASH_SHA1。
      */
-    certificate(person p);
+    certificate(person p, int32_t hashAlgorithm = CRYPTO_HASH_SHA256);
 
     /**
      * @brief 默认构造函数。
@@ -91,6 +99,12 @@ public:
      */
     std::string getHashRes() { return HashRes; }
 
+    /**
+     * @brief 获取计算证书 Hash 使用的摘要算法。
+     * @return CRYPTO_HASH_* 算法编号。
+     */
+    int32_t getHashAlgorithm() { return HashAlg; }
+
     /**
      * @brief 获取证书中的 RSA 公钥。
      * @return 包含 RSA 公钥的大数向量。
@@ -3,8 +3,22 @@
 //
 #include <certificate/certificate.h>
 #include "certificate/person.h"
+#include <stdexcept>
 
-certificate::certificate(person p):RSA_pub_key(2), EL_pub_key(3), name(p.getName()), Issuer("Admin"){
+// 计算证书摘要，返回小写十六进制字符串。
+// 仓库内的 Crypto.dll 尚未包含 crypto_hash，重新编译之前只支持 C++ 的 sha256()，其他算法编号一律拒绝。
+static std::string hashHex(int32_t algorithm, const std::string& input) {
+    if (algorithm == CRYPTO_HASH_SHA1) {
+        throw std::invalid_argument("SHA-1 is not collision resistant and cannot be used for certificates");
+    }
+    if (algorithm != CRYPTO_HASH_SHA256) {
+        throw std::invalid_argument("Unsupported certificate hash algorithm");
+    }
+    return sha256(input);
+}
+
+certificate::certificate(person p, int32_t hashAlgorithm)
+    : HashAlg(hashAlgorithm), name(p.getName()), Issuer("Admin"), RSA_pub_key(2), EL_pub_key(3){
 
     RSA_pub_key.push_back(p.getRsaPubKey()); // e
     RSA_pub_key.push_back(p.getRsaMoudle()); // n
@@ -28,7 +42,7 @@ certificate::certificate(person p):RSA_pub_key(2), EL_pub_key(3), name(p.getName
     std::string hString = name + Issuer + s;
 
 
-    HashRes = sha256(hString);
+    HashRes = hashHex(HashAlg, hString);
 
 }
 void certificate::cerSignIn(ElGamal elGamal){
@@ -64,6 +78,8 @@ std::vector<unsigned char> certificate::serialize() const {
     }
     append_string(ZZToString(EL_sign_res.first));
     append_string(ZZToString(EL_sign_res.second));
+    // 摘要算法放在末尾，旧版本的证书没有这一项
+    append_string(std::to_string(HashAlg));
 
     return buffer;
 }
@@ -125,6 +141,14 @@ void certificate::deserialize(const std::vector<unsigned char>& buffer) {
         EL_sign_res.second = StringToZZ(s);
     }
 
+    // 旧版本的证书没有摘要算法，按 SHA-256 处理
+    HashAlg = CRYPTO_HASH_SHA256;
+    if (offset < buffer.size()) {
+        std::string s;
+        read_string(s);
+        HashAlg = std::stoi(s);
+    }
+
 }
 
 